@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use crate::{AuthorityRecord, GatekeeperError, Role, RoleTable, SignerSet};
+
+/// Returns the key currently allowed to write under `namespace`.
+///
+/// `authority_record` must be the AuthorityRecord PDA of `namespace` (enforced by the
+/// seeds constraint on every write instruction). Until a record is created the
+/// namespace key itself is the active authority.
+pub fn active_authority(namespace: &Pubkey, authority_record: &AccountInfo, program_id: &Pubkey) -> Result<Pubkey> {
+    if authority_record.data_is_empty() || authority_record.owner != program_id {
+        return Ok(*namespace);
+    }
+
+    let data = authority_record.try_borrow_data()?;
+    let record = AuthorityRecord::try_deserialize(&mut &data[..])?;
+    Ok(record.authority)
+}
+
+/// Ensures `rent_recipient` is the active authority of `namespace`.
+///
+/// Closing instructions refund rent here rather than to whoever authorised the close,
+/// so a Closer or signer-set member cannot collect the namespace's rent.
+pub fn require_rent_recipient(
+    namespace: &Pubkey,
+    authority_record: &AccountInfo,
+    rent_recipient: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<()> {
+    if *rent_recipient.key != active_authority(namespace, authority_record, program_id)? {
+        return err!(GatekeeperError::InvalidAuthority);
+    }
+    Ok(())
+}
+
+/// Ensures the active authority of `namespace` signed the transaction.
+///
+/// Returns the signing account so handlers can use it as the rent payer or refund
+/// destination. The namespace key only counts while it is still the active authority,
+/// so a rotated-out key loses write access immediately.
+pub fn require_active_authority<'a, 'info>(
+    namespace: &'a AccountInfo<'info>,
+    authority_record: &AccountInfo<'info>,
+    operator: Option<&'a Signer<'info>>,
+    program_id: &Pubkey,
+) -> Result<&'a AccountInfo<'info>> {
+    let active = active_authority(namespace.key, authority_record, program_id)?;
+
+    if namespace.is_signer && *namespace.key == active {
+        return Ok(namespace);
+    }
+
+    match operator {
+        Some(operator) if operator.key() == active => Ok(&**operator),
+        _ => err!(GatekeeperError::InvalidAuthority),
+    }
+}
+
+/// Authorises a write by either the active authority or, when a role table is
+/// supplied, an `operator` holding one of `roles`.
+///
+/// Returns the signing account, as `require_active_authority` does.
+pub fn require_authority_or_role<'a, 'info>(
+    namespace: &'a AccountInfo<'info>,
+    authority_record: &AccountInfo<'info>,
+    operator: Option<&'a Signer<'info>>,
+    role_table: Option<&RoleTable>,
+    roles: &[Role],
+    program_id: &Pubkey,
+) -> Result<&'a AccountInfo<'info>> {
+    let authority = require_active_authority(namespace, authority_record, operator, program_id);
+
+    match (role_table, operator) {
+        (Some(role_table), Some(operator)) if authority.is_err() => {
+            if !role_table.has_any_role(operator.key, roles) {
+                return err!(GatekeeperError::MissingRole);
+            }
+            Ok(&**operator)
+        }
+        _ => authority,
+    }
+}
+
+/// Ensures at least `threshold` distinct members of `signer_set` signed the transaction.
+///
+/// Member signers are read from `remaining_accounts`; an account listed twice is only
+/// counted once. Returns the first signing member.
+pub fn require_signer_set_quorum<'a, 'info>(
+    signer_set: &SignerSet,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<&'a AccountInfo<'info>> {
+    // One bit per member index; MAX_SIGNER_SET_MEMBERS fits comfortably in a u32
+    let mut counted: u32 = 0;
+    let mut first_signer = None;
+
+    for account in remaining_accounts.iter().filter(|account| account.is_signer) {
+        if let Some(index) = signer_set.members.iter().position(|member| member == account.key) {
+            if counted & (1 << index) == 0 {
+                counted |= 1 << index;
+                first_signer.get_or_insert(account);
+            }
+        }
+    }
+
+    if counted.count_ones() < u32::from(signer_set.threshold) {
+        return err!(GatekeeperError::SignerSetQuorumNotMet);
+    }
+
+    first_signer.ok_or_else(|| error!(GatekeeperError::SignerSetQuorumNotMet))
+}
+
+/// Authorises a write by the active authority, an `operator` holding one of `roles`,
+/// or, when a signer set is supplied, a quorum of its members.
+///
+/// Returns the account that should receive any rent refund.
+#[allow(clippy::too_many_arguments)]
+pub fn require_authority_or_quorum<'a, 'info>(
+    namespace: &'a AccountInfo<'info>,
+    authority_record: &AccountInfo<'info>,
+    operator: Option<&'a Signer<'info>>,
+    role_table: Option<&RoleTable>,
+    roles: &[Role],
+    signer_set: Option<&SignerSet>,
+    remaining_accounts: &'a [AccountInfo<'info>],
+    program_id: &Pubkey,
+) -> Result<&'a AccountInfo<'info>> {
+    let authority = require_authority_or_role(namespace, authority_record, operator, role_table, roles, program_id);
+
+    match signer_set {
+        Some(signer_set) if authority.is_err() => require_signer_set_quorum(signer_set, remaining_accounts),
+        _ => authority,
+    }
+}
@@ -0,0 +1,88 @@
+//! Bit-level helpers over the bitmap region of `SandwichValidators` accounts.
+//!
+//! Bit `i` of a bitmap is bit `i % 8` of byte `i / 8`, so slot `epoch_start + i`
+//! maps to bit `i`.
+
+/// Whether bit `bit` is set; bits past the end of `bitmap` (not yet uploaded) are ungated.
+pub fn is_gated(bitmap: &[u8], bit: usize) -> bool {
+    bitmap.get(bit >> 3).is_some_and(|byte| (byte >> (bit & 7)) & 1 == 1)
+}
+
+/// Number of gated (set) bits in `bitmap`.
+pub fn count_gated_slots(bitmap: &[u8]) -> u32 {
+    let mut chunks = bitmap.chunks_exact(8);
+    let mut count: u32 = chunks
+        .by_ref()
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()).count_ones())
+        .sum();
+    count += chunks.remainder().iter().map(|byte| byte.count_ones()).sum::<u32>();
+    count
+}
+
+/// Gates (`gated`) or ungates every bit in `[start, start + len)`, filling whole bytes
+/// where the range covers them. Returns the number of bits that changed.
+///
+/// The caller must have checked that the range is non-empty and lies within `bitmap`.
+pub fn fill_range(bitmap: &mut [u8], start: usize, len: usize, gated: bool) -> u32 {
+    let end = start + len;
+    let first_byte = start / 8;
+    let last_byte = (end - 1) / 8;
+    let head_mask = 0xffu8 << (start % 8);
+    let tail_mask = 0xffu8 >> (7 - (end - 1) % 8);
+
+    let apply = |byte: &mut u8, mask: u8| -> u32 {
+        let before = *byte;
+        if gated {
+            *byte |= mask;
+        } else {
+            *byte &= !mask;
+        }
+        (before ^ *byte).count_ones()
+    };
+
+    if first_byte == last_byte {
+        return apply(&mut bitmap[first_byte], head_mask & tail_mask);
+    }
+
+    let mut changed = apply(&mut bitmap[first_byte], head_mask);
+
+    // Whole bytes between the partial edges
+    let middle = &mut bitmap[first_byte + 1..last_byte];
+    let set = count_gated_slots(middle);
+    changed += if gated { middle.len() as u32 * 8 - set } else { set };
+    middle.fill(if gated { 0xff } else { 0 });
+
+    changed + apply(&mut bitmap[last_byte], tail_mask)
+}
+
+/// Mask of the bits of byte `byte_index` that lie below bit `limit`, i.e. inside an
+/// epoch of `limit` bits. Bits at or past `limit` must never be set.
+pub fn bits_below_mask(byte_index: usize, limit: usize) -> u8 {
+    let first = byte_index * 8;
+    if first >= limit {
+        0
+    } else if limit - first >= 8 {
+        0xff
+    } else {
+        0xff >> (8 - (limit - first))
+    }
+}
+
+/// First ungated bit in `[from, end)`, treating bits past the end of `bitmap` as ungated
+/// (not yet uploaded). Fully gated bytes are skipped whole.
+pub fn next_ungated(bitmap: &[u8], from: usize, end: usize) -> Option<usize> {
+    let mut bit = from;
+    while bit < end {
+        let Some(&byte) = bitmap.get(bit / 8) else {
+            return Some(bit);
+        };
+        if bit & 7 == 0 && byte == 0xff {
+            bit += 8;
+        } else if (byte >> (bit % 8)) & 1 == 0 {
+            return Some(bit);
+        } else {
+            bit += 1;
+        }
+    }
+    None
+}
@@ -1,15 +1,41 @@
 // Transaction limits
 pub const MAX_SLOTS_PER_TRANSACTION: usize = 100;
+pub const MAX_RANGES_PER_TRANSACTION: usize = 32;
+pub const MAX_QUERY_SLOTS: usize = 64; // One bit each in SlotQueryResult::gated_mask
+pub const MAX_VALIDATION_SOURCES: usize = 8; // One bit each in SourceValidationResult::flagged_mask
+pub const MAX_COMPACT_SLOTS_PER_TRANSACTION: usize = 1_000; // Bounds decode and bit-flip compute, not transaction size
 
 // Epoch configuration 
 pub const SLOTS_PER_EPOCH: usize = 432_000;
+pub const SLOTS_PER_LEADER_WINDOW: u8 = 4; // Consecutive slots assigned to one leader
 
 // Bitmap size constants
 pub const FULL_BITMAP_SIZE_BYTES: usize = 54000; // 432,000 bits / 8 = 54,000 bytes
 pub const INITIAL_BITMAP_SIZE_BYTES: usize = 9000; // Initial size within 10KB limit
 
 // Account size constants
-pub const LARGE_BITMAP_ACCOUNT_BASE_SIZE: usize = 8 + 2 + 1 + 5; // discriminator + epoch + bump + padding
+pub const LARGE_BITMAP_ACCOUNT_BASE_SIZE: usize = 8 + 56; // discriminator + SandwichValidators header
 pub const INITIAL_ACCOUNT_SIZE: usize = 10240; // Initial 10KB allocation
-pub const TARGET_ACCOUNT_SIZE: usize = LARGE_BITMAP_ACCOUNT_BASE_SIZE + FULL_BITMAP_SIZE_BYTES; // 54KB for full epoch
+pub const TARGET_ACCOUNT_SIZE: usize = LARGE_BITMAP_ACCOUNT_BASE_SIZE + FULL_BITMAP_SIZE_BYTES; // 54,064 bytes for a full 432,000-slot epoch
 pub const MAX_REALLOC_SIZE: usize = 10240; // Solana's 10KB reallocation limit per operation
+
+// Signer set limits
+pub const MAX_SIGNER_SET_MEMBERS: usize = 10;
+
+// Role table limits
+pub const MAX_ROLE_GRANTS: usize = 16;
+
+// Integrator profile limits
+pub const MAX_PROFILE_AUTHORITIES: usize = 4;
+pub const MAX_PROFILE_EXEMPT_RANGES: usize = 8;
+
+// Curator committee limits
+pub const MAX_COMMITTEE_MEMBERS: usize = 10;
+
+// Integrator override limits (per list)
+pub const MAX_OVERRIDE_RANGES: usize = 32;
+
+// Provider registry limits
+pub const MAX_PROVIDERS: usize = 16;
+pub const MAX_PROVIDER_NAME_LEN: usize = 32; // bytes
+pub const MAX_PROVIDER_URI_LEN: usize = 128; // bytes
@@ -0,0 +1,128 @@
+//! Compact slot encodings accepted by bitmap write instructions.
+//!
+//! Every slot an instruction touches lies within one epoch, so clients send offsets
+//! from the epoch start instead of absolute `u64` slots, either as a list of slots
+//! ([`SlotOffsets`]) or as alternating runs of ungated and gated slots ([`BitmapRuns`]).
+//! Decoding is streaming: the instruction data is walked in place and no intermediate
+//! list is built.
+
+use anchor_lang::prelude::*;
+use crate::GatekeeperError;
+
+/// Longest LEB128 encoding of a `u32`.
+const MAX_VARINT_LEN: usize = 5;
+
+/// Decodes one unsigned LEB128 value from the front of `data`, returning it and the
+/// number of bytes read. Overlong and truncated encodings are rejected.
+pub fn read_varint(data: &[u8]) -> Result<(u32, usize)> {
+    let mut value: u64 = 0;
+    for (i, &byte) in data.iter().take(MAX_VARINT_LEN).enumerate() {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            // A trailing zero byte would be a second encoding of the same value
+            if i > 0 && byte == 0 {
+                break;
+            }
+            let value = u32::try_from(value).map_err(|_| GatekeeperError::InvalidSlotEncoding)?;
+            return Ok((value, i + 1));
+        }
+    }
+    err!(GatekeeperError::InvalidSlotEncoding)
+}
+
+/// Iterator over slot offsets encoded as LEB128 gaps.
+///
+/// The first value is the offset of the first slot from the epoch start; each later
+/// value is the gap to the previous offset. Offsets therefore come out strictly
+/// increasing, and a zero gap (a repeated slot) is reported as `DuplicateSlots`.
+#[derive(Clone)]
+pub struct SlotOffsets<'a> {
+    data: &'a [u8],
+    previous: Option<u32>,
+}
+
+impl<'a> SlotOffsets<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, previous: None }
+    }
+
+    /// Walks the whole encoding, returning the number of offsets and the last (largest)
+    /// one after checking that every value decodes.
+    pub fn validate(&self) -> Result<(usize, Option<u32>)> {
+        let mut count = 0;
+        let mut last = None;
+        for offset in self.clone() {
+            last = Some(offset?);
+            count += 1;
+        }
+        Ok((count, last))
+    }
+}
+
+impl Iterator for SlotOffsets<'_> {
+    type Item = Result<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let decoded = read_varint(self.data).and_then(|(value, len)| {
+            self.data = &self.data[len..];
+            let offset = match self.previous {
+                None => value,
+                Some(_) if value == 0 => return err!(GatekeeperError::DuplicateSlots),
+                Some(previous) => previous
+                    .checked_add(value)
+                    .ok_or(GatekeeperError::SlotOutOfRange)?,
+            };
+            self.previous = Some(offset);
+            Ok(offset)
+        });
+        if decoded.is_err() {
+            // Stop after the first error instead of decoding from a misaligned position
+            self.data = &[];
+        }
+        Some(decoded)
+    }
+}
+
+/// Iterator over a run-length encoded stretch of bitmap.
+///
+/// Runs are LEB128 lengths that alternate between ungated and gated slots, starting
+/// with ungated, so a stretch that begins gated starts with a zero-length run. Only
+/// that first run may be empty. Each item is a run's length and whether it is gated.
+pub struct BitmapRuns<'a> {
+    data: &'a [u8],
+    gated: bool,
+    first: bool,
+}
+
+impl<'a> BitmapRuns<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, gated: false, first: true }
+    }
+}
+
+impl Iterator for BitmapRuns<'_> {
+    type Item = Result<(u32, bool)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let decoded = read_varint(self.data).and_then(|(len, read)| {
+            if len == 0 && !self.first {
+                return err!(GatekeeperError::InvalidSlotEncoding);
+            }
+            self.data = &self.data[read..];
+            let run = (len, self.gated);
+            self.gated = !self.gated;
+            self.first = false;
+            Ok(run)
+        });
+        if decoded.is_err() {
+            self.data = &[];
+        }
+        Some(decoded)
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::{AcceptAuthority, AuthorityAccepted, GatekeeperError};
+
+/// Handler for the `accept_authority` instruction.
+///
+/// Completes a rotation. The signer must be the pending authority, which proves the
+/// new key is usable before the old one loses write access.
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let record = &mut ctx.accounts.authority_record;
+    let pending = record.pending_authority.ok_or(GatekeeperError::NoPendingAuthority)?;
+
+    if pending != ctx.accounts.pending_authority.key() {
+        return err!(GatekeeperError::InvalidAuthority);
+    }
+
+    // Guardian recoveries carry a delay during which the current authority may cancel
+    let clock = Clock::get()?;
+    if clock.unix_timestamp < record.pending_unlock_timestamp {
+        return err!(GatekeeperError::RecoveryDelayNotElapsed);
+    }
+
+    let previous_authority = record.authority;
+    record.authority = pending;
+    record.pending_authority = None;
+    record.pending_unlock_timestamp = 0;
+
+    emit!(AuthorityAccepted {
+        namespace: record.namespace,
+        previous_authority,
+        new_authority: pending,
+    });
+
+    Ok(())
+}
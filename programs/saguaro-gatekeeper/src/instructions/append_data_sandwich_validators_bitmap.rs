@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::AppendDataSandwichValidatorsBitmap;
+use crate::{AppendDataSandwichValidatorsBitmap, Role, SandwichValidatorsUpdated};
+use crate::authority::require_authority_or_role;
+use crate::bitmap::bits_below_mask;
+use crate::layout::Header;
 
 /// Handler for appending data to a sandwich validators bitmap account.
 /// 
@@ -7,11 +10,27 @@ use crate::AppendDataSandwichValidatorsBitmap;
 /// This is a low-level utility for writing pre-computed bitmap data.
 /// Most users should use `modify_sandwich_validators` instead.
 /// 
+/// Bits past the epoch's last slot are masked off, so a full tail byte (or bytes beyond
+/// the epoch) never gates slots that do not exist. The header's `gated_count` is
+/// adjusted by the bits this write sets and clears, so only the written bytes are scanned.
+/// 
 /// # Compute Optimization
 /// - Minimizes logging overhead
 /// - Early return for empty data
 /// - Direct memory operations
-pub fn handler(ctx: Context<AppendDataSandwichValidatorsBitmap>, _epoch_arg: u16, data: Vec<u8>) -> Result<()> {
+pub fn handler(ctx: Context<AppendDataSandwichValidatorsBitmap>, _epoch_arg: u64, data: Vec<u8>) -> Result<()> {
+    // Refuse to write bitmaps while the gatekeeper is frozen
+    ctx.accounts.gatekeeper_config.require_writable()?;
+
+    require_authority_or_role(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        &[Role::Writer],
+        ctx.program_id,
+    )?;
+
     // Validate data size early to avoid unnecessary loads
     if data.is_empty() {
         return Ok(());
@@ -21,18 +40,16 @@ pub fn handler(ctx: Context<AppendDataSandwichValidatorsBitmap>, _epoch_arg: u16
         return err!(crate::GatekeeperError::SlotOutOfRange);
     }
 
-    // First get the current bitmap length
-    let current_len = {
-        let sandwich_validators = ctx.accounts.sandwich_validators.load()?;
-        sandwich_validators.bitmap_len as usize
-    };
-    
     // Access account raw data for bitmap
     let account_info = ctx.accounts.sandwich_validators.to_account_info();
     let mut account_data = account_info.try_borrow_mut_data()?;
 
-    // Bitmap starts at DATA_OFFSET (discriminator 8 + struct fields 8)
-    let bitmap_data = &mut account_data[crate::SandwichValidators::DATA_OFFSET..];
+    // Only unsealed accounts on the current layout are writable; older versions must be migrated first
+    let mut header = Header::read_writable(&account_data)?;
+    let current_len = header.bitmap_len as usize;
+    let (_, slots_in_epoch) = ctx.accounts.gatekeeper_config.slots_per_epoch_source.epoch_bounds(header.epoch)?;
+    let bits_in_epoch = header.bits_for_slots(slots_in_epoch) as usize;
+    let bitmap_data = &mut account_data[header.data_offset()..];
     let available_space = bitmap_data.len().saturating_sub(current_len);
 
     if data.len() > available_space {
@@ -46,15 +63,25 @@ pub fn handler(ctx: Context<AppendDataSandwichValidatorsBitmap>, _epoch_arg: u16
         return err!(crate::GatekeeperError::SlotOutOfRange);
     }
 
-    // Append the new data at current offset
-    bitmap_data[current_len..current_len + data.len()].copy_from_slice(&data);
-    
-    // Drop the mutable borrow before loading again
-    drop(account_data);
+    // Append at the current offset, clamped to the epoch, recounting only the overwritten bytes
+    let mut slots_added = 0u32;
+    let mut slots_removed = 0u32;
+    let target = &mut bitmap_data[current_len..current_len + data.len()];
+    for (index, (byte, &chunk)) in target.iter_mut().zip(&data).enumerate() {
+        let old = *byte;
+        let new = chunk & bits_below_mask(current_len + index, bits_in_epoch);
+        slots_added += (new & !old).count_ones();
+        slots_removed += (old & !new).count_ones();
+        *byte = new;
+    }
 
-    // Update length field
-    let sandwich_validators = &mut ctx.accounts.sandwich_validators.load_mut()?;
-    sandwich_validators.bitmap_len += data.len() as u32;
+    header.bitmap_len += data.len() as u32;
+    header.gated_count = header.gated_count
+        .checked_sub(slots_removed)
+        .and_then(|count| count.checked_add(slots_added))
+        .ok_or(crate::GatekeeperError::SlotOutOfRange)?;
+    header.write(&mut account_data);
+    drop(account_data);
 
     #[cfg(feature = "debug-logs")]
     {
@@ -63,8 +90,18 @@ pub fn handler(ctx: Context<AppendDataSandwichValidatorsBitmap>, _epoch_arg: u16
             data.len(),
             current_len
         );
-        msg!("New bitmap_len: {}", sandwich_validators.bitmap_len);
-        msg!("Epoch: {}", sandwich_validators.epoch);
+        msg!("New bitmap_len: {}", header.bitmap_len);
+        msg!("Epoch: {}", header.epoch);
+    }
+
+    if slots_added > 0 || slots_removed > 0 {
+        emit!(SandwichValidatorsUpdated {
+            authority: ctx.accounts.multisig_authority.key(),
+            epoch: header.epoch,
+            slots_added,
+            slots_removed,
+            total_slots: header.gated_count,
+        });
     }
 
     Ok(())
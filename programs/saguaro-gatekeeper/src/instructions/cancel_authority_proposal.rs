@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::{CancelAuthorityProposal, AuthorityProposalCancelled, GatekeeperError};
+
+/// Handler for the `cancel_authority_proposal` instruction.
+///
+/// Clears the pending proposal. This is how the active authority vetoes a guardian
+/// recovery it did not ask for.
+pub fn handler(ctx: Context<CancelAuthorityProposal>) -> Result<()> {
+    let record = &mut ctx.accounts.authority_record;
+    let cancelled_authority = record.pending_authority.take().ok_or(GatekeeperError::NoPendingAuthority)?;
+    record.pending_unlock_timestamp = 0;
+
+    emit!(AuthorityProposalCancelled {
+        namespace: record.namespace,
+        cancelled_authority,
+    });
+
+    Ok(())
+}
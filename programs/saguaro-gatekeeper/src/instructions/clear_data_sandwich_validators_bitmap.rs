@@ -1,42 +1,67 @@
 use anchor_lang::prelude::*;
-use crate::ClearDataSandwichValidatorsBitmap;
+use crate::{ClearDataSandwichValidatorsBitmap, Role, SandwichValidatorsUpdated};
+use crate::authority::require_authority_or_quorum;
+use crate::layout::Header;
 
 /// Handler for clearing all data in a sandwich validators bitmap account.
 /// 
 /// **Utility Operation**: Clear all bitmap data (ungate all slots)
-/// This sets all slots in the bitmap to ungated (false) and resets bitmap_len to 0.
+/// This sets all slots in the bitmap to ungated (false) and resets bitmap_len and gated_count to 0.
 /// 
 /// # Compute Optimization
 /// - Minimizes logging overhead
 /// - Uses efficient memory clearing operations
-pub fn handler(ctx: Context<ClearDataSandwichValidatorsBitmap>, _epoch_arg: u16) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClearDataSandwichValidatorsBitmap<'info>>, _epoch_arg: u64) -> Result<()> {
+    // Refuse to clear bitmaps while the gatekeeper is frozen
+    ctx.accounts.gatekeeper_config.require_writable()?;
+
+    require_authority_or_quorum(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        &[Role::Ungater],
+        ctx.accounts.signer_set.as_deref(),
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+
     // Get account data for writing
     let account_info = ctx.accounts.sandwich_validators.to_account_info();
     let mut account_data = account_info.try_borrow_mut_data()?;
     
+    // Only unsealed accounts on the current layout are writable; older versions must be migrated first
+    let mut header = Header::read_writable(&account_data)?;
+
     #[cfg(feature = "debug-logs")]
-    {
-        // Get epoch from account data directly (bytes 8-9 after discriminator)
-        let epoch = u16::from_le_bytes([account_data[8], account_data[9]]);
-        msg!("Clearing all data in large bitmap for epoch {}", epoch);
-    }
+    msg!("Clearing all data in large bitmap for epoch {}", header.epoch);
     
-    // Clear bitmap data starting at DATA_OFFSET (16 bytes)
-    let bitmap_data = &mut account_data[crate::SandwichValidators::DATA_OFFSET..];
+    // Clear bitmap data following the header
+    let bitmap_data = &mut account_data[header.data_offset()..];
     bitmap_data.fill(0);
     
-    // Drop the raw data borrow before using load_mut
+    // Reset the bitmap_len and gated_count fields to 0
+    let slots_removed = header.gated_count;
+    header.bitmap_len = 0;
+    header.gated_count = 0;
+    header.write(&mut account_data);
     drop(account_data);
     
-    // Reset the bitmap_len field to 0
-    let sandwich_validators = &mut ctx.accounts.sandwich_validators.load_mut()?;
-    sandwich_validators.bitmap_len = 0;
-    
     #[cfg(feature = "debug-logs")]
     {
         msg!("Large bitmap data cleared successfully");
         msg!("All bitmap data reset to 0 and bitmap_len reset to 0");
     }
+
+    if slots_removed > 0 {
+        emit!(SandwichValidatorsUpdated {
+            authority: ctx.accounts.multisig_authority.key(),
+            epoch: header.epoch,
+            slots_added: 0,
+            slots_removed,
+            total_slots: 0,
+        });
+    }
     
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::{CloseIntegratorOverrides, IntegratorOverridesClosed};
+
+/// Handler for the `close_integrator_overrides` instruction.
+///
+/// The account is closed and its rent refunded to the owner by the `close` constraint.
+pub fn handler(ctx: Context<CloseIntegratorOverrides>, epoch_arg: u64) -> Result<()> {
+    #[cfg(feature = "debug-logs")]
+    msg!("Closing overrides of {} for epoch {}", ctx.accounts.owner.key(), epoch_arg);
+
+    emit!(IntegratorOverridesClosed {
+        owner: ctx.accounts.owner.key(),
+        epoch: epoch_arg,
+    });
+
+    Ok(())
+}
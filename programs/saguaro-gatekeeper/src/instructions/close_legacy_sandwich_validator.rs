@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::{CloseLegacySandwichValidator, Role, SandwichValidatorsClosed, GatekeeperError};
+use crate::authority::{require_authority_or_quorum, require_rent_recipient};
+
+/// Handles the `close_legacy_sandwich_validator` instruction.
+///
+/// Closes a `SandwichValidators` PDA created under the legacy u16 seed scheme for a
+/// past epoch. Legacy accounts use a different header layout, so the account is
+/// closed without being deserialized; the seeds constraint pins the epoch. Rent goes
+/// to the active authority, as in `close_sandwich_validator`.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CloseLegacySandwichValidator<'info>>, epoch_to_close: u16) -> Result<()> {
+    // Refuse to close bitmaps while the gatekeeper is frozen
+    ctx.accounts.gatekeeper_config.require_writable()?;
+
+    require_authority_or_quorum(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        &[Role::Closer],
+        ctx.accounts.signer_set.as_deref(),
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+    let rent_recipient = &ctx.accounts.rent_recipient;
+    require_rent_recipient(ctx.accounts.multisig_authority.key, &ctx.accounts.authority_record, rent_recipient, ctx.program_id)?;
+
+    let sandwich_validators = &ctx.accounts.sandwich_validators;
+    if sandwich_validators.data_is_empty() || sandwich_validators.owner != ctx.program_id {
+        return err!(GatekeeperError::InvalidPda);
+    }
+
+    // Only allow closing PDAs for past epochs
+    if u64::from(epoch_to_close) >= Clock::get()?.epoch {
+        return err!(GatekeeperError::EpochNotFinished);
+    }
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Closing legacy SandwichValidators PDA for epoch {}", epoch_to_close);
+
+    // Same steps as Anchor's `close`: move lamports, then hand the account back to the
+    // System Program with no data
+    let lamports = sandwich_validators.lamports();
+    **rent_recipient.try_borrow_mut_lamports()? = rent_recipient
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(GatekeeperError::RentNotMet)?;
+    **sandwich_validators.try_borrow_mut_lamports()? = 0;
+    sandwich_validators.assign(&system_program::ID);
+    sandwich_validators.resize(0)?;
+
+    emit!(SandwichValidatorsClosed {
+        authority: ctx.accounts.multisig_authority.key(),
+        epoch: u64::from(epoch_to_close),
+    });
+
+    Ok(())
+}
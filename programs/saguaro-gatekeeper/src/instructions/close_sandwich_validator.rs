@@ -1,15 +1,33 @@
 use anchor_lang::prelude::*;
-use crate::{CloseSandwichValidator, SandwichValidatorsClosed, GatekeeperError};
+use crate::{CloseSandwichValidator, Role, SandwichValidatorsClosed, GatekeeperError};
+use crate::authority::{require_authority_or_quorum, require_rent_recipient};
+use crate::layout::Header;
 
 /// Handles the `close_sandwich_validator` instruction.
 ///
 /// Closes an existing `SandwichValidators` PDA for a past epoch, returning its rent
-/// to the `multisig_authority`. Anchor's `close` constraint handles the actual closing
-/// and rent refund automatically.
-pub fn handler(ctx: Context<CloseSandwichValidator>, epoch_to_close: u16) -> Result<()> {
+/// to the active authority even when a Closer or signer set authorised the close.
+/// After a rotation this is no longer the `multisig_authority` namespace key, which
+/// may have been lost.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CloseSandwichValidator<'info>>, epoch_to_close: u64) -> Result<()> {
+    // Refuse to close bitmaps while the gatekeeper is frozen
+    ctx.accounts.gatekeeper_config.require_writable()?;
 
-    let sandwich_validators = ctx.accounts.sandwich_validators.load()?;
-    let epoch = sandwich_validators.epoch;
+    require_authority_or_quorum(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        &[Role::Closer],
+        ctx.accounts.signer_set.as_deref(),
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+    let rent_recipient = &ctx.accounts.rent_recipient;
+    require_rent_recipient(ctx.accounts.multisig_authority.key, &ctx.accounts.authority_record, rent_recipient, ctx.program_id)?;
+
+    // Any supported layout can be closed without migrating first
+    let epoch = Header::read(&ctx.accounts.sandwich_validators.to_account_info().try_borrow_data()?)?.epoch;
     let authority_key = ctx.accounts.multisig_authority.key();
 
     // Verify the epoch_to_close matches the PDA's epoch
@@ -22,7 +40,7 @@ pub fn handler(ctx: Context<CloseSandwichValidator>, epoch_to_close: u16) -> Res
     let current_epoch = clock.epoch;
     
     // Only allow closing PDAs for past epochs (current epoch or future epochs cannot be closed)
-    if u64::from(epoch_to_close) >= current_epoch {
+    if epoch_to_close >= current_epoch {
         return err!(GatekeeperError::EpochNotFinished);
     }
 
@@ -30,14 +48,12 @@ pub fn handler(ctx: Context<CloseSandwichValidator>, epoch_to_close: u16) -> Res
     {
         msg!("Closing SandwichValidators PDA for epoch {}", epoch);
         msg!("Current epoch: {}, closing epoch: {}", current_epoch, epoch_to_close);
-        msg!("Rent will be returned to authority: {}", authority_key);
+        msg!("Rent will be returned to authority: {}", rent_recipient.key);
     }
-    
-    // Anchor's `close` constraint in the account definition handles:
-    // 1. PDA validation (seeds + bump)
-    // 2. Authority verification
-    // 3. Account closing and rent refund
-    // No additional manual work needed.
+
+    // PDA validation is handled by the seeds constraint; close and refund rent here
+    // because the destination depends on the resolved active authority.
+    ctx.accounts.sandwich_validators.close(rent_recipient.to_account_info())?;
     
     // Emit event for monitoring
     emit!(SandwichValidatorsClosed {
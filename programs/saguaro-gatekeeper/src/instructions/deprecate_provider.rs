@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::{DeprecateProvider, ProviderStatus, ProviderUpdated, GatekeeperError};
+
+/// Handler for the `deprecate_provider` instruction.
+///
+/// Flags `authority`'s record as deprecated instead of removing it, so integrators
+/// enumerating the registry can see that the provider should no longer be relied on.
+pub fn handler(ctx: Context<DeprecateProvider>, authority: Pubkey) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    let registry = &mut ctx.accounts.provider_registry;
+    let index = registry.position(&authority)?;
+    if signer != ctx.accounts.gatekeeper_config.admin && signer != authority {
+        return err!(GatekeeperError::InvalidAuthority);
+    }
+
+    let record = &mut registry.providers[index];
+    record.status = ProviderStatus::Deprecated;
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Deprecated provider {} ({})", record.authority, record.name);
+
+    emit!(ProviderUpdated {
+        authority: record.authority,
+        name: record.name.clone(),
+        metadata_uri: record.metadata_uri.clone(),
+        first_epoch: record.first_epoch,
+        last_epoch: record.last_epoch,
+        status: record.status,
+    });
+
+    Ok(())
+}
@@ -1,34 +1,56 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use crate::{ExpandSandwichValidatorsBitmap, TARGET_ACCOUNT_SIZE, MAX_REALLOC_SIZE, GatekeeperError};
+use crate::{ExpandSandwichValidatorsBitmap, Role, SandwichValidators, INITIAL_ACCOUNT_SIZE, MAX_REALLOC_SIZE, GatekeeperError};
+use crate::authority::require_authority_or_role;
+use crate::layout::Header;
 
 pub fn handler(
     ctx: Context<ExpandSandwichValidatorsBitmap>, 
-    _epoch_arg: u16,
+    epoch_arg: u64,
 ) -> Result<()> {
+    // Refuse to expand bitmaps while the gatekeeper is frozen
+    ctx.accounts.gatekeeper_config.require_writable()?;
+
     let sandwich_validators_account = &ctx.accounts.sandwich_validators;
-    let multisig_authority = &ctx.accounts.multisig_authority;
+    let payer = require_authority_or_role(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        &[Role::Provisioner],
+        ctx.program_id,
+    )?;
 
     // Validate account exists and is owned by our program
     if sandwich_validators_account.data_is_empty() || *sandwich_validators_account.owner != *ctx.program_id {
         return err!(GatekeeperError::InvalidPda);
     }
+
+    // Sealed accounts cannot grow; older versions must be migrated first
+    let header = Header::read_writable(&sandwich_validators_account.try_borrow_data()?)?;
     
+    // The fully expanded size follows the epoch length from the configured source and the
+    // account's granularity; a leader-window account is complete after one expansion
+    let config = &ctx.accounts.gatekeeper_config;
+    let (_, slots_in_epoch) = config.slots_per_epoch_source.epoch_bounds(epoch_arg)?;
+    let target_account_size = SandwichValidators::target_account_size(slots_in_epoch, header.slots_per_bit)?;
+
     #[cfg(feature = "debug-logs")]
     {
         msg!("Current account size: {}", sandwich_validators_account.data_len());
-        msg!("Target account size: {}", TARGET_ACCOUNT_SIZE);
+        msg!("Target account size: {}", target_account_size);
     }
 
     // Calculate how much we need to expand with overflow protection
     let current_size = sandwich_validators_account.data_len();
     
-    // Validate current size is within reasonable bounds
-    if current_size > TARGET_ACCOUNT_SIZE {
+    // Validate current size is within reasonable bounds; accounts created at the
+    // initial 10KB size may exceed the target on short epochs
+    if current_size > target_account_size.max(INITIAL_ACCOUNT_SIZE) {
         return err!(GatekeeperError::InvalidPda);
     }
     
-    let bytes_needed = TARGET_ACCOUNT_SIZE.saturating_sub(current_size);
+    let bytes_needed = target_account_size.saturating_sub(current_size);
     
     if bytes_needed > 0 {
         #[cfg(feature = "debug-logs")]
@@ -38,7 +60,7 @@ pub fn handler(
         let rent = Rent::get()?;
         let expansion_size = bytes_needed.min(MAX_REALLOC_SIZE);
         let new_size = current_size.checked_add(expansion_size)
-            .filter(|&size| size <= TARGET_ACCOUNT_SIZE)
+            .filter(|&size| size <= target_account_size)
             .ok_or(GatekeeperError::SlotOutOfRange)?;
         
         // Only transfer rent needed for the size we're actually expanding to
@@ -50,7 +72,7 @@ pub fn handler(
             #[cfg(feature = "debug-logs")]
             {
                 msg!("Adding {} lamports for rent exemption", additional_lamports);
-                msg!("Rent calculated for size: {} bytes (not full target of {} bytes)", new_size, TARGET_ACCOUNT_SIZE);
+                msg!("Rent calculated for size: {} bytes (not full target of {} bytes)", new_size, target_account_size);
             }
             
             // Transfer additional lamports for rent exemption
@@ -58,7 +80,7 @@ pub fn handler(
                 CpiContext::new(
                     ctx.accounts.system_program.to_account_info(),
                     system_program::Transfer {
-                        from: multisig_authority.to_account_info(),
+                        from: payer.to_account_info(),
                         to: sandwich_validators_account.to_account_info(),
                     },
                 ),
@@ -83,8 +105,8 @@ pub fn handler(
             msg!("Expansion successful, new size: {}", sandwich_validators_account.data_len());
             
             // If we haven't reached target size yet, caller needs to invoke this instruction again
-            if new_size < TARGET_ACCOUNT_SIZE {
-                msg!("Account needs further expansion. Current: {}, Target: {}", new_size, TARGET_ACCOUNT_SIZE);
+            if new_size < target_account_size {
+                msg!("Account needs further expansion. Current: {}, Target: {}", new_size, target_account_size);
             }
         }
     } else {
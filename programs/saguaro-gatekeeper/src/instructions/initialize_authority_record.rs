@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::{InitializeAuthorityRecord, AuthorityGuardianUpdated, GatekeeperError};
+
+/// Handler for the `initialize_authority_record` instruction.
+///
+/// Creates the alias record for a namespace with the namespace key as the active
+/// authority. Write instructions keep working unchanged until a rotation is accepted.
+pub fn handler(
+    ctx: Context<InitializeAuthorityRecord>,
+    guardian: Option<Pubkey>,
+    recovery_delay_seconds: i64,
+) -> Result<()> {
+    if recovery_delay_seconds < 0 {
+        return err!(GatekeeperError::InvalidRecoveryDelay);
+    }
+
+    let namespace = ctx.accounts.multisig_authority.key();
+    let record = &mut ctx.accounts.authority_record;
+
+    record.namespace = namespace;
+    record.authority = namespace;
+    record.pending_authority = None;
+    record.pending_unlock_timestamp = 0;
+    record.guardian = guardian;
+    record.recovery_delay_seconds = recovery_delay_seconds;
+    record.bump = ctx.bumps.authority_record;
+
+    emit!(AuthorityGuardianUpdated {
+        namespace,
+        guardian,
+        recovery_delay_seconds,
+    });
+
+    Ok(())
+}
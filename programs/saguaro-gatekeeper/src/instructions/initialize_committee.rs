@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::{InitializeCommittee, Committee, CommitteeParams, CommitteeUpdated};
+
+/// Handler for the `initialize_committee` instruction.
+///
+/// Creates the committee of `namespace`. Members publish bitmaps with the ordinary write
+/// instructions under the namespaces the committee derives for them (see `join_committee`);
+/// the committee only records who counts and how many must agree.
+pub fn handler(ctx: Context<InitializeCommittee>, params: CommitteeParams) -> Result<()> {
+    Committee::validate_members(&params.members, params.threshold)?;
+
+    let committee_key = ctx.accounts.committee.key();
+    let committee = &mut ctx.accounts.committee;
+    committee.namespace = ctx.accounts.namespace.key();
+    committee.governance = params.governance;
+    committee.set_members(&committee_key, params.members, ctx.program_id);
+    committee.threshold = params.threshold;
+    committee.bump = ctx.bumps.committee;
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Initialized committee {} with {}-of-{} members", committee.key(), committee.threshold, committee.members.len());
+
+    emit!(CommitteeUpdated {
+        committee: committee.key(),
+        governance: committee.governance,
+        members: committee.members.clone(),
+        threshold: committee.threshold,
+    });
+
+    Ok(())
+}
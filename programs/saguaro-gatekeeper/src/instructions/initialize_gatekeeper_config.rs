@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::{InitializeGatekeeperConfig, GatekeeperConfig, GatekeeperConfigParams, GatekeeperConfigUpdated, OverrideMode};
+
+/// Handler for the `initialize_gatekeeper_config` instruction.
+///
+/// Creates the GatekeeperConfig singleton. Only the program upgrade authority can
+/// call this, which prevents the singleton PDA from being squatted after deployment.
+pub fn handler(ctx: Context<InitializeGatekeeperConfig>, params: GatekeeperConfigParams) -> Result<()> {
+    let config = &mut ctx.accounts.gatekeeper_config;
+
+    config.admin = params.admin;
+    config.mode = params.mode;
+    config.slots_per_epoch_source = params.slots_per_epoch_source;
+    config.fail_policy = params.fail_policy;
+    config.unsealed_policy = params.unsealed_policy;
+    config.override_mode = OverrideMode::Normal;
+    config.layout_version = GatekeeperConfig::LAYOUT_VERSION;
+    config.bump = ctx.bumps.gatekeeper_config;
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Initialized GatekeeperConfig with admin {}", config.admin);
+
+    emit!(GatekeeperConfigUpdated {
+        admin: config.admin,
+        mode: config.mode,
+        slots_per_epoch_source: config.slots_per_epoch_source,
+        fail_policy: config.fail_policy,
+        unsealed_policy: config.unsealed_policy,
+        layout_version: config.layout_version,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::{InitializeIntegratorProfile, IntegratorProfile, IntegratorProfileParams, IntegratorProfileUpdated};
+
+/// Handler for the `initialize_integrator_profile` instruction.
+///
+/// Creates the profile of `integrator_program`. Only that program's upgrade authority
+/// can call this, so nobody else can choose the gate lists applied to its users.
+pub fn handler(ctx: Context<InitializeIntegratorProfile>, params: IntegratorProfileParams) -> Result<()> {
+    IntegratorProfile::validate_params(&params)?;
+
+    let profile = &mut ctx.accounts.integrator_profile;
+    profile.integrator = ctx.accounts.integrator_program.key();
+    profile.bump = ctx.bumps.integrator_profile;
+    profile.apply(params);
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Initialized IntegratorProfile for {} with {} authorities", profile.integrator, profile.authorities.len());
+
+    emit!(IntegratorProfileUpdated {
+        integrator: profile.integrator,
+        admin: profile.admin,
+        authorities: profile.authorities.clone(),
+        combination: profile.combination,
+        fail_policy: profile.fail_policy,
+        exempt_ranges: profile.exempt_ranges.clone(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::{InitializeRoleTable, RoleGrant, RoleTable, RoleTableUpdated};
+use crate::authority::require_active_authority;
+
+/// Handler for the `initialize_role_table` instruction.
+///
+/// Creates the role table for a namespace so hot keys can be limited to the
+/// instructions they need instead of holding the full authority.
+pub fn handler(ctx: Context<InitializeRoleTable>, grants: Vec<RoleGrant>) -> Result<()> {
+    require_active_authority(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.program_id,
+    )?;
+
+    RoleTable::validate_grants(&grants)?;
+
+    let role_table = &mut ctx.accounts.role_table;
+    role_table.namespace = ctx.accounts.multisig_authority.key();
+    role_table.grants = grants;
+    role_table.bump = ctx.bumps.role_table;
+
+    emit!(RoleTableUpdated {
+        namespace: role_table.namespace,
+        grants: role_table.grants.clone(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::{InitializeSignerSet, SignerSet, SignerSetUpdated};
+use crate::authority::require_active_authority;
+
+/// Handler for the `initialize_signer_set` instruction.
+///
+/// Creates the built-in M-of-N signer set for a namespace. Only the active authority
+/// can create it; day-to-day writes can then be authorised by member quorum instead.
+pub fn handler(ctx: Context<InitializeSignerSet>, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    require_active_authority(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.program_id,
+    )?;
+
+    SignerSet::validate_members(&members, threshold)?;
+
+    let signer_set = &mut ctx.accounts.signer_set;
+    signer_set.namespace = ctx.accounts.multisig_authority.key();
+    signer_set.members = members;
+    signer_set.threshold = threshold;
+    signer_set.bump = ctx.bumps.signer_set;
+
+    emit!(SignerSetUpdated {
+        namespace: signer_set.namespace,
+        members: signer_set.members.clone(),
+        threshold,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::{JoinCommittee, CommitteeMemberJoined, GatekeeperError};
+
+/// Handler for the `join_committee` instruction.
+///
+/// Creates the AuthorityRecord of the member's committee namespace with the member as the
+/// active authority. The namespace is a PDA, so it can never sign; the member writes its
+/// bitmaps by passing it as `multisig_authority` and signing as `operator`.
+pub fn handler(ctx: Context<JoinCommittee>) -> Result<()> {
+    let member = ctx.accounts.member.key();
+    if !ctx.accounts.committee.members.contains(&member) {
+        return err!(GatekeeperError::InvalidAuthority);
+    }
+
+    let namespace = ctx.accounts.member_namespace.key();
+    let record = &mut ctx.accounts.authority_record;
+
+    // Already joined; the record may have been rotated since, so leave it untouched
+    if record.namespace == namespace {
+        return Ok(());
+    }
+
+    record.namespace = namespace;
+    record.authority = member;
+    record.pending_authority = None;
+    record.pending_unlock_timestamp = 0;
+    record.guardian = None;
+    record.recovery_delay_seconds = 0;
+    record.bump = ctx.bumps.authority_record;
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Member {} joined committee {} as {}", member, ctx.accounts.committee.key(), namespace);
+
+    emit!(CommitteeMemberJoined {
+        committee: ctx.accounts.committee.key(),
+        member,
+        namespace,
+    });
+
+    Ok(())
+}
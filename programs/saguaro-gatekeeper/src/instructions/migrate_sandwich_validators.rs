@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::{MigrateSandwichValidators, Role, SandwichValidators, SandwichValidatorsMigrated, GatekeeperError};
+use crate::authority::require_authority_or_role;
+use crate::bitmap::count_gated_slots;
+use crate::layout::{Header, VERSION_CURRENT};
+
+/// Handles the `migrate_sandwich_validators` instruction.
+///
+/// Upgrades a `SandwichValidators` account to the current layout in place, keeping its
+/// address, bitmap and gated slots, so live epochs never need to be closed and recreated.
+/// The account may sit at either the u64-seeded or the legacy u16-seeded address for
+/// `epoch_arg`. Accounts already in the current layout are left untouched, except that
+/// one at the legacy address gets `FLAG_LEGACY_SEEDS` if it was migrated before the flag
+/// existed. The flag is what lets the write, seal and unseal instructions accept the
+/// legacy address afterwards (see `layout::is_bitmap_pda`).
+///
+/// # Behavior
+/// - Versions 0 and 2: grows the account by the size difference of the headers, funding
+///   the extra rent from the signer, and shifts the bitmap up. These accounts start unsealed.
+/// - Version 3: the bitmap stays in place and the seal is preserved.
+/// - Every older version has `gated_count` seeded by counting the whole bitmap once, which
+///   may need a raised compute unit limit on fully expanded accounts.
+/// - Accounts at the legacy address are flagged with `FLAG_LEGACY_SEEDS`, so validation
+///   derives only that address from the stored bump.
+pub fn handler(ctx: Context<MigrateSandwichValidators>, epoch_arg: u64) -> Result<()> {
+    // Refuse to migrate bitmaps while the gatekeeper is frozen
+    ctx.accounts.gatekeeper_config.require_writable()?;
+
+    let payer = require_authority_or_role(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        &[Role::Provisioner],
+        ctx.program_id,
+    )?;
+
+    let sandwich_validators = &ctx.accounts.sandwich_validators;
+    if sandwich_validators.data_is_empty() || sandwich_validators.owner != ctx.program_id {
+        return err!(GatekeeperError::InvalidPda);
+    }
+
+    // The account must be the namespace's PDA for this epoch under either seed scheme
+    let authority_key = ctx.accounts.multisig_authority.key();
+    let (expected_pda, _) = Pubkey::find_program_address(
+        &[SandwichValidators::SEED_PREFIX, authority_key.as_ref(), &epoch_arg.to_le_bytes()],
+        ctx.program_id,
+    );
+    let at_legacy_address = sandwich_validators.key() != expected_pda;
+    if at_legacy_address {
+        let legacy_pda = u16::try_from(epoch_arg).ok().map(|legacy_epoch| {
+            Pubkey::find_program_address(
+                &[SandwichValidators::LEGACY_SEED_PREFIX, authority_key.as_ref(), &legacy_epoch.to_le_bytes()],
+                ctx.program_id,
+            ).0
+        });
+        if legacy_pda != Some(sandwich_validators.key()) {
+            return err!(GatekeeperError::InvalidPda);
+        }
+    }
+
+    let header = Header::read(&sandwich_validators.try_borrow_data()?)?;
+    if header.epoch != epoch_arg {
+        return err!(GatekeeperError::EpochMismatch);
+    }
+
+    let flags = if at_legacy_address {
+        header.flags | SandwichValidators::FLAG_LEGACY_SEEDS
+    } else {
+        header.flags
+    };
+
+    if header.version == VERSION_CURRENT {
+        // Legacy-address accounts migrated before the flag existed only need it recorded
+        if flags != header.flags {
+            Header { flags, ..header }.write(&mut sandwich_validators.try_borrow_mut_data()?);
+        }
+        #[cfg(feature = "debug-logs")]
+        msg!("Account already uses layout version {}", VERSION_CURRENT);
+        return Ok(());
+    }
+
+    // `Header::read` only returns supported versions, none of which has a larger header
+    let old_offset = header.data_offset();
+    if old_offset < SandwichValidators::DATA_OFFSET {
+        let current_size = sandwich_validators.data_len();
+        let new_size = current_size
+            .checked_add(SandwichValidators::DATA_OFFSET - old_offset)
+            .ok_or(GatekeeperError::SlotOutOfRange)?;
+
+        // Top up rent for the larger header
+        let required_lamports = Rent::get()?.minimum_balance(new_size);
+        let additional_lamports = required_lamports.saturating_sub(sandwich_validators.lamports());
+        if additional_lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: payer.to_account_info(),
+                        to: sandwich_validators.to_account_info(),
+                    },
+                ),
+                additional_lamports,
+            )?;
+        }
+
+        sandwich_validators.resize(new_size)?;
+
+        // Shift the bitmap past the new header; the header is rewritten over the gap below
+        let mut data = sandwich_validators.try_borrow_mut_data()?;
+        data.copy_within(old_offset..current_size, SandwichValidators::DATA_OFFSET);
+    }
+
+    // Versions before the counter existed need one full scan to seed `gated_count`
+    let mut data = sandwich_validators.try_borrow_mut_data()?;
+    let gated_count = count_gated_slots(&data[SandwichValidators::DATA_OFFSET..]);
+    Header { version: VERSION_CURRENT, gated_count, flags, ..header }.write(&mut data);
+    drop(data);
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Migrated SandwichValidators for epoch {} from layout version {} to {}", epoch_arg, header.version, VERSION_CURRENT);
+
+    emit!(SandwichValidatorsMigrated {
+        authority: authority_key,
+        epoch: epoch_arg,
+        from_version: header.version,
+        to_version: VERSION_CURRENT,
+    });
+
+    Ok(())
+}
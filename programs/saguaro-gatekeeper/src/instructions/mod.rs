@@ -1,16 +1,92 @@
 pub mod close_sandwich_validator;
+pub mod close_legacy_sandwich_validator;
+pub mod migrate_sandwich_validators;
+pub mod seal_sandwich_validators;
+pub mod unseal_sandwich_validators;
 pub mod set_sandwich_validators;
 pub mod modify_sandwich_validators;
+pub mod modify_sandwich_validators_ranges;
+pub mod modify_sandwich_validators_compact;
 pub mod validate_sandwich_validators;
+pub mod validate_v2;
+pub mod validate_strict;
+pub mod validate_shadow;
+pub mod validate_with_overrides;
+pub mod validate_epoch_boundary;
+pub mod validate_sources;
+pub mod query_slots;
 pub mod expand_sandwich_validators_bitmap;
 pub mod append_data_sandwich_validators_bitmap;
+pub mod write_bitmap_chunk;
+pub mod write_bitmap_runs;
 pub mod clear_data_sandwich_validators_bitmap;
+pub mod initialize_gatekeeper_config;
+pub mod update_gatekeeper_config;
+pub mod set_override_mode;
+pub mod initialize_authority_record;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod cancel_authority_proposal;
+pub mod recover_authority;
+pub mod set_authority_guardian;
+pub mod initialize_signer_set;
+pub mod update_signer_set;
+pub mod initialize_role_table;
+pub mod update_role_table;
+pub mod initialize_committee;
+pub mod update_committee;
+pub mod join_committee;
+pub mod set_integrator_overrides;
+pub mod close_integrator_overrides;
+pub mod initialize_integrator_profile;
+pub mod update_integrator_profile;
+pub mod register_provider;
+pub mod update_provider;
+pub mod deprecate_provider;
 
 // Export specific handlers instead of using glob imports to avoid ambiguity
 pub use close_sandwich_validator::handler as close_sandwich_validator_handler;
+pub use close_legacy_sandwich_validator::handler as close_legacy_sandwich_validator_handler;
+pub use migrate_sandwich_validators::handler as migrate_sandwich_validators_handler;
+pub use seal_sandwich_validators::handler as seal_sandwich_validators_handler;
+pub use unseal_sandwich_validators::handler as unseal_sandwich_validators_handler;
 pub use set_sandwich_validators::handler as set_sandwich_validators_handler;
 pub use modify_sandwich_validators::handler as modify_sandwich_validators_handler;
+pub use modify_sandwich_validators_ranges::handler as modify_sandwich_validators_ranges_handler;
+pub use modify_sandwich_validators_compact::handler as modify_sandwich_validators_compact_handler;
 pub use validate_sandwich_validators::handler as validate_sandwich_validators_handler;
+pub use validate_v2::handler as validate_v2_handler;
+pub use validate_strict::handler as validate_strict_handler;
+pub use validate_shadow::handler as validate_shadow_handler;
+pub use validate_with_overrides::handler as validate_with_overrides_handler;
+pub use validate_epoch_boundary::handler as validate_epoch_boundary_handler;
+pub use validate_sources::handler as validate_sources_handler;
+pub use query_slots::handler as query_slots_handler;
 pub use expand_sandwich_validators_bitmap::handler as expand_sandwich_validators_bitmap_handler;
 pub use append_data_sandwich_validators_bitmap::handler as append_data_sandwich_validators_bitmap_handler;
+pub use write_bitmap_chunk::handler as write_bitmap_chunk_handler;
+pub use write_bitmap_runs::handler as write_bitmap_runs_handler;
 pub use clear_data_sandwich_validators_bitmap::handler as clear_data_sandwich_validators_bitmap_handler;
+pub use initialize_gatekeeper_config::handler as initialize_gatekeeper_config_handler;
+pub use update_gatekeeper_config::handler as update_gatekeeper_config_handler;
+pub use set_override_mode::handler as set_override_mode_handler;
+pub use initialize_authority_record::handler as initialize_authority_record_handler;
+pub use propose_authority::handler as propose_authority_handler;
+pub use accept_authority::handler as accept_authority_handler;
+pub use cancel_authority_proposal::handler as cancel_authority_proposal_handler;
+pub use recover_authority::handler as recover_authority_handler;
+pub use set_authority_guardian::handler as set_authority_guardian_handler;
+pub use initialize_signer_set::handler as initialize_signer_set_handler;
+pub use update_signer_set::handler as update_signer_set_handler;
+pub use initialize_role_table::handler as initialize_role_table_handler;
+pub use update_role_table::handler as update_role_table_handler;
+pub use initialize_committee::handler as initialize_committee_handler;
+pub use update_committee::handler as update_committee_handler;
+pub use join_committee::handler as join_committee_handler;
+pub use set_integrator_overrides::handler as set_integrator_overrides_handler;
+pub use close_integrator_overrides::handler as close_integrator_overrides_handler;
+pub use initialize_integrator_profile::handler as initialize_integrator_profile_handler;
+pub use update_integrator_profile::handler as update_integrator_profile_handler;
+pub use register_provider::handler as register_provider_handler;
+pub use update_provider::handler as update_provider_handler;
+pub use deprecate_provider::handler as deprecate_provider_handler;
\ No newline at end of file
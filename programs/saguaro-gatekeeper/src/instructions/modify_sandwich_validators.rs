@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::{ModifySandwichValidators, SandwichValidatorsUpdated, MAX_SLOTS_PER_TRANSACTION, GatekeeperError, SLOTS_PER_EPOCH, INITIAL_BITMAP_SIZE_BYTES, FULL_BITMAP_SIZE_BYTES};
+use crate::{ModifySandwichValidators, Role, SandwichValidators, SandwichValidatorsUpdated, MAX_SLOTS_PER_TRANSACTION, GatekeeperError, SLOTS_PER_EPOCH, INITIAL_ACCOUNT_SIZE, FULL_BITMAP_SIZE_BYTES};
+use crate::authority::require_authority_or_quorum;
+use crate::layout::Header;
 
 /// Handler for the `modify_sandwich_validators` instruction.
 /// 
@@ -8,21 +10,30 @@ use crate::{ModifySandwichValidators, SandwichValidatorsUpdated, MAX_SLOTS_PER_T
 /// Use `expand_sandwich_validators_bitmap` first if you need more capacity.
 /// 
 /// # Slot Range Limits
-/// - Each epoch contains exactly 432,000 slots (SLOTS_PER_EPOCH)
-/// - Slots must be within the epoch range: [epoch_start, epoch_start + 432,000)
-/// - Bitmap size determines how many of these 432,000 slots can be tracked
-/// - Full bitmap (54,000 bytes) can track all 432,000 slots in an epoch
+/// - Epoch boundaries come from the GatekeeperConfig `slots_per_epoch_source`
+/// - Slots must be within the epoch range: [epoch_start, epoch_start + slots_in_epoch)
+/// - Bitmap size determines how many of the epoch's slots can be tracked
+/// - A full bitmap holds one bit per slot (54,000 bytes for a 432,000-slot epoch), or one
+///   bit per 4-slot leader window (13,500 bytes) when created with `slots_per_bit` = 4
+///
+/// # Leader-Window Accounts
+/// Each listed slot stands for its whole window: gating or ungating it gates or ungates
+/// the window. Two gated slots in one window are `DuplicateSlots`, and a window with
+/// slots in both lists is `OverlapSlots`. Counts in the event and header are windows.
 /// 
 /// # Compute Optimization
 /// This handler uses lazy loading and direct memory operations to minimize compute usage:
 /// - Avoids full deserialization of the bitmap account
 /// - Uses stack-based duplicate checking for small arrays
 /// - Performs direct bit manipulation on borrowed account data
-pub fn handler(ctx: Context<ModifySandwichValidators>, epoch_arg: u16, mut slots_to_gate: Vec<u64>, mut slots_to_ungate: Vec<u64>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ModifySandwichValidators<'info>>, epoch_arg: u64, mut slots_to_gate: Vec<u64>, mut slots_to_ungate: Vec<u64>) -> Result<()> {
     // Compile-time assertion to ensure bitmap size is consistent with slot count
     const _: () = assert!(FULL_BITMAP_SIZE_BYTES * 8 >= SLOTS_PER_EPOCH, "Full bitmap must be able to hold all epoch slots");
-    // Validate that neither operation exceeds per-transaction limits
+    // Refuse to modify bitmaps while the gatekeeper is frozen
+    let config = &ctx.accounts.gatekeeper_config;
+    config.require_writable()?;
 
+    // Validate that neither operation exceeds per-transaction limits
     if slots_to_gate.len() + slots_to_ungate.len() > MAX_SLOTS_PER_TRANSACTION {
         return err!(GatekeeperError::TooManySlots);
     }
@@ -57,23 +68,40 @@ pub fn handler(ctx: Context<ModifySandwichValidators>, epoch_arg: u16, mut slots
     let sandwich_validators_ai = &ctx.accounts.sandwich_validators;
     let multisig_authority = &ctx.accounts.multisig_authority;
 
+    // Ungaters may only remove gates; adding any gate needs the Writer role
+    let roles: &[Role] = if slots_to_gate.is_empty() {
+        &[Role::Writer, Role::Ungater]
+    } else {
+        &[Role::Writer]
+    };
+    require_authority_or_quorum(
+        multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        roles,
+        ctx.accounts.signer_set.as_deref(),
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+
     // Validate account exists and is owned by our program
     if sandwich_validators_ai.data_is_empty() || *sandwich_validators_ai.owner != *ctx.program_id {
         return err!(GatekeeperError::InvalidPda);
     }
 
-    // Calculate epoch start slot
-    let epoch_start_slot = (epoch_arg as u64) * SLOTS_PER_EPOCH as u64;
+    // Calculate epoch start slot and length from the configured source
+    let (epoch_start_slot, slots_in_epoch) = config.slots_per_epoch_source.epoch_bounds(epoch_arg)?;
 
     // Lazy loading - only read the epoch for validation
     let data_borrow = sandwich_validators_ai.try_borrow_data()?;
     
-    // SandwichValidators structure: discriminator(8) + epoch(2) + bump(1) + padding(5) + bitmap_data
-    const HEADER_SIZE: usize = 16; // SandwichValidators::DATA_OFFSET
+    // Only unsealed accounts on the current layout are writable; older versions must be migrated first
+    let mut header = Header::read_writable(&data_borrow)?;
+    const HEADER_SIZE: usize = SandwichValidators::DATA_OFFSET;
     
     // Validate epoch matches
-    let stored_epoch = u16::from_le_bytes([data_borrow[8], data_borrow[9]]);
-    if stored_epoch != epoch_arg {
+    if header.epoch != epoch_arg {
         return err!(GatekeeperError::EpochMismatch);
     }
     
@@ -81,8 +109,10 @@ pub fn handler(ctx: Context<ModifySandwichValidators>, epoch_arg: u16, mut slots
     let bitmap_len = data_borrow.len() - HEADER_SIZE;
     
     // Validate bitmap size - allow both initial and expanded sizes
-    // For 432,000 slots, we need exactly 54,000 bytes (432,000 bits / 8)
-    if bitmap_len < INITIAL_BITMAP_SIZE_BYTES || bitmap_len > FULL_BITMAP_SIZE_BYTES {
+    // The fully expanded bitmap follows the epoch length (54,000 bytes for 432,000 slots);
+    // accounts created at the initial 10KB size remain valid on shorter epochs
+    let max_account_size = SandwichValidators::target_account_size(slots_in_epoch, header.slots_per_bit)?.max(INITIAL_ACCOUNT_SIZE);
+    if bitmap_len == 0 || bitmap_len > max_account_size - HEADER_SIZE {
         return err!(GatekeeperError::InvalidPda);
     }
     
@@ -91,13 +121,17 @@ pub fn handler(ctx: Context<ModifySandwichValidators>, epoch_arg: u16, mut slots
     
     drop(data_borrow);
 
+    // Each bit covers `slots_per_bit` consecutive slots (a whole leader window on coarse accounts)
+    let slots_per_bit = usize::from(header.slots_per_bit);
+
     // Calculate max trackable slots based on current bitmap size with comprehensive overflow protection
-    // Ensure we don't exceed the epoch boundary of 432,000 slots
+    // Ensure we don't exceed the epoch boundary
     let bitmap_max_slots = bitmap_len
         .checked_mul(8)
+        .and_then(|bits| bits.checked_mul(slots_per_bit))
         .filter(|&slots| slots <= usize::MAX / 2) // Additional safety margin
         .ok_or(GatekeeperError::SlotOutOfRange)?;
-    let epoch_max_slots = SLOTS_PER_EPOCH;
+    let epoch_max_slots = usize::try_from(slots_in_epoch).map_err(|_| GatekeeperError::SlotOutOfRange)?;
     let max_trackable_slots = std::cmp::min(bitmap_max_slots, epoch_max_slots);
     let max_trackable_slot = epoch_start_slot
         .checked_add(max_trackable_slots as u64)
@@ -143,16 +177,30 @@ pub fn handler(ctx: Context<ModifySandwichValidators>, epoch_arg: u16, mut slots
         Ok(())
     }
 
-    // Calculate epoch end for validation (epoch contains exactly SLOTS_PER_EPOCH slots)
-    let epoch_end_slot = epoch_start_slot.checked_add(SLOTS_PER_EPOCH as u64).ok_or(GatekeeperError::SlotOutOfRange)?;
+    // Calculate epoch end for validation (epoch contains exactly slots_in_epoch slots)
+    let epoch_end_slot = epoch_start_slot.checked_add(slots_in_epoch).ok_or(GatekeeperError::SlotOutOfRange)?;
     
     // Validate both arrays against current bitmap capacity and epoch boundaries
     check_duplicates_and_validate(&slots_to_ungate, epoch_start_slot, max_trackable_slot, epoch_end_slot - 1)?;
     check_duplicates_and_validate(&slots_to_gate, epoch_start_slot, max_trackable_slot, epoch_end_slot - 1)?;
 
+    // On leader-window accounts distinct slots can share a bit; both lists are sorted, so
+    // their windows are too
+    if slots_per_bit > 1 {
+        let window_of = |slot: &u64| header.bit_index(slot - epoch_start_slot);
+        let gate_windows: Vec<usize> = slots_to_gate.iter().map(window_of).collect();
+        let ungate_windows: Vec<usize> = slots_to_ungate.iter().map(window_of).collect();
+        if gate_windows.windows(2).any(|pair| pair[0] == pair[1]) {
+            return err!(GatekeeperError::DuplicateSlots);
+        }
+        if gate_windows.iter().any(|window| ungate_windows.binary_search(window).is_ok()) {
+            return err!(GatekeeperError::OverlapSlots);
+        }
+    }
+
     // Direct bit manipulation functions that work with current bitmap size and respect epoch boundaries
     #[inline(always)]
-    fn is_slot_gated_direct(data: &[u8], slot: u64, epoch_start: u64, bitmap_len: usize) -> bool {
+    fn is_slot_gated_direct(data: &[u8], slot: u64, epoch_start: u64, epoch_max_slots: usize, bitmap_len: usize, slots_per_bit: usize) -> bool {
         // Validate inputs first
         if slot < epoch_start || bitmap_len == 0 {
             return false;
@@ -164,17 +212,18 @@ pub fn handler(ctx: Context<ModifySandwichValidators>, epoch_arg: u16, mut slots
         };
         
         // Calculate max trackable slots respecting both bitmap size and epoch boundary
-        let bitmap_max_slots = match bitmap_len.checked_mul(8) {
-            Some(slots) if slots <= SLOTS_PER_EPOCH => slots,
-            _ => return false, // Overflow or exceeds epoch, treat as not gated
+        let bitmap_max_slots = match bitmap_len.checked_mul(8).and_then(|bits| bits.checked_mul(slots_per_bit)) {
+            Some(slots) => slots.min(epoch_max_slots),
+            _ => return false, // Overflow, treat as not gated
         };
         
         if slot_offset >= bitmap_max_slots {
             return false;
         }
         
-        let byte_index = slot_offset / 8;
-        let bit_index = slot_offset % 8;
+        let bit = slot_offset / slots_per_bit;
+        let byte_index = bit / 8;
+        let bit_index = bit % 8;
         let byte_pos = match HEADER_SIZE.checked_add(byte_index) {
             Some(pos) if pos < data.len() => pos,
             _ => return false, // Overflow or out of bounds, treat as not gated
@@ -184,7 +233,7 @@ pub fn handler(ctx: Context<ModifySandwichValidators>, epoch_arg: u16, mut slots
     }
     
     #[inline(always)]
-    fn set_slot_gated_direct(data: &mut [u8], slot: u64, epoch_start: u64, bitmap_len: usize, gated: bool) -> Result<()> {
+    fn set_slot_gated_direct(data: &mut [u8], slot: u64, epoch_start: u64, epoch_max_slots: usize, bitmap_len: usize, slots_per_bit: usize, gated: bool) -> Result<()> {
         // Validate inputs first
         if slot < epoch_start || bitmap_len == 0 {
             return err!(GatekeeperError::SlotOutOfRange);
@@ -196,15 +245,17 @@ pub fn handler(ctx: Context<ModifySandwichValidators>, epoch_arg: u16, mut slots
         // Calculate max trackable slots respecting both bitmap size and epoch boundary
         let bitmap_max_slots = bitmap_len
             .checked_mul(8)
-            .filter(|&slots| slots <= SLOTS_PER_EPOCH)
+            .and_then(|bits| bits.checked_mul(slots_per_bit))
+            .map(|slots| slots.min(epoch_max_slots))
             .ok_or(GatekeeperError::SlotOutOfRange)?;
         
         if slot_offset >= bitmap_max_slots {
             return err!(GatekeeperError::SlotOutOfRange);
         }
         
-        let byte_index = slot_offset / 8;
-        let bit_index = slot_offset % 8;
+        let bit = slot_offset / slots_per_bit;
+        let byte_index = bit / 8;
+        let bit_index = bit % 8;
         let byte_pos = HEADER_SIZE.checked_add(byte_index)
             .filter(|&pos| pos < data.len())
             .ok_or(GatekeeperError::SlotOutOfRange)?;
@@ -218,8 +269,8 @@ pub fn handler(ctx: Context<ModifySandwichValidators>, epoch_arg: u16, mut slots
         Ok(())
     }
 
-    let mut slots_added = 0u16;
-    let mut slots_removed = 0u16;
+    let mut slots_added = 0u32;
+    let mut slots_removed = 0u32;
 
     // Get mutable access to account data for direct bit manipulation
     let mut data = sandwich_validators_ai.try_borrow_mut_data()?;
@@ -227,8 +278,8 @@ pub fn handler(ctx: Context<ModifySandwichValidators>, epoch_arg: u16, mut slots
     // Step 1: Ungate slots if specified
     if !slots_to_ungate.is_empty() {
         for slot in &slots_to_ungate {
-            if is_slot_gated_direct(&data, *slot, epoch_start_slot, bitmap_len) {
-                set_slot_gated_direct(&mut data, *slot, epoch_start_slot, bitmap_len, false)?;
+            if is_slot_gated_direct(&data, *slot, epoch_start_slot, epoch_max_slots, bitmap_len, slots_per_bit) {
+                set_slot_gated_direct(&mut data, *slot, epoch_start_slot, epoch_max_slots, bitmap_len, slots_per_bit, false)?;
                 slots_removed += 1;
             }
         }
@@ -238,24 +289,27 @@ pub fn handler(ctx: Context<ModifySandwichValidators>, epoch_arg: u16, mut slots
     if !slots_to_gate.is_empty() {
         // Check for already gated slots first
         for slot in &slots_to_gate {
-            if is_slot_gated_direct(&data, *slot, epoch_start_slot, bitmap_len) {
+            if is_slot_gated_direct(&data, *slot, epoch_start_slot, epoch_max_slots, bitmap_len, slots_per_bit) {
                 return err!(GatekeeperError::DuplicateSlots);
             }
         }
 
         // Gate new slots in bitmap
         for slot in &slots_to_gate {
-            set_slot_gated_direct(&mut data, *slot, epoch_start_slot, bitmap_len, true)?;
+            set_slot_gated_direct(&mut data, *slot, epoch_start_slot, epoch_max_slots, bitmap_len, slots_per_bit, true)?;
             slots_added += 1;
         }
     }
 
-    drop(data);
+    // Keep the header counter exact without rescanning the bitmap: only slots whose
+    // bit actually flipped were counted above
+    header.gated_count = header.gated_count
+        .checked_sub(slots_removed)
+        .and_then(|count| count.checked_add(slots_added))
+        .ok_or(GatekeeperError::SlotOutOfRange)?;
+    header.write(&mut data);
 
-    // For monitoring purposes, emit the net change as an approximation
-    // Note: The total_slots field represents the net change for this operation only,
-    // not the actual total gated slots in the bitmap to avoid expensive scanning.
-    let net_change_approximation = slots_added.saturating_sub(slots_removed);
+    drop(data);
     
     // Emit event for monitoring - only emit if there were actual changes
     if slots_added > 0 || slots_removed > 0 {
@@ -264,7 +318,7 @@ pub fn handler(ctx: Context<ModifySandwichValidators>, epoch_arg: u16, mut slots
             epoch: epoch_arg,
             slots_added,
             slots_removed,
-            total_slots: net_change_approximation, // Net change for this operation only
+            total_slots: header.gated_count,
         });
     }
     
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use crate::{ModifySandwichValidatorsCompact, Role, SandwichValidators, SandwichValidatorsUpdated, GatekeeperError, MAX_COMPACT_SLOTS_PER_TRANSACTION, INITIAL_ACCOUNT_SIZE};
+use crate::authority::require_authority_or_quorum;
+use crate::encoding::SlotOffsets;
+use crate::layout::Header;
+
+/// Handler for the `modify_sandwich_validators_compact` instruction.
+///
+/// Compact counterpart of `modify_sandwich_validators`. Slots are sent as offsets from
+/// the epoch start, encoded as LEB128 gaps (see [`SlotOffsets`]): the first value is the
+/// offset of the first slot and every later value the distance to the previous one. A
+/// typical leader schedule encodes in one or two bytes per slot instead of eight.
+///
+/// # Validation
+/// - At most `MAX_COMPACT_SLOTS_PER_TRANSACTION` slots across both lists
+/// - The gap encoding forces each list to be strictly increasing; a zero gap is a
+///   duplicate (`DuplicateSlots`), and malformed varints fail with `InvalidSlotEncoding`
+/// - No slot may be both gated and ungated (`OverlapSlots`)
+/// - Every offset must lie within the epoch and the current bitmap capacity
+/// - As with the slot-list instruction, gating an already gated slot is `DuplicateSlots`
+/// - On leader-window accounts each offset stands for its window: two gated offsets in
+///   one window are `DuplicateSlots`, and a window in both lists is `OverlapSlots`
+///
+/// # Compute Optimization
+/// - Offsets are decoded in place, so no `Vec<u64>` or set is ever built
+/// - Sorted lists make the bounds check a single comparison against the last offset
+///   and the gate/ungate overlap check a linear merge
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ModifySandwichValidatorsCompact<'info>>,
+    epoch_arg: u64,
+    gate_offsets: Vec<u8>,
+    ungate_offsets: Vec<u8>,
+) -> Result<()> {
+    // Refuse to modify bitmaps while the gatekeeper is frozen
+    let config = &ctx.accounts.gatekeeper_config;
+    config.require_writable()?;
+
+    let (gate_count, last_gate) = SlotOffsets::new(&gate_offsets).validate()?;
+    let (ungate_count, last_ungate) = SlotOffsets::new(&ungate_offsets).validate()?;
+    if gate_count + ungate_count > MAX_COMPACT_SLOTS_PER_TRANSACTION {
+        return err!(GatekeeperError::TooManySlots);
+    }
+    if gate_count == 0 && ungate_count == 0 {
+        return Ok(());
+    }
+
+    let sandwich_validators_ai = &ctx.accounts.sandwich_validators;
+    let multisig_authority = &ctx.accounts.multisig_authority;
+
+    // Ungaters may only remove gates; adding any gate needs the Writer role
+    let roles: &[Role] = if gate_count == 0 {
+        &[Role::Writer, Role::Ungater]
+    } else {
+        &[Role::Writer]
+    };
+    require_authority_or_quorum(
+        multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        roles,
+        ctx.accounts.signer_set.as_deref(),
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+
+    // Validate account exists and is owned by our program
+    if sandwich_validators_ai.data_is_empty() || *sandwich_validators_ai.owner != *ctx.program_id {
+        return err!(GatekeeperError::InvalidPda);
+    }
+
+    let (_, slots_in_epoch) = config.slots_per_epoch_source.epoch_bounds(epoch_arg)?;
+
+    let mut data = sandwich_validators_ai.try_borrow_mut_data()?;
+
+    // Only unsealed accounts on the current layout are writable; older versions must be migrated first
+    let mut header = Header::read_writable(&data)?;
+    if header.epoch != epoch_arg {
+        return err!(GatekeeperError::EpochMismatch);
+    }
+
+    let data_offset = header.data_offset();
+    let bitmap_len = data.len() - data_offset;
+    let max_account_size = SandwichValidators::target_account_size(slots_in_epoch, header.slots_per_bit)?.max(INITIAL_ACCOUNT_SIZE);
+    if bitmap_len == 0 || bitmap_len > max_account_size - data_offset {
+        return err!(GatekeeperError::InvalidPda);
+    }
+
+    // Offsets must fall within both the epoch and the bitmap's current capacity
+    let trackable_slots = (bitmap_len as u64)
+        .checked_mul(8 * u64::from(header.slots_per_bit))
+        .ok_or(GatekeeperError::SlotOutOfRange)?
+        .min(slots_in_epoch);
+    if last_gate.max(last_ungate).is_some_and(|last| u64::from(last) >= trackable_slots) {
+        return err!(GatekeeperError::SlotOutOfRange);
+    }
+
+    // Both lists are sorted, so one merge pass finds any bit (slot, or leader window)
+    // present in both
+    let mut gates = SlotOffsets::new(&gate_offsets);
+    let mut ungates = SlotOffsets::new(&ungate_offsets);
+    let mut gate = gates.next().transpose()?;
+    let mut ungate = ungates.next().transpose()?;
+    while let (Some(a), Some(b)) = (gate, ungate) {
+        match header.bit_index(a.into()).cmp(&header.bit_index(b.into())) {
+            core::cmp::Ordering::Less => gate = gates.next().transpose()?,
+            core::cmp::Ordering::Greater => ungate = ungates.next().transpose()?,
+            core::cmp::Ordering::Equal => return err!(GatekeeperError::OverlapSlots),
+        }
+    }
+
+    let bitmap = &mut data[data_offset..];
+
+    // Step 1: Ungate slots, counting only those that were gated
+    let mut slots_removed = 0u32;
+    for offset in SlotOffsets::new(&ungate_offsets) {
+        let bit = header.bit_index(offset?.into());
+        let mask = 1u8 << (bit % 8);
+        if bitmap[bit / 8] & mask != 0 {
+            bitmap[bit / 8] &= !mask;
+            slots_removed += 1;
+        }
+    }
+
+    // Step 2: Gate slots; any failure reverts the whole transaction
+    let mut slots_added = 0u32;
+    for offset in SlotOffsets::new(&gate_offsets) {
+        let bit = header.bit_index(offset?.into());
+        let mask = 1u8 << (bit % 8);
+        if bitmap[bit / 8] & mask != 0 {
+            return err!(GatekeeperError::DuplicateSlots);
+        }
+        bitmap[bit / 8] |= mask;
+        slots_added += 1;
+    }
+
+    header.gated_count = header.gated_count
+        .checked_sub(slots_removed)
+        .and_then(|count| count.checked_add(slots_added))
+        .ok_or(GatekeeperError::SlotOutOfRange)?;
+    header.write(&mut data);
+    drop(data);
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Compact update for epoch {}: {} slots gated, {} ungated", epoch_arg, slots_added, slots_removed);
+
+    if slots_added > 0 || slots_removed > 0 {
+        emit!(SandwichValidatorsUpdated {
+            authority: multisig_authority.key(),
+            epoch: epoch_arg,
+            slots_added,
+            slots_removed,
+            total_slots: header.gated_count,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use crate::{ModifySandwichValidatorsRanges, Role, SandwichValidators, SandwichValidatorsUpdated, SlotRange, GatekeeperError, MAX_RANGES_PER_TRANSACTION, INITIAL_ACCOUNT_SIZE};
+use crate::authority::require_authority_or_quorum;
+use crate::bitmap::fill_range;
+use crate::layout::Header;
+
+/// Handler for the `modify_sandwich_validators_ranges` instruction.
+///
+/// Range-based counterpart of `modify_sandwich_validators`: each `SlotRange` gates or
+/// ungates `len` consecutive slots starting at `start`, so a leader window or a long
+/// stretch of an epoch costs one entry instead of one per slot.
+///
+/// # Validation
+/// - At most `MAX_RANGES_PER_TRANSACTION` ranges in total
+/// - Every range is non-empty and lies within [epoch_start, epoch_start + slots_in_epoch)
+///   and the current bitmap capacity
+/// - Ranges in the same list must not overlap (`DuplicateSlots`), and no gate range may
+///   overlap an ungate range (`OverlapSlots`)
+/// - Unlike the slot-list instruction, gating an already gated slot is not an error;
+///   only slots whose state changes are counted
+/// - On leader-window accounts a range applies to every window it touches, and no window
+///   may be touched by both lists (`OverlapSlots`); counts are then windows
+///
+/// # Compute Optimization
+/// - Bytes fully covered by a range are filled whole; only the edge bytes are masked
+/// - Overlap checks are pairwise over at most `MAX_RANGES_PER_TRANSACTION` ranges, without allocation
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ModifySandwichValidatorsRanges<'info>>,
+    epoch_arg: u64,
+    ranges_to_gate: Vec<SlotRange>,
+    ranges_to_ungate: Vec<SlotRange>,
+) -> Result<()> {
+    // Refuse to modify bitmaps while the gatekeeper is frozen
+    let config = &ctx.accounts.gatekeeper_config;
+    config.require_writable()?;
+
+    if ranges_to_gate.len() + ranges_to_ungate.len() > MAX_RANGES_PER_TRANSACTION {
+        return err!(GatekeeperError::TooManySlots);
+    }
+    if ranges_to_gate.is_empty() && ranges_to_ungate.is_empty() {
+        return Ok(());
+    }
+
+    let sandwich_validators_ai = &ctx.accounts.sandwich_validators;
+    let multisig_authority = &ctx.accounts.multisig_authority;
+
+    // Ungaters may only remove gates; adding any gate needs the Writer role
+    let roles: &[Role] = if ranges_to_gate.is_empty() {
+        &[Role::Writer, Role::Ungater]
+    } else {
+        &[Role::Writer]
+    };
+    require_authority_or_quorum(
+        multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        roles,
+        ctx.accounts.signer_set.as_deref(),
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+
+    // Validate account exists and is owned by our program
+    if sandwich_validators_ai.data_is_empty() || *sandwich_validators_ai.owner != *ctx.program_id {
+        return err!(GatekeeperError::InvalidPda);
+    }
+
+    let (epoch_start_slot, slots_in_epoch) = config.slots_per_epoch_source.epoch_bounds(epoch_arg)?;
+
+    let mut data = sandwich_validators_ai.try_borrow_mut_data()?;
+
+    // Only unsealed accounts on the current layout are writable; older versions must be migrated first
+    let mut header = Header::read_writable(&data)?;
+    if header.epoch != epoch_arg {
+        return err!(GatekeeperError::EpochMismatch);
+    }
+
+    let data_offset = header.data_offset();
+    let bitmap_len = data.len() - data_offset;
+    let max_account_size = SandwichValidators::target_account_size(slots_in_epoch, header.slots_per_bit)?.max(INITIAL_ACCOUNT_SIZE);
+    if bitmap_len == 0 || bitmap_len > max_account_size - data_offset {
+        return err!(GatekeeperError::InvalidPda);
+    }
+
+    // Ranges must end within both the epoch and the bitmap's current capacity
+    let trackable_slots = (bitmap_len as u64)
+        .checked_mul(8 * u64::from(header.slots_per_bit))
+        .ok_or(GatekeeperError::SlotOutOfRange)?
+        .min(slots_in_epoch);
+    let max_trackable_slot = epoch_start_slot
+        .checked_add(trackable_slots)
+        .ok_or(GatekeeperError::SlotOutOfRange)?;
+
+    for range in ranges_to_gate.iter().chain(&ranges_to_ungate) {
+        let in_bounds = range.len > 0
+            && range.start >= epoch_start_slot
+            && range.end().is_some_and(|end| end <= max_trackable_slot);
+        if !in_bounds {
+            return err!(GatekeeperError::SlotOutOfRange);
+        }
+    }
+
+    fn check_no_overlaps(ranges: &[SlotRange]) -> Result<()> {
+        for (i, a) in ranges.iter().enumerate() {
+            if ranges[i + 1..].iter().any(|b| a.overlaps(b)) {
+                return err!(GatekeeperError::DuplicateSlots);
+            }
+        }
+        Ok(())
+    }
+    check_no_overlaps(&ranges_to_gate)?;
+    check_no_overlaps(&ranges_to_ungate)?;
+
+    // The bits a range covers, as a range of bit indices; identical to the slot offsets
+    // unless each bit covers a leader window
+    let bits_of = |range: &SlotRange| {
+        let offset = range.start - epoch_start_slot;
+        let first = header.bit_index(offset) as u64;
+        let end = header.bits_for_slots(offset + u64::from(range.len));
+        SlotRange { start: first, len: (end - first) as u32 }
+    };
+    if ranges_to_gate.iter().any(|a| ranges_to_ungate.iter().any(|b| bits_of(a).overlaps(&bits_of(b)))) {
+        return err!(GatekeeperError::OverlapSlots);
+    }
+
+    // Apply ungates before gates, as the slot-list instruction does
+    let bitmap = &mut data[data_offset..];
+    let mut slots_removed = 0u32;
+    for range in &ranges_to_ungate {
+        let bits = bits_of(range);
+        slots_removed += fill_range(bitmap, bits.start as usize, bits.len as usize, false);
+    }
+    let mut slots_added = 0u32;
+    for range in &ranges_to_gate {
+        let bits = bits_of(range);
+        slots_added += fill_range(bitmap, bits.start as usize, bits.len as usize, true);
+    }
+
+    header.gated_count = header.gated_count
+        .checked_sub(slots_removed)
+        .and_then(|count| count.checked_add(slots_added))
+        .ok_or(GatekeeperError::SlotOutOfRange)?;
+    header.write(&mut data);
+    drop(data);
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Range update for epoch {}: {} slots gated, {} ungated", epoch_arg, slots_added, slots_removed);
+
+    if slots_added > 0 || slots_removed > 0 {
+        emit!(SandwichValidatorsUpdated {
+            authority: multisig_authority.key(),
+            epoch: epoch_arg,
+            slots_added,
+            slots_removed,
+            total_slots: header.gated_count,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::{ProposeAuthority, AuthorityProposed};
+
+/// Handler for the `propose_authority` instruction.
+///
+/// Records `new_authority` as pending. Proposals made by the active authority can be
+/// accepted immediately; any previous proposal is replaced.
+pub fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    let record = &mut ctx.accounts.authority_record;
+
+    record.pending_authority = Some(new_authority);
+    record.pending_unlock_timestamp = 0;
+
+    emit!(AuthorityProposed {
+        namespace: record.namespace,
+        proposed_by: ctx.accounts.authority.key(),
+        pending_authority: new_authority,
+        unlock_timestamp: 0,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::{QuerySlots, SlotQueryResult, GatekeeperConfig, GatekeeperError, MAX_QUERY_SLOTS};
+use crate::bitmap::{is_gated, next_ungated};
+use super::validate_sandwich_validators::locate_bitmap;
+
+/// Handles the `query_slots` instruction.
+///
+/// Answers the same question as `validate_sandwich_validators` for up to
+/// `MAX_QUERY_SLOTS` slots of the current epoch, but reports the answer through return
+/// data instead of failing, so CPI callers can route around a gated slot.
+///
+/// # Behavior
+/// - An empty `slots` list queries the current slot
+/// - Override mode `Paused` / `BlockAll`, and the fail policy for an unconfigured epoch,
+///   apply to every slot uniformly, with `from_bitmap` unset
+/// - Slots outside the current epoch are answered by the fail policy
+/// - `next_ungated_slot` searches the bitmap from the first queried slot to the end of the
+///   epoch; bits not yet uploaded count as ungated. It is `None` when the first queried
+///   slot lies past the current epoch, also when the override or fail policy decides
+pub fn handler(ctx: Context<QuerySlots>, slots: Vec<u64>) -> Result<SlotQueryResult> {
+    if slots.len() > MAX_QUERY_SLOTS {
+        return err!(GatekeeperError::TooManySlots);
+    }
+
+    let clock = &ctx.accounts.clock;
+    let current_slot = [clock.slot];
+    let slots: &[u64] = if slots.is_empty() { &current_slot } else { &slots };
+
+    let pda_account = &ctx.accounts.sandwich_validators;
+    let config = &GatekeeperConfig::effective(ctx.accounts.gatekeeper_config.as_deref());
+
+    let (first_slot, slots_in_epoch) = config.slots_per_epoch_source.epoch_bounds(clock.epoch)?;
+    let offset_of = |slot: u64| slot.checked_sub(first_slot).filter(|&offset| offset < slots_in_epoch);
+    let search_from = slots[0].max(first_slot);
+
+    // Every slot gets the same answer when the override or fail policy decides; the next
+    // ungated slot must still lie within the current epoch
+    let uniform = |gated: bool| SlotQueryResult {
+        epoch: clock.epoch,
+        gated_mask: if gated { u64::MAX >> (64 - slots.len()) } else { 0 },
+        next_ungated_slot: if gated { None } else { offset_of(search_from).map(|_| search_from) },
+        from_bitmap: false,
+    };
+
+    if let Some(outcome) = config.override_mode.forced_outcome() {
+        return Ok(uniform(outcome.is_err()));
+    }
+    let unconfigured_gated = config.fail_policy.unconfigured().is_err();
+
+    let header = match locate_bitmap(pda_account, &ctx.accounts.multisig_authority.key(), clock.epoch, config, ctx.program_id)? {
+        Some(header) => header,
+        None => return Ok(uniform(unconfigured_gated)),
+    };
+    let data = pda_account.try_borrow_data()?;
+    let bitmap = &data[header.data_offset()..];
+
+    let mut gated_mask = 0u64;
+    for (i, &slot) in slots.iter().enumerate() {
+        let gated = match offset_of(slot) {
+            Some(offset) => is_gated(bitmap, header.bit_index(offset)),
+            None => unconfigured_gated,
+        };
+        if gated {
+            gated_mask |= 1 << i;
+        }
+    }
+
+    // Searched bit by bit; an ungated leader window already under way at `search_from`
+    // answers with `search_from` itself
+    let next_ungated_slot = offset_of(search_from)
+        .and_then(|offset| {
+            next_ungated(bitmap, header.bit_index(offset), header.bits_for_slots(slots_in_epoch) as usize)
+                .map(|bit| (bit as u64 * u64::from(header.slots_per_bit)).max(offset))
+        })
+        .map(|offset| first_slot + offset);
+
+    Ok(SlotQueryResult {
+        epoch: clock.epoch,
+        gated_mask,
+        next_ungated_slot,
+        from_bitmap: true,
+    })
+}
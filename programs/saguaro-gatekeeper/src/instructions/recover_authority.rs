@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::{RecoverAuthority, AuthorityProposed, GatekeeperError};
+
+/// Handler for the `recover_authority` instruction.
+///
+/// Lets the guardian propose a replacement when the active authority key is lost.
+/// The proposal only becomes acceptable after `recovery_delay_seconds`, giving the
+/// active authority time to cancel it if the key was not actually lost.
+pub fn handler(ctx: Context<RecoverAuthority>, new_authority: Pubkey) -> Result<()> {
+    let record = &mut ctx.accounts.authority_record;
+
+    let clock = Clock::get()?;
+    let unlock_timestamp = clock
+        .unix_timestamp
+        .checked_add(record.recovery_delay_seconds)
+        .ok_or(GatekeeperError::InvalidRecoveryDelay)?;
+
+    record.pending_authority = Some(new_authority);
+    record.pending_unlock_timestamp = unlock_timestamp;
+
+    emit!(AuthorityProposed {
+        namespace: record.namespace,
+        proposed_by: ctx.accounts.guardian.key(),
+        pending_authority: new_authority,
+        unlock_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::{RegisterProvider, ProviderMetadata, ProviderRecord, ProviderStatus, ProviderUpdated, GatekeeperError, MAX_PROVIDERS};
+
+/// Handler for the `register_provider` instruction.
+///
+/// Appends an active record for `authority`. The admin signature is enforced by the
+/// `has_one` constraint on the account struct; the registry is created on first use.
+pub fn handler(ctx: Context<RegisterProvider>, authority: Pubkey, metadata: ProviderMetadata) -> Result<()> {
+    metadata.validate()?;
+
+    let registry = &mut ctx.accounts.provider_registry;
+    registry.bump = ctx.bumps.provider_registry;
+
+    if registry.providers.iter().any(|record| record.authority == authority) {
+        return err!(GatekeeperError::ProviderAlreadyRegistered);
+    }
+    registry.check_name_available(&metadata.name, &authority)?;
+    if registry.providers.len() >= MAX_PROVIDERS {
+        return err!(GatekeeperError::ProviderRegistryFull);
+    }
+
+    let record = ProviderRecord {
+        authority,
+        name: metadata.name,
+        metadata_uri: metadata.metadata_uri,
+        first_epoch: metadata.first_epoch,
+        last_epoch: metadata.last_epoch,
+        status: ProviderStatus::Active,
+    };
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Registered provider {} as {}", record.authority, record.name);
+
+    emit!(ProviderUpdated {
+        authority: record.authority,
+        name: record.name.clone(),
+        metadata_uri: record.metadata_uri.clone(),
+        first_epoch: record.first_epoch,
+        last_epoch: record.last_epoch,
+        status: record.status,
+    });
+
+    registry.providers.push(record);
+
+    Ok(())
+}
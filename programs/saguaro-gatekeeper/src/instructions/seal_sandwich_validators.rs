@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::{SealSandwichValidators, GatekeeperError, Role, SandwichValidators, SandwichValidatorsSealed};
+use crate::authority::require_authority_or_quorum;
+use crate::layout::Header;
+
+/// Handles the `seal_sandwich_validators` instruction.
+///
+/// Finalizes a fully uploaded bitmap: hashes the bitmap bytes with SHA-256, checks the
+/// result against the hash of the artifact built off-chain, then stores the hash and
+/// sets the sealed flag. A sealed account rejects every write until it is unsealed.
+///
+/// # Behavior
+/// - The hash covers every bitmap byte from the end of the header to the end of the account
+/// - The account must be expanded to the full size of its epoch
+/// - Like unsealing, sealing can be authorised by the namespace's signer set quorum
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, SealSandwichValidators<'info>>, epoch_arg: u64, expected_hash: [u8; 32]) -> Result<()> {
+    // Refuse to seal bitmaps while the gatekeeper is frozen
+    ctx.accounts.gatekeeper_config.require_writable()?;
+
+    require_authority_or_quorum(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        &[Role::Writer],
+        ctx.accounts.signer_set.as_deref(),
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+
+    let sandwich_validators = &ctx.accounts.sandwich_validators;
+    if sandwich_validators.data_is_empty() || *sandwich_validators.owner != *ctx.program_id {
+        return err!(GatekeeperError::InvalidPda);
+    }
+
+    let mut data = sandwich_validators.try_borrow_mut_data()?;
+    let mut header = Header::read_writable(&data)?;
+    if header.epoch != epoch_arg {
+        return err!(GatekeeperError::EpochMismatch);
+    }
+
+    // A partially expanded bitmap cannot be the finished artifact
+    let config = &ctx.accounts.gatekeeper_config;
+    let (_, slots_in_epoch) = config.slots_per_epoch_source.epoch_bounds(epoch_arg)?;
+    if data.len() < SandwichValidators::target_account_size(slots_in_epoch, header.slots_per_bit)? {
+        return err!(GatekeeperError::BitmapIncomplete);
+    }
+
+    let bitmap_hash = hash(&data[header.data_offset()..]).to_bytes();
+    if bitmap_hash != expected_hash {
+        return err!(GatekeeperError::BitmapHashMismatch);
+    }
+
+    header.flags |= SandwichValidators::FLAG_SEALED;
+    header.bitmap_hash = bitmap_hash;
+    header.write(&mut data);
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Sealed SandwichValidators for epoch {}", epoch_arg);
+
+    emit!(SandwichValidatorsSealed {
+        authority: ctx.accounts.multisig_authority.key(),
+        epoch: epoch_arg,
+        bitmap_hash,
+    });
+
+    Ok(())
+}
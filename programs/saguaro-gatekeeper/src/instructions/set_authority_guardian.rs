@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::{SetAuthorityGuardian, AuthorityGuardianUpdated, GatekeeperError};
+
+/// Handler for the `set_authority_guardian` instruction.
+///
+/// Sets or removes the guardian. A pending guardian recovery is left untouched; use
+/// `cancel_authority_proposal` to stop it.
+pub fn handler(
+    ctx: Context<SetAuthorityGuardian>,
+    guardian: Option<Pubkey>,
+    recovery_delay_seconds: i64,
+) -> Result<()> {
+    if recovery_delay_seconds < 0 {
+        return err!(GatekeeperError::InvalidRecoveryDelay);
+    }
+
+    let record = &mut ctx.accounts.authority_record;
+    record.guardian = guardian;
+    record.recovery_delay_seconds = recovery_delay_seconds;
+
+    emit!(AuthorityGuardianUpdated {
+        namespace: record.namespace,
+        guardian,
+        recovery_delay_seconds,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::{SetIntegratorOverrides, IntegratorOverrides, IntegratorOverridesUpdated, GatekeeperConfig, SlotRange};
+
+/// Handler for the `set_integrator_overrides` instruction.
+///
+/// Creates the owner's overrides for `epoch_arg` on first use and replaces both range
+/// lists on every call. Unlike the SandwichValidators write instructions this is not
+/// affected by the config's Frozen mode, since only the owner's own overrides change.
+pub fn handler(
+    ctx: Context<SetIntegratorOverrides>,
+    epoch_arg: u64,
+    allow: Vec<SlotRange>,
+    deny: Vec<SlotRange>,
+) -> Result<()> {
+    let config = GatekeeperConfig::effective(ctx.accounts.gatekeeper_config.as_deref());
+    let (first_slot, slots_in_epoch) = config.slots_per_epoch_source.epoch_bounds(epoch_arg)?;
+    IntegratorOverrides::validate_ranges(&allow, &deny, first_slot, slots_in_epoch)?;
+
+    let overrides = &mut ctx.accounts.integrator_overrides;
+    overrides.owner = ctx.accounts.owner.key();
+    overrides.epoch = epoch_arg;
+    overrides.bump = ctx.bumps.integrator_overrides;
+    overrides.allow = allow;
+    overrides.deny = deny;
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Set overrides of {} for epoch {}: {} allow, {} deny ranges", overrides.owner, epoch_arg, overrides.allow.len(), overrides.deny.len());
+
+    emit!(IntegratorOverridesUpdated {
+        owner: overrides.owner,
+        epoch: epoch_arg,
+        allow: overrides.allow.clone(),
+        deny: overrides.deny.clone(),
+    });
+
+    Ok(())
+}
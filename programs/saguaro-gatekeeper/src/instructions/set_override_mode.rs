@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::{SetOverrideMode, OverrideMode, OverrideModeChanged};
+
+/// Handler for the `set_override_mode` instruction.
+///
+/// Switches the validation override in a single instruction so an incident can be
+/// contained without clearing or rewriting any bitmap. The admin signature is
+/// enforced by the `has_one` constraint on the account struct.
+pub fn handler(ctx: Context<SetOverrideMode>, override_mode: OverrideMode) -> Result<()> {
+    let config = &mut ctx.accounts.gatekeeper_config;
+    let previous_mode = config.override_mode;
+    config.override_mode = override_mode;
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Override mode changed from {:?} to {:?}", previous_mode, override_mode);
+
+    emit!(OverrideModeChanged {
+        admin: config.admin,
+        previous_mode,
+        override_mode,
+        slot: Clock::get()?.slot,
+    });
+
+    Ok(())
+}
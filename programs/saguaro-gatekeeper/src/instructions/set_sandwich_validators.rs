@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{program::invoke_signed, system_instruction, system_program};
-use crate::{GatekeeperError, SetSandwichValidators, SandwichValidatorsSet, INITIAL_ACCOUNT_SIZE, SandwichValidators};
+use crate::{GatekeeperError, SetSandwichValidators, SandwichValidatorsSet, INITIAL_ACCOUNT_SIZE, SandwichValidators, Role};
+use crate::authority::require_authority_or_role;
+use crate::layout::Header;
 
 /// Handler for the `set_sandwich_validators` instruction.
 ///
@@ -8,17 +10,35 @@ use crate::{GatekeeperError, SetSandwichValidators, SandwichValidatorsSet, INITI
 /// This instruction only creates the SandwichValidators account with initial 10KB size.
 /// No slots are set - use `modify_sandwich_validators` for slot operations.
 /// Use `expand_sandwich_validators_bitmap` to expand to full epoch size.
+/// With `slots_per_bit` set to a leader window (4), each bit covers a whole window and
+/// the full epoch needs a quarter of the space (13,564 bytes for 432,000 slots).
 ///
 /// # Compute Optimization
 /// This handler focuses solely on account creation:
 /// - Direct account creation with System Program
 /// - Minimal memory allocations
 /// - No slot processing overhead
-pub fn handler(ctx: Context<SetSandwichValidators>, epoch_arg: u16) -> Result<()> {
+pub fn handler(ctx: Context<SetSandwichValidators>, epoch_arg: u64, slots_per_bit: u8) -> Result<()> {
+    SandwichValidators::validate_slots_per_bit(slots_per_bit)?;
+
     let sandwich_validators_ai = &ctx.accounts.sandwich_validators;
     let multisig_authority = &ctx.accounts.multisig_authority;
     let system_program = &ctx.accounts.system_program;
 
+    // The active authority or a Provisioner signs and pays; PDA seeds always use the namespace key
+    let payer = require_authority_or_role(
+        multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        &[Role::Provisioner],
+        ctx.program_id,
+    )?;
+
+    // Refuse to create new bitmaps while the gatekeeper is frozen
+    let config = &ctx.accounts.gatekeeper_config;
+    config.require_writable()?;
+
     // Ensure PDA hasn't been hijacked
     if sandwich_validators_ai.owner != &system_program::ID {
         return err!(GatekeeperError::InvalidPda);
@@ -30,7 +50,7 @@ pub fn handler(ctx: Context<SetSandwichValidators>, epoch_arg: u16) -> Result<()
     }
 
     // Protect against PDA squatting
-    // If PDA is pre-funded with lamports, reclaim to authority
+    // If PDA is pre-funded with lamports, reclaim to the paying authority
     if sandwich_validators_ai.lamports() > 0 {
         let squatted_lamports = sandwich_validators_ai.lamports();
         
@@ -47,12 +67,12 @@ pub fn handler(ctx: Context<SetSandwichValidators>, epoch_arg: u16) -> Result<()
         invoke_signed(
             &system_instruction::transfer(
                 sandwich_validators_ai.key,
-                multisig_authority.key,
+                payer.key,
                 squatted_lamports,
             ),
             &[
                 sandwich_validators_ai.to_account_info(),
-                multisig_authority.to_account_info(),
+                payer.to_account_info(),
             ],
             &[seeds],
         )?;
@@ -60,12 +80,14 @@ pub fn handler(ctx: Context<SetSandwichValidators>, epoch_arg: u16) -> Result<()
 
     // Create the account with initial size (10KB limit for System Program)
     let rent = Rent::get()?;
-    let account_size = INITIAL_ACCOUNT_SIZE; // Start with 10KB due to Solana limitations
+    // Start with 10KB due to Solana limitations, or less when the whole epoch fits
+    let (_, slots_in_epoch) = config.slots_per_epoch_source.epoch_bounds(epoch_arg)?;
+    let account_size = INITIAL_ACCOUNT_SIZE.min(SandwichValidators::target_account_size(slots_in_epoch, slots_per_bit)?);
     let lamports = rent.minimum_balance(account_size);
 
     // Create account using direct invoke_signed
     let create_account_ix = anchor_lang::solana_program::system_instruction::create_account(
-        multisig_authority.key,
+        payer.key,
         sandwich_validators_ai.key,
         lamports,
         account_size as u64,
@@ -75,7 +97,7 @@ pub fn handler(ctx: Context<SetSandwichValidators>, epoch_arg: u16) -> Result<()
     anchor_lang::solana_program::program::invoke_signed(
         &create_account_ix,
         &[
-            multisig_authority.to_account_info(),
+            payer.to_account_info(),
             sandwich_validators_ai.to_account_info(),
             system_program.to_account_info(),
         ],
@@ -93,25 +115,13 @@ pub fn handler(ctx: Context<SetSandwichValidators>, epoch_arg: u16) -> Result<()
     // Initialize account structure
     let mut data = sandwich_validators_ai.try_borrow_mut_data()?;
 
-    // SandwichValidators structure: discriminator (8) + epoch (2) + bump (1) + padding (5) + bitmap data
-    const HEADER_SIZE: usize = 16; // SandwichValidators::DATA_OFFSET
-
-    // Write discriminator - use Anchor's generated discriminator for SandwichValidators
-    use anchor_lang::Discriminator;
-    data[0..8].copy_from_slice(&SandwichValidators::DISCRIMINATOR);
-
-    // Write epoch
-    data[8..10].copy_from_slice(&epoch_arg.to_le_bytes());
-
-    // Write bump
-    data[10] = ctx.bumps.sandwich_validators;
-
-    // Clear padding bytes
-    data[11..16].fill(0);
+    // Write discriminator and header in the current layout
+    let header = Header::new(epoch_arg, ctx.bumps.sandwich_validators, slots_per_bit);
+    header.write(&mut data);
 
     // Initialize bitmap area to zero (all slots ungated by default)
     // Use fill() which may be vectorized by the compiler
-    data[HEADER_SIZE..].fill(0);
+    data[header.data_offset()..].fill(0);
 
     drop(data);
 
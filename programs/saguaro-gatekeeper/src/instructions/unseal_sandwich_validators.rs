@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::{UnsealSandwichValidators, GatekeeperError, Role, SandwichValidators, SandwichValidatorsUnsealed};
+use crate::authority::require_authority_or_quorum;
+use crate::layout::Header;
+
+/// Handles the `unseal_sandwich_validators` instruction.
+///
+/// Clears the sealed flag and the recorded hash so the bitmap can be written again.
+/// Unsealing an account that is not sealed is a no-op.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, UnsealSandwichValidators<'info>>, epoch_arg: u64) -> Result<()> {
+    // Refuse to unseal bitmaps while the gatekeeper is frozen
+    ctx.accounts.gatekeeper_config.require_writable()?;
+
+    require_authority_or_quorum(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        &[Role::Writer],
+        ctx.accounts.signer_set.as_deref(),
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+
+    let sandwich_validators = &ctx.accounts.sandwich_validators;
+    if sandwich_validators.data_is_empty() || *sandwich_validators.owner != *ctx.program_id {
+        return err!(GatekeeperError::InvalidPda);
+    }
+
+    let mut data = sandwich_validators.try_borrow_mut_data()?;
+    let mut header = Header::read_current(&data)?;
+    if header.epoch != epoch_arg {
+        return err!(GatekeeperError::EpochMismatch);
+    }
+    if !header.is_sealed() {
+        return Ok(());
+    }
+
+    header.flags &= !SandwichValidators::FLAG_SEALED;
+    header.bitmap_hash = [0; 32];
+    header.write(&mut data);
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Unsealed SandwichValidators for epoch {}", epoch_arg);
+
+    emit!(SandwichValidatorsUnsealed {
+        authority: ctx.accounts.multisig_authority.key(),
+        epoch: epoch_arg,
+    });
+
+    Ok(())
+}
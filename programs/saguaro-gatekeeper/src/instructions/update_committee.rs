@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::{UpdateCommittee, Committee, CommitteeParams, CommitteeUpdated};
+
+/// Handler for the `update_committee` instruction.
+///
+/// Replaces the governance key, members and threshold. The governance signature is
+/// enforced by the `has_one` constraint on the account struct.
+pub fn handler(ctx: Context<UpdateCommittee>, params: CommitteeParams) -> Result<()> {
+    Committee::validate_members(&params.members, params.threshold)?;
+
+    let committee_key = ctx.accounts.committee.key();
+    let committee = &mut ctx.accounts.committee;
+    committee.governance = params.governance;
+    committee.set_members(&committee_key, params.members, ctx.program_id);
+    committee.threshold = params.threshold;
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Updated committee {} to {}-of-{} members", committee.key(), committee.threshold, committee.members.len());
+
+    emit!(CommitteeUpdated {
+        committee: committee.key(),
+        governance: committee.governance,
+        members: committee.members.clone(),
+        threshold: committee.threshold,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::{UpdateGatekeeperConfig, GatekeeperConfigParams, GatekeeperConfigUpdated};
+
+/// Handler for the `update_gatekeeper_config` instruction.
+///
+/// Replaces every setting in the config. The admin signature is enforced by the
+/// `has_one` constraint on the account struct.
+pub fn handler(ctx: Context<UpdateGatekeeperConfig>, params: GatekeeperConfigParams) -> Result<()> {
+    let config = &mut ctx.accounts.gatekeeper_config;
+
+    config.admin = params.admin;
+    config.mode = params.mode;
+    config.slots_per_epoch_source = params.slots_per_epoch_source;
+    config.fail_policy = params.fail_policy;
+    config.unsealed_policy = params.unsealed_policy;
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Updated GatekeeperConfig, admin is now {}", config.admin);
+
+    emit!(GatekeeperConfigUpdated {
+        admin: config.admin,
+        mode: config.mode,
+        slots_per_epoch_source: config.slots_per_epoch_source,
+        fail_policy: config.fail_policy,
+        unsealed_policy: config.unsealed_policy,
+        layout_version: config.layout_version,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::{UpdateIntegratorProfile, IntegratorProfile, IntegratorProfileParams, IntegratorProfileUpdated};
+
+/// Handler for the `update_integrator_profile` instruction.
+///
+/// Replaces every setting in the profile. The admin signature is enforced by the
+/// `has_one` constraint on the account struct.
+pub fn handler(ctx: Context<UpdateIntegratorProfile>, params: IntegratorProfileParams) -> Result<()> {
+    IntegratorProfile::validate_params(&params)?;
+
+    let profile = &mut ctx.accounts.integrator_profile;
+    profile.apply(params);
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Updated IntegratorProfile for {}, admin is now {}", profile.integrator, profile.admin);
+
+    emit!(IntegratorProfileUpdated {
+        integrator: profile.integrator,
+        admin: profile.admin,
+        authorities: profile.authorities.clone(),
+        combination: profile.combination,
+        fail_policy: profile.fail_policy,
+        exempt_ranges: profile.exempt_ranges.clone(),
+    });
+
+    Ok(())
+}
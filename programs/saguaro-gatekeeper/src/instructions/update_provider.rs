@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::{UpdateProvider, ProviderMetadata, ProviderUpdated, GatekeeperError};
+
+/// Handler for the `update_provider` instruction.
+///
+/// Replaces the descriptive fields of `authority`'s record. Either the config admin or
+/// the provider itself may sign, so a provider can keep its own metadata current.
+pub fn handler(ctx: Context<UpdateProvider>, authority: Pubkey, metadata: ProviderMetadata) -> Result<()> {
+    metadata.validate()?;
+
+    let signer = ctx.accounts.signer.key();
+    let registry = &mut ctx.accounts.provider_registry;
+    let index = registry.position(&authority)?;
+    if signer != ctx.accounts.gatekeeper_config.admin && signer != authority {
+        return err!(GatekeeperError::InvalidAuthority);
+    }
+    registry.check_name_available(&metadata.name, &authority)?;
+
+    let record = &mut registry.providers[index];
+    record.name = metadata.name;
+    record.metadata_uri = metadata.metadata_uri;
+    record.first_epoch = metadata.first_epoch;
+    record.last_epoch = metadata.last_epoch;
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Updated provider {} ({})", record.authority, record.name);
+
+    emit!(ProviderUpdated {
+        authority: record.authority,
+        name: record.name.clone(),
+        metadata_uri: record.metadata_uri.clone(),
+        first_epoch: record.first_epoch,
+        last_epoch: record.last_epoch,
+        status: record.status,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::{RoleGrant, RoleTable, RoleTableUpdated, UpdateRoleTable};
+use crate::authority::require_active_authority;
+
+/// Handler for the `update_role_table` instruction.
+///
+/// Replaces every grant in the table; keys left out lose their roles immediately.
+pub fn handler(ctx: Context<UpdateRoleTable>, grants: Vec<RoleGrant>) -> Result<()> {
+    require_active_authority(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.program_id,
+    )?;
+
+    RoleTable::validate_grants(&grants)?;
+
+    let role_table = &mut ctx.accounts.role_table;
+    role_table.grants = grants;
+
+    emit!(RoleTableUpdated {
+        namespace: role_table.namespace,
+        grants: role_table.grants.clone(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::{UpdateSignerSet, SignerSet, SignerSetUpdated};
+use crate::authority::require_active_authority;
+
+/// Handler for the `update_signer_set` instruction.
+///
+/// Replaces the members and threshold. Membership changes are governed by the active
+/// authority rather than the set itself, so a compromised quorum cannot entrench itself.
+pub fn handler(ctx: Context<UpdateSignerSet>, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    require_active_authority(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.program_id,
+    )?;
+
+    SignerSet::validate_members(&members, threshold)?;
+
+    let signer_set = &mut ctx.accounts.signer_set;
+    signer_set.members = members;
+    signer_set.threshold = threshold;
+
+    emit!(SignerSetUpdated {
+        namespace: signer_set.namespace,
+        members: signer_set.members.clone(),
+        threshold,
+    });
+
+    Ok(())
+}
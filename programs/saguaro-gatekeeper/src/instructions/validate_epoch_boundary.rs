@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::{ValidateEpochBoundary, GatekeeperError, GatekeeperConfig};
+use crate::layout::{bitmap_pda, check_current_slot, is_epoch_pda, legacy_bitmap_pda, Header};
+
+/// Handles the `validate_epoch_boundary` instruction.
+///
+/// Clients derive the PDA for the epoch they expect, but a transaction landing after the
+/// boundary would otherwise validate against a PDA for the wrong epoch and pass. Here the
+/// caller supplies candidates (normally epochs N and N+1) in `remaining_accounts` and the
+/// one for the epoch the transaction actually lands in is used.
+///
+/// # Behavior
+/// 0. If the GatekeeperConfig override is Paused or BlockAll, returns its outcome immediately
+/// 1. An existing candidate whose header is for the current epoch and whose address is the
+///    namespace's PDA under its stored bump is validated like `validate_v2`
+/// 2. Otherwise, a candidate at the current epoch's (never created) PDA address applies
+///    the fail policy; this is the only path that searches for the bump. As in
+///    `validate_sandwich_validators`, the empty legacy u16-seeded address counts too while
+///    the epoch fits in a u16
+/// 3. If no candidate is the current epoch's PDA, fails with `MissingSandwichValidatorsAccount`
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ValidateEpochBoundary<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let multisig_authority = ctx.accounts.multisig_authority.key();
+    let config: &GatekeeperConfig = &ctx.accounts.gatekeeper_config;
+
+    // Incident override takes precedence over every bitmap
+    if let Some(outcome) = config.override_mode.forced_outcome() {
+        return outcome;
+    }
+
+    for candidate in ctx.remaining_accounts {
+        if candidate.data_is_empty() || candidate.owner != ctx.program_id {
+            continue;
+        }
+        let data = candidate.try_borrow_data()?;
+        // Other account types owned by this program are not candidates
+        let Ok(header) = Header::read(&data) else {
+            continue;
+        };
+        if header.epoch == clock.epoch
+            && is_epoch_pda(&candidate.key(), &multisig_authority, clock.epoch, header.bump, header.uses_legacy_seeds(), ctx.program_id)
+        {
+            return check_current_slot(config, &header, &data, &clock);
+        }
+    }
+
+    // No bitmap exists for the current epoch. If the caller passed its (empty) address
+    // under either seed scheme, the epoch is simply unconfigured
+    let expected_pda = bitmap_pda(&multisig_authority, clock.epoch, ctx.program_id);
+    let legacy_pda = legacy_bitmap_pda(&multisig_authority, clock.epoch, ctx.program_id);
+    if ctx.remaining_accounts.iter().any(|candidate| {
+        candidate.data_is_empty() && (candidate.key() == expected_pda || Some(candidate.key()) == legacy_pda)
+    }) {
+        return config.fail_policy.unconfigured();
+    }
+
+    err!(GatekeeperError::MissingSandwichValidatorsAccount)
+}
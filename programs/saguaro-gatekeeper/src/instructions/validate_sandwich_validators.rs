@@ -1,21 +1,25 @@
 use anchor_lang::prelude::*;
-use crate::{ValidateSandwichValidators, GatekeeperError, SandwichValidators};
-use crate::constants::SLOTS_PER_EPOCH;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions::get_instruction_relative;
+use crate::{ValidateSandwichValidators, Committee, GatekeeperError, GatekeeperConfig, IntegratorProfile, SourceCombination, UnsealedPolicy};
+use crate::quorum::{self, find_source};
+use crate::layout::{bitmap_pda, legacy_bitmap_pda, Header};
 
 /// Handles the `validate_sandwich_validators` instruction with minimal compute usage.
 ///
 /// This function checks if the current slot is gated by reading only the specific bit
-/// from the bitmap with optimized validation relying on reliable Clock sysvar.
+/// from the bitmap with optimized validation relying on the Clock sysvar.
 ///
 /// # CPI Safety Model
 /// This instruction is designed for safe Cross-Program Invocation (CPI) by third-party programs:
-/// - **Fail-Open Design**: If no PDA exists for current epoch, validation passes (allows operation)
+/// - **Fail-Open Design**: If no PDA exists for current epoch, validation passes (allows operation),
+///   unless the GatekeeperConfig `fail_policy` is `Closed`
 /// - **Authority-Independent**: CPI callers only need to provide multisig_authority pubkey (no signer required)
 /// - **Transaction Atomicity**: SlotIsGated error will cause entire transaction to fail, providing sandwich protection
 /// - **No State Changes**: This is a read-only validation function that never modifies blockchain state
 ///
 /// # Security Notes
-/// - Validates PDA address to prevent bypass attacks using current epoch from Clock sysvar
+/// - Validates PDA address to prevent bypass attacks using current epoch from the Clock sysvar
 /// - PDA derivation failure results in fail-open behavior (ungated) 
 /// - Uses safe error handling to prevent panics during CPI calls
 /// - Clock sysvar provides reliable current slot/epoch data
@@ -24,51 +28,95 @@ use crate::constants::SLOTS_PER_EPOCH;
 /// * `ctx` - The context containing `ValidateSandwichValidators` accounts
 ///
 /// # Behavior
-/// 1. Derives expected PDA address using current epoch from Clock sysvar
-/// 2. If PDA doesn't match or doesn't exist, treats slot as ungated (fail-open)
-/// 3. If PDA exists and matches, checks specific bit in bitmap for current slot
-/// 4. Returns SlotIsGated error only if slot is explicitly gated in bitmap
-pub fn handler(ctx: Context<ValidateSandwichValidators>) -> Result<()> {
-    let clock = &ctx.accounts.clock;
-    let current_slot = clock.slot;
-    let current_epoch = clock.epoch as u16;
+/// 0. If the GatekeeperConfig override is Paused or BlockAll, returns its outcome immediately
+/// 1. Derives expected PDA address using current epoch from the Clock sysvar, falling back to the
+///    legacy u16 PDA while the epoch still fits in a u16 (never after it would wrap)
+/// 2. If PDA doesn't match or doesn't exist, applies the configured fail policy (fail-open by default)
+/// 3. If PDA exists and matches, reads the header version to locate the bitmap. An unsealed
+///    bitmap is treated as unconfigured when the config's `unsealed_policy` is `Ignore`
+/// 4. Checks the bit covering the current slot (one slot, or a leader window when the
+///    header's `slots_per_bit` is 4)
+/// 5. Returns SlotIsGated error only if slot is explicitly gated in bitmap
+///
+/// When an `integrator_profile` is supplied, steps 1-5 are replaced by `check_slot_with_profile`.
+/// The profile is only honoured when its integrator is the top-level program (see
+/// `calling_program`), so integrators behind a router cannot use one, and a caller that
+/// omits its profile is decided by `multisig_authority`'s bitmap instead.
+/// Otherwise, when a `committee` is supplied, they are replaced by `check_slot_with_committee`.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ValidateSandwichValidators<'info>>) -> Result<()> {
+    let config = &GatekeeperConfig::effective(ctx.accounts.gatekeeper_config.as_deref());
+    let clock: &Clock = &ctx.accounts.clock;
 
-    let pda_account = &ctx.accounts.sandwich_validators;
-    let multisig_authority = &ctx.accounts.multisig_authority;
+    if let Some(profile) = ctx.accounts.integrator_profile.as_deref() {
+        let instructions_sysvar = ctx.accounts.instructions_sysvar.as_ref()
+            .ok_or(GatekeeperError::IntegratorProfileUnverifiable)?;
+        if calling_program(instructions_sysvar)? != profile.integrator {
+            return err!(GatekeeperError::IntegratorProfileMismatch);
+        }
+        let candidates: Vec<&AccountInfo<'info>> = std::iter::once(&ctx.accounts.sandwich_validators)
+            .chain(ctx.remaining_accounts)
+            .collect();
+        return check_slot_with_profile(profile, &candidates, clock, config, ctx.program_id);
+    }
 
-    // CRITICAL: Validate PDA address to prevent bypass attacks
-    // This implicitly validates the epoch since epoch is part of the PDA seeds
-    let expected_pda = Pubkey::find_program_address(
-        &[
-            SandwichValidators::SEED_PREFIX,
-            multisig_authority.key().as_ref(),
-            &current_epoch.to_le_bytes(),
-        ],
+    if let Some(committee) = ctx.accounts.committee.as_ref() {
+        let candidates: Vec<&AccountInfo<'info>> = std::iter::once(&ctx.accounts.sandwich_validators)
+            .chain(ctx.remaining_accounts)
+            .collect();
+        return check_slot_with_committee(committee, &committee.key(), &candidates, clock, config, ctx.program_id);
+    }
+
+    check_slot(
+        &ctx.accounts.sandwich_validators,
+        &ctx.accounts.multisig_authority.key(),
+        clock,
+        config,
         ctx.program_id,
-    ).0;
-    
-    if pda_account.key() != expected_pda {
-        return Ok(()); // Wrong PDA, treat as ungated (fail-open)
+    )
+}
+
+/// The `validate_sandwich_validators` decision for the current slot, shared with the shadow
+/// variant. Fails with `SlotIsGated` exactly when validation would block.
+pub(crate) fn check_slot(
+    pda_account: &AccountInfo,
+    multisig_authority: &Pubkey,
+    clock: &Clock,
+    config: &GatekeeperConfig,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let current_slot = clock.slot;
+    let current_epoch = clock.epoch;
+
+    // Incident override takes precedence over every bitmap
+    if let Some(outcome) = config.override_mode.forced_outcome() {
+        return outcome;
     }
 
-    // Account exists and PDA is valid, proceed with minimal validation
+    // Missing, foreign or (under `UnsealedPolicy::Ignore`) unsealed bitmaps fall back to the fail policy
+    let header = match locate_bitmap(pda_account, multisig_authority, current_epoch, config, program_id)? {
+        Some(header) => header,
+        None => return config.fail_policy.unconfigured(),
+    };
     let data = pda_account.try_borrow_data()?;
-    
+
     // Skip redundant checks - if PDA derivation succeeded:
     // - Account size is guaranteed by Anchor
     // - Epoch in data must match current_epoch (part of PDA seed)
     // - current_slot is guaranteed to be within current_epoch (reliable Clock)
     
-    // Direct slot offset calculation within epoch
-    let epoch_start = (current_epoch as u64) * SLOTS_PER_EPOCH as u64;
-    let slot_offset = (current_slot - epoch_start) as usize;
-    let byte_index = slot_offset >> 3;  // Bit shift instead of division
-    let bit_index = slot_offset & 7;    // Bit mask instead of modulo
+    // Slot offset within the epoch; a slot outside the epoch's bounds cannot be looked up
+    let slot_offset = match config.slots_per_epoch_source.slot_offset(clock.epoch, current_slot)? {
+        Some(offset) => offset,
+        None => return config.fail_policy.unconfigured(),
+    };
+    // Leader-window accounts cover several slots with each bit
+    let bit = header.bit_index(slot_offset);
+    let byte_index = bit >> 3;  // Bit shift instead of division
+    let bit_index = bit & 7;    // Bit mask instead of modulo
 
     // Direct byte access at calculated position
-    // Layout: discriminator(8) + epoch(2) + bump(1) + padding(5) + bitmap
-    const BITMAP_OFFSET: usize = 16; // SandwichValidators::DATA_OFFSET
-    let target_pos = BITMAP_OFFSET + byte_index;
+    // Layout: discriminator(8) + versioned header + bitmap
+    let target_pos = header.data_offset() + byte_index;
 
     // Only check if we can read the byte (bounds safety)
     if let Some(&byte) = data.get(target_pos) {
@@ -78,4 +126,132 @@ pub fn handler(ctx: Context<ValidateSandwichValidators>) -> Result<()> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// The program that invoked this instruction through CPI.
+///
+/// The instructions sysvar only exposes top-level instructions, so the caller is known only
+/// when it is the top-level program itself. Deeper invocations fail closed with
+/// `IntegratorProfileUnverifiable`; a top-level call reports this program, which matches no
+/// profile. This is why a program reached through a router or aggregator cannot use its
+/// profile: the router, not the integrator, is the top-level program.
+fn calling_program(instructions_sysvar: &AccountInfo) -> Result<Pubkey> {
+    if get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT + 1 {
+        return err!(GatekeeperError::IntegratorProfileUnverifiable);
+    }
+    Ok(get_instruction_relative(0, instructions_sysvar)?.program_id)
+}
+
+/// The decision for the current slot under an integrator's profile.
+///
+/// # Behavior
+/// 0. If the GatekeeperConfig override is Paused or BlockAll, returns its outcome immediately
+/// 1. Slots in the profile's exempt ranges pass
+/// 2. Each profile authority's PDA for the current epoch must be among `candidates`,
+///    otherwise fails with `MissingSandwichValidatorsAccount`
+/// 3. An authority without a usable bitmap counts as gated only under the profile's
+///    closed fail policy; the config's unsealed policy still applies
+/// 4. The authorities' decisions are combined by union (a quorum of one) or intersection
+///    (a quorum of all) through the shared `quorum::evaluate`
+pub(crate) fn check_slot_with_profile(
+    profile: &IntegratorProfile,
+    candidates: &[&AccountInfo],
+    clock: &Clock,
+    config: &GatekeeperConfig,
+    program_id: &Pubkey,
+) -> Result<()> {
+    if let Some(outcome) = config.override_mode.forced_outcome() {
+        return outcome;
+    }
+    if profile.is_exempt(clock.slot) {
+        return Ok(());
+    }
+
+    let sources = profile.authorities.iter()
+        .map(|authority| find_source(authority, candidates, clock.epoch, program_id))
+        .collect::<Result<Vec<_>>>()?;
+    let required = match profile.combination {
+        SourceCombination::Union => 1,
+        SourceCombination::Intersection => sources.len(),
+    };
+    let unconfigured_gated = profile.fail_policy.unconfigured().is_err();
+    if quorum::evaluate(&sources, required, config, clock, unconfigured_gated)?.gated {
+        return err!(GatekeeperError::SlotIsGated);
+    }
+    Ok(())
+}
+
+/// The decision for the current slot under a curator committee: gated once at least
+/// `threshold` members gate it in the bitmaps of their member namespaces. Those are located
+/// and counted as in `check_slot_with_profile`, with the config's fail policy for members
+/// without a bitmap.
+pub(crate) fn check_slot_with_committee(
+    committee: &Committee,
+    committee_key: &Pubkey,
+    candidates: &[&AccountInfo],
+    clock: &Clock,
+    config: &GatekeeperConfig,
+    program_id: &Pubkey,
+) -> Result<()> {
+    if let Some(outcome) = config.override_mode.forced_outcome() {
+        return outcome;
+    }
+
+    let sources = committee.member_namespaces(committee_key, program_id)?.iter()
+        .map(|namespace| find_source(namespace, candidates, clock.epoch, program_id))
+        .collect::<Result<Vec<_>>>()?;
+    let unconfigured_gated = config.fail_policy.unconfigured().is_err();
+    let result = quorum::evaluate(&sources, usize::from(committee.threshold), config, clock, unconfigured_gated)?;
+
+    #[cfg(feature = "debug-logs")]
+    msg!("{} of {} committee members flag slot {}", result.flagged_count, committee.members.len(), clock.slot);
+
+    if result.gated {
+        return err!(GatekeeperError::SlotIsGated);
+    }
+    Ok(())
+}
+
+/// Locates the bitmap that governs `epoch` on the read path shared by validation and queries.
+///
+/// Returns the account's header, which locates the bitmap and gives its granularity, or
+/// `None` when the epoch counts as unconfigured: the account is not the namespace's PDA for `epoch`, was never created, or is
+/// unsealed while the config's `unsealed_policy` is `Ignore`.
+pub(crate) fn locate_bitmap(
+    pda_account: &AccountInfo,
+    multisig_authority: &Pubkey,
+    epoch: u64,
+    config: &GatekeeperConfig,
+    program_id: &Pubkey,
+) -> Result<Option<Header>> {
+    // CRITICAL: Validate PDA address to prevent bypass attacks
+    // This implicitly validates the epoch since epoch is part of the PDA seeds
+    if pda_account.key() != bitmap_pda(multisig_authority, epoch, program_id) {
+        // Transition window: integrators deriving the legacy u16 PDA keep working,
+        // until the epoch no longer fits in a u16
+        if legacy_bitmap_pda(multisig_authority, epoch, program_id) != Some(pda_account.key()) {
+            return Ok(None); // Wrong PDA
+        }
+    }
+
+    bitmap_header(pda_account, config, program_id)
+}
+
+/// Header of an account already known to sit at the expected PDA, or `None` when it was
+/// never created or is unsealed while the config's `unsealed_policy` is `Ignore`.
+fn bitmap_header(pda_account: &AccountInfo, config: &GatekeeperConfig, program_id: &Pubkey) -> Result<Option<Header>> {
+    // Matching PDA that was never created means the epoch is not configured
+    if pda_account.data_is_empty() || pda_account.owner != program_id {
+        return Ok(None);
+    }
+
+    // The bitmap offset depends on the layout version; legacy-address accounts may
+    // still hold the version 0 layout or have been migrated in place
+    let header = Header::read(&pda_account.try_borrow_data()?)?;
+
+    // Bitmaps still being uploaded can be skipped until they are sealed
+    if config.unsealed_policy == UnsealedPolicy::Ignore && !header.is_sealed() {
+        return Ok(None);
+    }
+    Ok(Some(header))
+}
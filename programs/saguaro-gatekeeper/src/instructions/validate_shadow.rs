@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::{ValidateShadow, GatekeeperConfig, GatekeeperError, SlotGatedShadow};
+use super::validate_sandwich_validators::check_slot;
+
+/// Handles the `validate_shadow` instruction.
+///
+/// Makes the same decision as `validate_sandwich_validators` but never blocks on it, so
+/// integrators can measure how often their users would be stopped before enforcing.
+///
+/// # Behavior
+/// 1. Evaluates override mode, fail policy, unsealed policy and the bitmap exactly like
+///    `validate_sandwich_validators`
+/// 2. Where that would fail with `SlotIsGated`, emits `SlotGatedShadow` through a
+///    self-CPI (so it is not lost to log truncation) and returns Ok
+/// 3. Any other error, e.g. a corrupt account header, is returned unchanged
+pub fn handler(ctx: Context<ValidateShadow>) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+    let multisig_authority = ctx.accounts.multisig_authority.key();
+    let config = &GatekeeperConfig::effective(ctx.accounts.gatekeeper_config.as_deref());
+
+    match check_slot(&ctx.accounts.sandwich_validators, &multisig_authority, clock, config, ctx.program_id) {
+        Err(error) if error == GatekeeperError::SlotIsGated.into() => {
+            #[cfg(feature = "debug-logs")]
+            msg!("Shadow mode: slot {} of epoch {} would be gated", clock.slot, clock.epoch);
+
+            emit_cpi!(SlotGatedShadow {
+                authority: multisig_authority,
+                epoch: clock.epoch,
+                slot: clock.slot,
+            });
+            Ok(())
+        }
+        outcome => outcome,
+    }
+}
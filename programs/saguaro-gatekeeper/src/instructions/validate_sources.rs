@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::{ValidateSources, SourceValidationResult, GatekeeperConfig, GatekeeperError, MAX_VALIDATION_SOURCES};
+use crate::layout::is_bitmap_pda;
+use crate::quorum;
+
+/// Handles the `validate_sources` instruction.
+///
+/// Protocols honouring several curators would otherwise CPI once per authority. Here the
+/// caller passes every (authority, PDA) pair in `remaining_accounts` and gets back which
+/// of them flag the current slot, combined by a k-of-n `quorum` (1 = union, n = all).
+///
+/// # Behavior
+/// 0. Each PDA must be its authority's PDA for the current epoch (stored bump for existing
+///    accounts), otherwise fails with `InvalidSandwichValidatorsPDA`
+/// 1. The sources are counted by the shared `quorum::evaluate`. If the GatekeeperConfig
+///    override is Paused or BlockAll, no bitmap is read: Paused flags nothing and BlockAll
+///    flags every source
+/// 2. A source is flagged when its bitmap gates the current slot, or when it has no usable
+///    bitmap and the fail policy is `Closed`
+/// 3. With `enforce`, fails with `SlotIsGated` when at least `quorum` sources are flagged;
+///    otherwise returns the result either way
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ValidateSources<'info>>,
+    quorum: u8,
+    enforce: bool,
+) -> Result<SourceValidationResult> {
+    let sources = ctx.remaining_accounts;
+    let source_count = sources.len() / 2;
+    if sources.is_empty()
+        || sources.len() & 1 != 0
+        || source_count > MAX_VALIDATION_SOURCES
+        || quorum == 0
+        || usize::from(quorum) > source_count
+    {
+        return err!(GatekeeperError::InvalidValidationSources);
+    }
+    // A repeated authority would count twice towards the quorum
+    for i in 0..source_count {
+        if (i + 1..source_count).any(|j| sources[2 * j].key() == sources[2 * i].key()) {
+            return err!(GatekeeperError::InvalidValidationSources);
+        }
+    }
+
+    let clock = Clock::get()?;
+    let config: &GatekeeperConfig = &ctx.accounts.gatekeeper_config;
+
+    let mut accounts = Vec::with_capacity(source_count);
+    for pair in sources.chunks_exact(2) {
+        if !is_bitmap_pda(&pair[0].key(), &pair[1], clock.epoch, ctx.program_id) {
+            return err!(GatekeeperError::InvalidSandwichValidatorsPDA);
+        }
+        accounts.push(&pair[1]);
+    }
+    let unconfigured_gated = config.fail_policy.unconfigured().is_err();
+    let result = quorum::evaluate(&accounts, usize::from(quorum), config, &clock, unconfigured_gated)?;
+    // At most MAX_VALIDATION_SOURCES (8) sources, so the mask and count fit in a u8
+    let flagged_mask = result.flagged_mask as u8;
+    let flagged_count = result.flagged_count as u8;
+    let gated = result.gated;
+
+    #[cfg(feature = "debug-logs")]
+    msg!("{} of {} sources flag slot {} (mask {:#010b})", flagged_count, source_count, clock.slot, flagged_mask);
+
+    if gated && enforce {
+        return err!(GatekeeperError::SlotIsGated);
+    }
+
+    Ok(SourceValidationResult {
+        epoch: clock.epoch,
+        slot: clock.slot,
+        flagged_mask,
+        flagged_count,
+        gated,
+    })
+}
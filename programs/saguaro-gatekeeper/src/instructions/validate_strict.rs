@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::{ValidateStrict, GatekeeperError, SandwichValidators, GatekeeperConfig, UnsealedPolicy};
+use crate::layout::{check_current_slot, is_epoch_pda, Header};
+
+/// Handles the `validate_strict` instruction.
+///
+/// `validate_sandwich_validators` and `validate_v2` fail open on an account they cannot
+/// use, so a wrong authority, epoch encoding or program ID quietly disables gating. This
+/// variant reports those mistakes instead.
+///
+/// # Behavior
+/// 0. If the GatekeeperConfig override is Paused or BlockAll, returns its outcome immediately
+/// 1. An account that was never created must sit at the namespace's PDA for the current
+///    epoch; it then fails with `EpochNotConfigured` if `require_configured` is set and
+///    applies the fail policy otherwise
+/// 2. An existing account must be owned by this program, carry the SandwichValidators
+///    discriminator, hold the current epoch and sit at the PDA for its stored bump;
+///    anything else fails with `InvalidSandwichValidatorsPDA`
+/// 3. With `require_configured`, an unsealed bitmap ignored by `UnsealedPolicy::Ignore`
+///    fails with `EpochNotConfigured`
+/// 4. Returns SlotIsGated only if the current slot is gated in the bitmap
+pub fn handler(ctx: Context<ValidateStrict>, require_configured: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let pda_account = &ctx.accounts.sandwich_validators;
+    let multisig_authority = ctx.accounts.multisig_authority.key();
+    let config: &GatekeeperConfig = &ctx.accounts.gatekeeper_config;
+
+    // Incident override takes precedence over every bitmap
+    if let Some(outcome) = config.override_mode.forced_outcome() {
+        return outcome;
+    }
+
+    let not_configured = || {
+        if require_configured {
+            err!(GatekeeperError::EpochNotConfigured)
+        } else {
+            config.fail_policy.unconfigured()
+        }
+    };
+
+    if pda_account.data_is_empty() {
+        // Without a stored bump the address can only be checked by searching for it
+        let (expected_pda, _) = Pubkey::find_program_address(
+            &[SandwichValidators::SEED_PREFIX, multisig_authority.as_ref(), &clock.epoch.to_le_bytes()],
+            ctx.program_id,
+        );
+        if pda_account.key() != expected_pda {
+            return err!(GatekeeperError::InvalidSandwichValidatorsPDA);
+        }
+        return not_configured();
+    }
+
+    if pda_account.owner != ctx.program_id {
+        return err!(GatekeeperError::InvalidSandwichValidatorsPDA);
+    }
+    let data = pda_account.try_borrow_data()?;
+    let header = Header::read(&data).map_err(|_| GatekeeperError::InvalidSandwichValidatorsPDA)?;
+    if header.epoch != clock.epoch
+        || !is_epoch_pda(&pda_account.key(), &multisig_authority, clock.epoch, header.bump, header.uses_legacy_seeds(), ctx.program_id)
+    {
+        return err!(GatekeeperError::InvalidSandwichValidatorsPDA);
+    }
+
+    if config.unsealed_policy == UnsealedPolicy::Ignore && !header.is_sealed() {
+        return not_configured();
+    }
+
+    check_current_slot(config, &header, &data, &clock)
+}
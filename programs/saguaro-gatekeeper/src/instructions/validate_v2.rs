@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::{ValidateV2, GatekeeperConfig};
+use crate::layout::{check_current_slot, is_epoch_pda, Header};
+
+/// Handles the `validate_v2` instruction.
+///
+/// Same outcomes as `validate_sandwich_validators` at a fraction of the compute cost:
+/// - The PDA is checked with `create_program_address` and the bump stored in the account
+///   header, instead of `find_program_address` searching for the bump on every call
+/// - The header records which seed scheme the address uses, so exactly one address is
+///   derived, and it is checked before the rest of the header is trusted
+/// - The clock comes from `Clock::get`, so callers do not pass the Clock sysvar
+///
+/// # Behavior
+/// 0. If the GatekeeperConfig override is Paused or BlockAll, returns its outcome immediately
+/// 1. A missing or foreign-owned account, an address that is not the namespace's PDA for
+///    the current epoch (u64 seeds, or legacy u16 seeds for legacy and migrated legacy
+///    accounts while the epoch fits), an unreadable header or a header for another epoch
+///    applies the configured fail policy
+/// 2. An unsealed bitmap is treated as unconfigured when the config's `unsealed_policy` is `Ignore`
+/// 3. Returns SlotIsGated only if the current slot is gated in the bitmap
+pub fn handler(ctx: Context<ValidateV2>) -> Result<()> {
+    let clock = Clock::get()?;
+    let pda_account = &ctx.accounts.sandwich_validators;
+    let multisig_authority = ctx.accounts.multisig_authority.key();
+    let config: &GatekeeperConfig = &ctx.accounts.gatekeeper_config;
+
+    // Incident override takes precedence over every bitmap
+    if let Some(outcome) = config.override_mode.forced_outcome() {
+        return outcome;
+    }
+
+    if pda_account.data_is_empty() || pda_account.owner != ctx.program_id {
+        return config.fail_policy.unconfigured();
+    }
+
+    let data = pda_account.try_borrow_data()?;
+
+    // CRITICAL: the stored bump only helps derive the address; the address itself must
+    // still match the namespace and current epoch to prevent bypass with another account
+    let is_current_pda = Header::seeds(&data).is_some_and(|(bump, legacy)| {
+        is_epoch_pda(&pda_account.key(), &multisig_authority, clock.epoch, bump, legacy, ctx.program_id)
+    });
+    if !is_current_pda {
+        return config.fail_policy.unconfigured();
+    }
+
+    let header = match Header::read(&data) {
+        Ok(header) if header.epoch == clock.epoch => header,
+        _ => return config.fail_policy.unconfigured(),
+    };
+
+    check_current_slot(config, &header, &data, &clock)
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::{ValidateWithOverrides, GatekeeperConfig, GatekeeperError, IntegratorOverrides};
+use super::validate_sandwich_validators::check_slot;
+
+/// Handles the `validate_with_overrides` instruction.
+///
+/// Lets an integrator disagree with individual gating decisions, or block extra slots,
+/// without forking the program or changing the authority's data.
+///
+/// # Behavior
+/// 0. If the GatekeeperConfig override is Paused or BlockAll, returns its outcome immediately
+/// 1. `integrator_overrides` must be `override_owner`'s PDA for the current epoch, otherwise
+///    fails with `InvalidPda`; a PDA that was never created holds no overrides
+/// 2. A slot in a deny range fails with `SlotIsGated`; a slot in an allow range passes
+/// 3. Any other slot is validated like `validate_sandwich_validators`
+pub fn handler(ctx: Context<ValidateWithOverrides>) -> Result<()> {
+    let clock = Clock::get()?;
+    let config: &GatekeeperConfig = &ctx.accounts.gatekeeper_config;
+
+    // Incident override takes precedence over the integrator's overrides as well
+    if let Some(outcome) = config.override_mode.forced_outcome() {
+        return outcome;
+    }
+
+    match override_decision(&ctx.accounts.integrator_overrides, &ctx.accounts.override_owner.key(), clock.epoch, clock.slot, ctx.program_id)? {
+        Some(true) => err!(GatekeeperError::SlotIsGated),
+        Some(false) => Ok(()),
+        None => check_slot(
+            &ctx.accounts.sandwich_validators,
+            &ctx.accounts.multisig_authority.key(),
+            &clock,
+            config,
+            ctx.program_id,
+        ),
+    }
+}
+
+/// Reads the owner's overrides for `epoch` and returns their decision for `slot`.
+fn override_decision(account: &AccountInfo, owner: &Pubkey, epoch: u64, slot: u64, program_id: &Pubkey) -> Result<Option<bool>> {
+    let epoch_bytes = epoch.to_le_bytes();
+
+    if account.data_is_empty() {
+        // Without a stored bump the address can only be checked by searching for it
+        let (expected_pda, _) = Pubkey::find_program_address(
+            &[IntegratorOverrides::SEED, owner.as_ref(), &epoch_bytes],
+            program_id,
+        );
+        if account.key() != expected_pda {
+            return err!(GatekeeperError::InvalidPda);
+        }
+        return Ok(None);
+    }
+
+    if account.owner != program_id {
+        return err!(GatekeeperError::InvalidPda);
+    }
+    let overrides = IntegratorOverrides::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    let is_expected_pda = Pubkey::create_program_address(
+        &[IntegratorOverrides::SEED, owner.as_ref(), &epoch_bytes, &[overrides.bump]],
+        program_id,
+    )
+    .is_ok_and(|pda| pda == account.key());
+    if !is_expected_pda {
+        return err!(GatekeeperError::InvalidPda);
+    }
+
+    Ok(overrides.decision(slot))
+}
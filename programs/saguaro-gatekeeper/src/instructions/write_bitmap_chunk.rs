@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use crate::{WriteBitmapChunk, BitmapWriteMode, Role, SandwichValidatorsUpdated, GatekeeperError};
+use crate::authority::require_authority_or_role;
+use crate::bitmap::bits_below_mask;
+use crate::layout::Header;
+
+/// Handler for writing a chunk of bitmap data at an explicit byte offset.
+///
+/// **Utility Operation**: Raw bitmap data writing
+/// The offset-addressed counterpart of `append_data_sandwich_validators_bitmap`. Because
+/// the target position comes from the instruction rather than the upload cursor, writing
+/// the same chunk twice leaves the bitmap unchanged, so uploads can be retried or
+/// replayed out of order.
+///
+/// # Behavior
+/// - `Overwrite` replaces the bytes, `Or` gates the chunk's set bits and `AndNot`
+///   ungates them; the last two leave every other slot untouched
+/// - Fails with `EpochMismatch` if the account's header belongs to another epoch
+/// - Bits past the end of the epoch are never set, whatever the chunk holds, so a padded
+///   tail byte cannot inflate `gated_count`
+/// - `bitmap_len` is raised to the end of the chunk if it lies further, keeping the
+///   append cursor after every written byte
+/// - The header's `gated_count` is adjusted by the bits that actually changed
+pub fn handler(
+    ctx: Context<WriteBitmapChunk>,
+    epoch_arg: u64,
+    offset: u32,
+    data: Vec<u8>,
+    mode: BitmapWriteMode,
+) -> Result<()> {
+    // Refuse to write bitmaps while the gatekeeper is frozen
+    ctx.accounts.gatekeeper_config.require_writable()?;
+
+    // Clearing gates is all an Ungater may do
+    let roles: &[Role] = if mode == BitmapWriteMode::AndNot {
+        &[Role::Writer, Role::Ungater]
+    } else {
+        &[Role::Writer]
+    };
+    require_authority_or_role(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        roles,
+        ctx.program_id,
+    )?;
+
+    if data.is_empty() {
+        return Ok(());
+    }
+
+    let account_info = ctx.accounts.sandwich_validators.to_account_info();
+    let mut account_data = account_info.try_borrow_mut_data()?;
+
+    // Only unsealed accounts on the current layout are writable; older versions must be migrated first
+    let mut header = Header::read_writable(&account_data)?;
+    if header.epoch != epoch_arg {
+        return err!(GatekeeperError::EpochMismatch);
+    }
+    let (_, slots_in_epoch) = ctx.accounts.gatekeeper_config.slots_per_epoch_source.epoch_bounds(epoch_arg)?;
+    let bits_in_epoch = header.bits_for_slots(slots_in_epoch) as usize;
+    let bitmap_data = &mut account_data[header.data_offset()..];
+
+    let start = offset as usize;
+    let end = start
+        .checked_add(data.len())
+        .filter(|&end| end <= bitmap_data.len())
+        .ok_or(GatekeeperError::SlotOutOfRange)?;
+
+    let mut slots_added = 0u32;
+    let mut slots_removed = 0u32;
+    for (index, (byte, &chunk)) in bitmap_data[start..end].iter_mut().zip(&data).enumerate() {
+        let old = *byte;
+        let new = mode.apply(old, chunk) & bits_below_mask(start + index, bits_in_epoch);
+        slots_added += (new & !old).count_ones();
+        slots_removed += (old & !new).count_ones();
+        *byte = new;
+    }
+
+    header.bitmap_len = header.bitmap_len.max(end as u32);
+    header.gated_count = header.gated_count
+        .checked_sub(slots_removed)
+        .and_then(|count| count.checked_add(slots_added))
+        .ok_or(GatekeeperError::SlotOutOfRange)?;
+    header.write(&mut account_data);
+    drop(account_data);
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Wrote {} bytes at offset {} ({:?}): {} slots gated, {} ungated", data.len(), offset, mode, slots_added, slots_removed);
+
+    if slots_added > 0 || slots_removed > 0 {
+        emit!(SandwichValidatorsUpdated {
+            authority: ctx.accounts.multisig_authority.key(),
+            epoch: header.epoch,
+            slots_added,
+            slots_removed,
+            total_slots: header.gated_count,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use crate::{WriteBitmapRuns, Role, SandwichValidatorsUpdated, GatekeeperError};
+use crate::authority::require_authority_or_role;
+use crate::bitmap::fill_range;
+use crate::encoding::BitmapRuns;
+use crate::layout::Header;
+
+/// Handler for overwriting a stretch of the bitmap from run-length encoded data.
+///
+/// **Utility Operation**: Bulk bitmap upload
+/// Runs (see [`BitmapRuns`]) are expanded in place starting at bit `offset` (the slot
+/// offset, or the leader-window index on accounts with `slots_per_bit` = 4),
+/// replacing every slot they cover. Long runs fill whole bytes, so the cost follows the
+/// number of runs rather than the number of slots, and a sparse epoch fits in a few
+/// transactions. Like `write_bitmap_chunk`, writing the same runs twice is harmless.
+///
+/// # Behavior
+/// - Fails with `EpochMismatch` if the account's header belongs to another epoch
+/// - Fails with `SlotOutOfRange` if `offset` lies at or past the epoch's last bit
+/// - Runs are clamped to the end of the epoch: the part of a run past the epoch's last
+///   bit is skipped, so an encoded tail byte cannot gate slots that do not exist
+/// - `bitmap_len` is raised to the byte holding the last bit written if it lies further
+/// - The header's `gated_count` is adjusted by the slots whose state changed
+pub fn handler(ctx: Context<WriteBitmapRuns>, epoch_arg: u64, offset: u32, runs: Vec<u8>) -> Result<()> {
+    // Refuse to write bitmaps while the gatekeeper is frozen
+    ctx.accounts.gatekeeper_config.require_writable()?;
+
+    require_authority_or_role(
+        &ctx.accounts.multisig_authority,
+        &ctx.accounts.authority_record,
+        ctx.accounts.operator.as_ref(),
+        ctx.accounts.role_table.as_deref(),
+        &[Role::Writer],
+        ctx.program_id,
+    )?;
+
+    if runs.is_empty() {
+        return Ok(());
+    }
+
+    let account_info = ctx.accounts.sandwich_validators.to_account_info();
+    let mut account_data = account_info.try_borrow_mut_data()?;
+
+    // Only unsealed accounts on the current layout are writable; older versions must be migrated first
+    let mut header = Header::read_writable(&account_data)?;
+    if header.epoch != epoch_arg {
+        return err!(GatekeeperError::EpochMismatch);
+    }
+    let (_, slots_in_epoch) = ctx.accounts.gatekeeper_config.slots_per_epoch_source.epoch_bounds(epoch_arg)?;
+    let bits_in_epoch = header.bits_for_slots(slots_in_epoch) as usize;
+    if offset as usize >= bits_in_epoch {
+        return err!(GatekeeperError::SlotOutOfRange);
+    }
+    let bitmap_data = &mut account_data[header.data_offset()..];
+    let capacity = bitmap_data.len() * 8;
+
+    let mut position = offset as usize;
+    let mut written_end = 0usize;
+    let mut slots_added = 0u32;
+    let mut slots_removed = 0u32;
+    for run in BitmapRuns::new(&runs) {
+        let (len, gated) = run?;
+        if len == 0 {
+            continue;
+        }
+        let end = position
+            .checked_add(len as usize)
+            .ok_or(GatekeeperError::SlotOutOfRange)?;
+        // Only the part of the run inside the epoch is written, and it must fit the bitmap
+        let write_end = end.min(bits_in_epoch);
+        if write_end > capacity {
+            return err!(GatekeeperError::SlotOutOfRange);
+        }
+        if write_end > position {
+            let changed = fill_range(bitmap_data, position, write_end - position, gated);
+            written_end = write_end;
+            if gated {
+                slots_added += changed;
+            } else {
+                slots_removed += changed;
+            }
+        }
+        position = end;
+    }
+
+    header.bitmap_len = header.bitmap_len.max(written_end.div_ceil(8) as u32);
+    header.gated_count = header.gated_count
+        .checked_sub(slots_removed)
+        .and_then(|count| count.checked_add(slots_added))
+        .ok_or(GatekeeperError::SlotOutOfRange)?;
+    header.write(&mut account_data);
+    drop(account_data);
+
+    #[cfg(feature = "debug-logs")]
+    msg!("Expanded runs over slots {}..{}: {} slots gated, {} ungated", offset, position, slots_added, slots_removed);
+
+    if slots_added > 0 || slots_removed > 0 {
+        emit!(SandwichValidatorsUpdated {
+            authority: ctx.accounts.multisig_authority.key(),
+            epoch: header.epoch,
+            slots_added,
+            slots_removed,
+            total_slots: header.gated_count,
+        });
+    }
+
+    Ok(())
+}
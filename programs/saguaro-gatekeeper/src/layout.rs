@@ -0,0 +1,255 @@
+//! Byte layout of `SandwichValidators` accounts.
+//!
+//! Bitmap accounts are too large to deserialize, so handlers read and write the
+//! header directly. All offsets live here; handlers go through [`Header`] and
+//! dispatch on its `version` instead of hard-coding positions.
+//!
+//! | Version | Header bytes after the discriminator                                   | Bitmap offset |
+//! |---------|------------------------------------------------------------------------|---------------|
+//! | 0       | epoch u16 (2) + bump (1) + padding (1) + bitmap_len (4)                | 16            |
+//! | 2       | legacy epoch (2, zero) + bump (1) + version (1) + bitmap_len (4) + epoch u64 (8) | 24    |
+//! | 3       | version 2 header + flags (1) + reserved (7) + bitmap_hash (32)         | 64            |
+//! | 4       | version 3 header with slots_per_bit (1) and gated_count (4) in reserved bytes | 64     |
+//!
+//! Version 4 accounts written before `slots_per_bit` existed hold 0 there, which reads as
+//! one slot per bit. Version 1 was never written. Older versions are upgraded in place by
+//! `migrate_sandwich_validators`.
+//!
+//! The address check against the stored bump and seed scheme, and the final bit lookup
+//! for the current slot, are shared by every validation path and live here as well.
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use core::ops::Range;
+use crate::{GatekeeperConfig, GatekeeperError, SandwichValidators, UnsealedPolicy};
+use crate::bitmap::is_gated;
+
+/// Legacy u16 layout, written before the header carried a version.
+pub const VERSION_LEGACY: u8 = 0;
+/// u64 epoch layout without lifecycle flags.
+pub const VERSION_V2: u8 = 2;
+/// Sealable layout without a gated-slot counter.
+pub const VERSION_V3: u8 = 3;
+/// Layout written by `set_sandwich_validators` and produced by migration.
+pub const VERSION_CURRENT: u8 = SandwichValidators::LAYOUT_VERSION;
+
+/// Bitmap offset of version 2 accounts.
+pub const V2_DATA_OFFSET: usize = 24;
+
+const DISCRIMINATOR: Range<usize> = 0..8;
+const LEGACY_EPOCH: Range<usize> = 8..10;
+const BUMP: usize = 10;
+const VERSION: usize = 11;
+const BITMAP_LEN: Range<usize> = 12..16;
+const EPOCH: Range<usize> = 16..24;
+const FLAGS: usize = 24;
+const SLOTS_PER_BIT: usize = 25;
+const RESERVED: Range<usize> = 26..28;
+const GATED_COUNT: Range<usize> = 28..32;
+const BITMAP_HASH: Range<usize> = 32..64;
+
+/// Decoded `SandwichValidators` header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub version: u8,
+    pub bump: u8,
+    pub bitmap_len: u32,
+    pub epoch: u64,
+    pub flags: u8,
+    /// Consecutive slots covered by each bit; always at least 1.
+    pub slots_per_bit: u8,
+    pub gated_count: u32,
+    pub bitmap_hash: [u8; 32],
+}
+
+impl Header {
+    /// Header of a freshly created account in the current layout.
+    pub fn new(epoch: u64, bump: u8, slots_per_bit: u8) -> Self {
+        Self { version: VERSION_CURRENT, bump, bitmap_len: 0, epoch, flags: 0, slots_per_bit, gated_count: 0, bitmap_hash: [0; 32] }
+    }
+
+    /// Reads the header of any supported version after checking the discriminator.
+    /// Versions without lifecycle fields read as unsealed, versions without a counter
+    /// read a `gated_count` of zero, and all but the current one use one slot per bit.
+    pub fn read(data: &[u8]) -> Result<Self> {
+        if data.len() < SandwichValidators::LEGACY_DATA_OFFSET
+            || data[DISCRIMINATOR] != *SandwichValidators::DISCRIMINATOR
+        {
+            return err!(GatekeeperError::InvalidPda);
+        }
+        let mut header = Self {
+            version: data[VERSION],
+            bump: data[BUMP],
+            bitmap_len: u32::from_le_bytes(data[BITMAP_LEN].try_into().unwrap()),
+            epoch: 0,
+            flags: 0,
+            slots_per_bit: 1,
+            gated_count: 0,
+            bitmap_hash: [0; 32],
+        };
+        match header.version {
+            VERSION_LEGACY => {
+                header.epoch = u16::from_le_bytes(data[LEGACY_EPOCH].try_into().unwrap()) as u64;
+            }
+            VERSION_V2 | VERSION_V3 | VERSION_CURRENT if data.len() >= header.data_offset() => {
+                header.epoch = u64::from_le_bytes(data[EPOCH].try_into().unwrap());
+                if header.version >= VERSION_V3 {
+                    header.flags = data[FLAGS];
+                    header.bitmap_hash.copy_from_slice(&data[BITMAP_HASH]);
+                }
+                if header.version == VERSION_CURRENT {
+                    header.slots_per_bit = data[SLOTS_PER_BIT].max(1);
+                    header.gated_count = u32::from_le_bytes(data[GATED_COUNT].try_into().unwrap());
+                }
+            }
+            _ => return err!(GatekeeperError::UnsupportedLayoutVersion),
+        }
+        Ok(header)
+    }
+
+    /// Bump and seed scheme of the account's address, read straight from the bytes that
+    /// carry them so the address can be verified before the rest of the header is
+    /// trusted. `None` if the data is too short to hold them.
+    pub fn seeds(data: &[u8]) -> Option<(u8, bool)> {
+        let version = *data.get(VERSION)?;
+        let flags = if version >= VERSION_V3 { *data.get(FLAGS)? } else { 0 };
+        let legacy = version == VERSION_LEGACY || flags & SandwichValidators::FLAG_LEGACY_SEEDS != 0;
+        Some((data[BUMP], legacy))
+    }
+
+    /// Reads the header and requires the current layout.
+    pub fn read_current(data: &[u8]) -> Result<Self> {
+        let header = Self::read(data)?;
+        if header.version != VERSION_CURRENT {
+            return err!(GatekeeperError::UnsupportedLayoutVersion);
+        }
+        Ok(header)
+    }
+
+    /// Reads the header of an account a write handler may change: current layout, unsealed.
+    pub fn read_writable(data: &[u8]) -> Result<Self> {
+        let header = Self::read_current(data)?;
+        if header.is_sealed() {
+            return err!(GatekeeperError::AccountSealed);
+        }
+        Ok(header)
+    }
+
+    /// Offset at which the bitmap begins for this header's version.
+    pub fn data_offset(&self) -> usize {
+        match self.version {
+            VERSION_LEGACY => SandwichValidators::LEGACY_DATA_OFFSET,
+            VERSION_V2 => V2_DATA_OFFSET,
+            _ => SandwichValidators::DATA_OFFSET,
+        }
+    }
+
+    /// Bit covering the slot `slot_offset` slots after the epoch start.
+    pub fn bit_index(&self, slot_offset: u64) -> usize {
+        (slot_offset / u64::from(self.slots_per_bit)) as usize
+    }
+
+    /// Number of bits covering the first `slots` slots of the epoch.
+    pub fn bits_for_slots(&self, slots: u64) -> u64 {
+        slots.div_ceil(u64::from(self.slots_per_bit))
+    }
+
+    /// Whether the account lives at the legacy u16-seeded address: layout version 0, or
+    /// migrated from it.
+    pub fn uses_legacy_seeds(&self) -> bool {
+        self.version == VERSION_LEGACY || self.flags & SandwichValidators::FLAG_LEGACY_SEEDS != 0
+    }
+
+    /// Whether `seal_sandwich_validators` has frozen the bitmap.
+    pub fn is_sealed(&self) -> bool {
+        self.flags & SandwichValidators::FLAG_SEALED != 0
+    }
+
+    /// Writes the discriminator and this header in the current layout.
+    pub fn write(&self, data: &mut [u8]) {
+        data[DISCRIMINATOR].copy_from_slice(SandwichValidators::DISCRIMINATOR);
+        data[LEGACY_EPOCH].fill(0);
+        data[BUMP] = self.bump;
+        data[VERSION] = VERSION_CURRENT;
+        data[BITMAP_LEN].copy_from_slice(&self.bitmap_len.to_le_bytes());
+        data[EPOCH].copy_from_slice(&self.epoch.to_le_bytes());
+        data[FLAGS] = self.flags;
+        data[SLOTS_PER_BIT] = self.slots_per_bit;
+        data[RESERVED].fill(0);
+        data[GATED_COUNT].copy_from_slice(&self.gated_count.to_le_bytes());
+        data[BITMAP_HASH].copy_from_slice(&self.bitmap_hash);
+    }
+}
+
+/// Whether `address` is the namespace's SandwichValidators PDA for `epoch` with `bump`,
+/// under the u64 seeds or, for `legacy` accounts while the epoch fits, the legacy u16
+/// seeds. Only the scheme the account records is derived.
+pub fn is_epoch_pda(address: &Pubkey, multisig_authority: &Pubkey, epoch: u64, bump: u8, legacy: bool, program_id: &Pubkey) -> bool {
+    let bump = [bump];
+    let is_pda = |seeds: &[&[u8]]| {
+        Pubkey::create_program_address(seeds, program_id).is_ok_and(|pda| pda == *address)
+    };
+    if legacy {
+        u16::try_from(epoch).is_ok_and(|legacy_epoch| {
+            is_pda(&[SandwichValidators::LEGACY_SEED_PREFIX, multisig_authority.as_ref(), &legacy_epoch.to_le_bytes(), &bump])
+        })
+    } else {
+        is_pda(&[SandwichValidators::SEED_PREFIX, multisig_authority.as_ref(), &epoch.to_le_bytes(), &bump])
+    }
+}
+
+/// `authority`'s u64-seeded SandwichValidators PDA for `epoch`, found by bump search.
+pub fn bitmap_pda(authority: &Pubkey, epoch: u64, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[SandwichValidators::SEED_PREFIX, authority.as_ref(), &epoch.to_le_bytes()],
+        program_id,
+    ).0
+}
+
+/// `authority`'s legacy u16-seeded SandwichValidators PDA for `epoch`, or `None` once the
+/// epoch no longer fits in a u16: the legacy address would then refer to a wrapped (stale)
+/// epoch, so it is never accepted.
+pub fn legacy_bitmap_pda(authority: &Pubkey, epoch: u64, program_id: &Pubkey) -> Option<Pubkey> {
+    let legacy_epoch = u16::try_from(epoch).ok()?;
+    Some(Pubkey::find_program_address(
+        &[SandwichValidators::LEGACY_SEED_PREFIX, authority.as_ref(), &legacy_epoch.to_le_bytes()],
+        program_id,
+    ).0)
+}
+
+/// Whether `account` is `authority`'s SandwichValidators PDA for `epoch`. An existing
+/// account is checked with its stored bump and seed scheme, so a migrated account at the
+/// legacy u16-seeded address is accepted; only an account that was never created needs a
+/// bump search, under the u64 seeds.
+pub fn is_bitmap_pda(authority: &Pubkey, account: &AccountInfo, epoch: u64, program_id: &Pubkey) -> bool {
+    if account.data_is_empty() {
+        return account.key() == bitmap_pda(authority, epoch, program_id);
+    }
+    account.owner == program_id
+        && account.try_borrow_data().is_ok_and(|data| {
+            Header::seeds(&data).is_some_and(|(bump, legacy)| {
+                is_epoch_pda(&account.key(), authority, epoch, bump, legacy, program_id)
+            })
+        })
+}
+
+/// Final validation step once the current epoch's account is known: applies the unsealed
+/// policy and fails with `SlotIsGated` when the current slot is gated in the bitmap.
+pub fn check_current_slot(config: &GatekeeperConfig, header: &Header, data: &[u8], clock: &Clock) -> Result<()> {
+    // Bitmaps still being uploaded can be skipped until they are sealed
+    if config.unsealed_policy == UnsealedPolicy::Ignore && !header.is_sealed() {
+        return config.fail_policy.unconfigured();
+    }
+
+    // Slot offset within the epoch; a slot outside the epoch's bounds cannot be looked up
+    let slot_offset = match config.slots_per_epoch_source.slot_offset(clock.epoch, clock.slot)? {
+        Some(offset) => offset,
+        None => return config.fail_policy.unconfigured(),
+    };
+
+    if is_gated(&data[header.data_offset()..], header.bit_index(slot_offset)) {
+        return err!(GatekeeperError::SlotIsGated);
+    }
+
+    Ok(())
+}
@@ -2,9 +2,16 @@
 #![allow(deprecated)]
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{bpf_loader_upgradeable, sysvar::instructions as sysvar_instructions};
+use layout::is_bitmap_pda;
 
+pub mod authority;
+pub mod bitmap;
 pub mod constants;
+pub mod encoding;
 pub mod instructions;
+pub mod layout;
+pub mod quorum;
 
 // Re-export all constants for backward compatibility
 pub use constants::*;
@@ -24,11 +31,14 @@ pub mod saguaro_gatekeeper {
     /// - Requires multisig authority as signer
     /// - Manages rent-exemption through proper lamport transfers
     /// - Account starts with all slots ungated (bitmap zeroed)
+    /// - `slots_per_bit` is fixed for the account's lifetime: 1 tracks single slots, 4 tracks
+    ///   leader windows in a quarter of the space
     pub fn set_sandwich_validators(
         ctx: Context<SetSandwichValidators>,
-        epoch_arg: u16,
+        epoch_arg: u64,
+        slots_per_bit: u8,
     ) -> Result<()> {
-        instructions::set_sandwich_validators_handler(ctx, epoch_arg)
+        instructions::set_sandwich_validators_handler(ctx, epoch_arg, slots_per_bit)
     }
 
     /// Modify slots in an existing SandwichValidators PDA.
@@ -40,230 +50,2442 @@ pub mod saguaro_gatekeeper {
     /// - Requires multisig authority as signer
     /// - Validates PDA existence and authority match
     /// - Validates slot limits and prevents duplicates
-    pub fn modify_sandwich_validators(
-        ctx: Context<ModifySandwichValidators>,
-        epoch_arg: u16,
+    pub fn modify_sandwich_validators<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifySandwichValidators<'info>>,
+        epoch_arg: u64,
         slots_to_gate: Vec<u64>,
         slots_to_ungate: Vec<u64>,
     ) -> Result<()> {
         instructions::modify_sandwich_validators_handler(ctx, epoch_arg, slots_to_gate, slots_to_ungate)
     }
 
+    /// Gate or ungate contiguous `(start, len)` slot ranges in an existing SandwichValidators PDA.
+    ///
+    /// # Security Notes:
+    /// - Requires multisig authority as signer (or a Writer; an Ungater when only ungating)
+    /// - Ranges must lie within the epoch and the current bitmap capacity
+    /// - Rejects overlapping ranges, within a list or between gate and ungate
+    pub fn modify_sandwich_validators_ranges<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifySandwichValidatorsRanges<'info>>,
+        epoch_arg: u64,
+        ranges_to_gate: Vec<SlotRange>,
+        ranges_to_ungate: Vec<SlotRange>,
+    ) -> Result<()> {
+        instructions::modify_sandwich_validators_ranges_handler(ctx, epoch_arg, ranges_to_gate, ranges_to_ungate)
+    }
+
+    /// Gate or ungate slots sent as LEB128 gaps relative to the epoch start, which fits
+    /// several times more slots in a transaction than `modify_sandwich_validators`.
+    ///
+    /// # Security Notes:
+    /// - Requires multisig authority as signer (or a Writer; an Ungater when only ungating)
+    /// - Same duplicate, overlap and bounds rules as `modify_sandwich_validators`
+    /// - Decodes the offsets in place without building a slot list
+    pub fn modify_sandwich_validators_compact<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifySandwichValidatorsCompact<'info>>,
+        epoch_arg: u64,
+        gate_offsets: Vec<u8>,
+        ungate_offsets: Vec<u8>,
+    ) -> Result<()> {
+        instructions::modify_sandwich_validators_compact_handler(ctx, epoch_arg, gate_offsets, ungate_offsets)
+    }
+
 
     /// Validate whether the current slot is gated for sandwich facilitating validators.
     /// This is a public instruction safe for Cross-Program Invocation (CPI).
+    /// Accepts the u64-seeded PDA, or the legacy u16-seeded PDA while the epoch fits in a u16.
     /// 
     /// # CPI Safety:
     /// - Returns Ok if PDA doesn't exist (allows normal operation)
     /// - Returns SlotIsGated error only if slot is explicitly gated
     /// - Always performs validation for CPI compatibility
-    /// - Derives current epoch and slot from Clock sysvar internally
-    pub fn validate_sandwich_validators(
-        ctx: Context<ValidateSandwichValidators>,
+    /// - Derives current epoch and slot from the Clock sysvar internally
+    /// - Keeps the baseline `[sandwich_validators, multisig_authority, clock]` layout; the
+    ///   GatekeeperConfig is an optional fourth account. When supplied, its override mode is
+    ///   applied before the bitmap; when omitted, the defaults (`Normal`, fail-open) apply
+    /// - Profiles are opt-in: only when the caller passes its IntegratorProfile and the
+    ///   instructions sysvar do the profile's authorities, combination, fail policy and
+    ///   exempt ranges apply instead of `multisig_authority`'s bitmap. The profile must belong
+    ///   to the program that issued the CPI, and a caller that cannot be identified (no
+    ///   sysvar, or a CPI nested below another program) fails with
+    ///   `IntegratorProfileUnverifiable` rather than falling back to the default decision
+    /// - Profile limits: (1) only a program invoked directly by the transaction can be
+    ///   identified, so an integrator reached through a router or aggregator cannot use its
+    ///   profile at all; (2) nothing forces a caller to pass its profile, so a call that omits
+    ///   it is decided by `multisig_authority`'s bitmap and escapes the profile's policy.
+    ///   A profile is a policy an integrator opts into for its own calls, not a constraint
+    ///   the gatekeeper can enforce on it
+    /// - With a `committee` account, the slot is gated once `threshold` members gate it in the
+    ///   bitmaps of their member namespaces (passed in `remaining_accounts`). A profile takes
+    ///   precedence over a committee, and either replaces `multisig_authority`'s bitmap
+    pub fn validate_sandwich_validators<'info>(
+        ctx: Context<'_, '_, '_, 'info, ValidateSandwichValidators<'info>>,
     ) -> Result<()> {
         instructions::validate_sandwich_validators_handler(ctx)
     }
 
+    /// Lower-cost variant of `validate_sandwich_validators` with the same outcomes.
+    /// Verifies the PDA with the bump stored in the account instead of searching for it,
+    /// and reads the clock through `Clock::get`, so only the PDA, authority and config are needed.
+    ///
+    /// # CPI Safety:
+    /// - Same fail-open behaviour, override mode and fail policy as `validate_sandwich_validators`
+    /// - An account that is not the namespace's PDA for the current epoch is treated as unconfigured
+    /// - Accepts both the u64-seeded and the legacy u16-seeded PDA
+    pub fn validate_v2(ctx: Context<ValidateV2>) -> Result<()> {
+        instructions::validate_v2_handler(ctx)
+    }
+
+    /// Dry-run variant of `validate_sandwich_validators` for measuring impact before
+    /// enforcing. Takes the same accounts plus the event CPI accounts.
+    ///
+    /// # CPI Safety:
+    /// - Never fails with `SlotIsGated`; where validation would, emits `SlotGatedShadow`
+    ///   through `emit_cpi!` and returns Ok
+    /// - Override mode, fail policy and unsealed policy are evaluated as in
+    ///   `validate_sandwich_validators`, so a shadow block means a real one
+    /// - The self-CPI adds one level of invocation depth to the caller's transaction
+    pub fn validate_shadow(ctx: Context<ValidateShadow>) -> Result<()> {
+        instructions::validate_shadow_handler(ctx)
+    }
+
+    /// Validation with an integrator's own allow and deny ranges layered on top of the
+    /// authority's bitmap. Reads the clock through `Clock::get`, as `validate_v2` does.
+    ///
+    /// # CPI Safety:
+    /// - The override mode applies first; then a denied slot fails with `SlotIsGated` and an
+    ///   allowed slot passes, whatever the bitmap says
+    /// - Other slots are validated exactly like `validate_sandwich_validators`
+    /// - Fails with `InvalidPda` if `integrator_overrides` is not `override_owner`'s PDA for the
+    ///   current epoch; a PDA that was never created means no overrides
+    pub fn validate_with_overrides(ctx: Context<ValidateWithOverrides>) -> Result<()> {
+        instructions::validate_with_overrides_handler(ctx)
+    }
+
+    /// Fail-closed variant of `validate_v2` for integrators who want client-side mistakes
+    /// to surface instead of silently disabling gating.
+    ///
+    /// # CPI Safety:
+    /// - Fails with `InvalidSandwichValidatorsPDA` if the account is not the namespace's PDA
+    ///   for the current epoch, is owned by another program or is not a SandwichValidators account
+    /// - With `require_configured`, fails with `EpochNotConfigured` when the PDA was never
+    ///   created (or is unsealed under `UnsealedPolicy::Ignore`) instead of applying the fail policy
+    /// - Otherwise the same override mode and gating outcome as `validate_v2`
+    pub fn validate_strict(ctx: Context<ValidateStrict>, require_configured: bool) -> Result<()> {
+        instructions::validate_strict_handler(ctx, require_configured)
+    }
+
+    /// Epoch-boundary-safe validation. The caller passes the namespace's PDAs for the epoch
+    /// it expects and the next one in `remaining_accounts`, and the account matching the
+    /// epoch the transaction lands in is used, so protection holds across epoch turnover.
+    ///
+    /// # CPI Safety:
+    /// - Fails with `MissingSandwichValidatorsAccount` when no supplied account is the
+    ///   current epoch's PDA, instead of passing silently
+    /// - A current-epoch PDA that was never created applies the fail policy, as in
+    ///   `validate_sandwich_validators`
+    /// - Same override mode, unsealed policy and gating outcome as `validate_v2`
+    pub fn validate_epoch_boundary<'info>(
+        ctx: Context<'_, '_, '_, 'info, ValidateEpochBoundary<'info>>,
+    ) -> Result<()> {
+        instructions::validate_epoch_boundary_handler(ctx)
+    }
+
+    /// Validate the current slot against several authorities' gate lists at once. The
+    /// caller passes up to `MAX_VALIDATION_SOURCES` (authority, PDA) pairs in
+    /// `remaining_accounts`; the slot is gated when at least `quorum` of them gate it.
+    ///
+    /// # CPI Safety:
+    /// - Returns which sources flagged the slot through return data
+    /// - With `enforce`, fails with `SlotIsGated` once the quorum is reached; without it,
+    ///   never fails because of gating, so the caller can aggregate the sources itself
+    /// - A PDA that is not its authority's PDA for the current epoch fails with
+    ///   `InvalidSandwichValidatorsPDA`; one that was never created follows the fail policy
+    /// - Same override mode and unsealed policy as `validate_v2`
+    pub fn validate_sources<'info>(
+        ctx: Context<'_, '_, '_, 'info, ValidateSources<'info>>,
+        quorum: u8,
+        enforce: bool,
+    ) -> Result<SourceValidationResult> {
+        instructions::validate_sources_handler(ctx, quorum, enforce)
+    }
+
+    /// Report whether slots of the current epoch are gated, through return data.
+    /// The non-failing counterpart of `validate_sandwich_validators` for callers that
+    /// want to branch rather than abort.
+    ///
+    /// # CPI Safety:
+    /// - Never fails because a slot is gated; the result is returned via `set_return_data`
+    /// - Queries the current slot when `slots` is empty, and at most `MAX_QUERY_SLOTS` slots
+    /// - Applies the same PDA checks, override mode and fail policy as validation
+    pub fn query_slots(
+        ctx: Context<QuerySlots>,
+        slots: Vec<u64>,
+    ) -> Result<SlotQueryResult> {
+        instructions::query_slots_handler(ctx, slots)
+    }
+
     /// Close a SandwichValidators PDA for a past epoch and refund rent.
     /// 
     /// # Security Notes:
     /// - Only allows closing PDAs for past epochs
     /// - Requires multisig authority as signer
-    pub fn close_sandwich_validator(
-        ctx: Context<CloseSandwichValidator>,
-        epoch_to_close: u16,
+    /// - Rent goes to the active authority, never to a Closer or signer set member
+    pub fn close_sandwich_validator<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseSandwichValidator<'info>>,
+        epoch_to_close: u64,
     ) -> Result<()> {
         instructions::close_sandwich_validator_handler(ctx, epoch_to_close)
     }
 
+    /// Close a SandwichValidators PDA created under the legacy u16 seed scheme and refund rent.
+    ///
+    /// # Security Notes:
+    /// - Only allows closing PDAs for past epochs
+    /// - Requires multisig authority as signer (or a Closer / signer set quorum)
+    /// - Rent goes to the active authority, never to a Closer or signer set member
+    pub fn close_legacy_sandwich_validator<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseLegacySandwichValidator<'info>>,
+        epoch_to_close: u16,
+    ) -> Result<()> {
+        instructions::close_legacy_sandwich_validator_handler(ctx, epoch_to_close)
+    }
+
+    /// Upgrade a SandwichValidators account to the current layout version in place.
+    ///
+    /// # Security Notes:
+    /// - Accepts only the namespace's u64-seeded or legacy u16-seeded PDA for `epoch_arg`
+    /// - Requires multisig authority as signer (or a Provisioner, who funds any extra rent)
+    /// - Preserves every gated slot; accounts already on the current layout are unchanged
+    /// - Flags legacy-address accounts so the write, seal and unseal instructions accept
+    ///   them afterwards
+    pub fn migrate_sandwich_validators(
+        ctx: Context<MigrateSandwichValidators>,
+        epoch_arg: u64,
+    ) -> Result<()> {
+        instructions::migrate_sandwich_validators_handler(ctx, epoch_arg)
+    }
+
+    /// Seal a fully uploaded bitmap, recording its SHA-256 hash and freezing the account.
+    ///
+    /// # Security Notes:
+    /// - Requires multisig authority as signer (or a Writer / signer set quorum)
+    /// - Fails unless the on-chain bitmap hashes to `expected_hash`
+    /// - Modify, append, clear and expand are rejected while sealed; close is still allowed
+    pub fn seal_sandwich_validators<'info>(
+        ctx: Context<'_, '_, '_, 'info, SealSandwichValidators<'info>>,
+        epoch_arg: u64,
+        expected_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::seal_sandwich_validators_handler(ctx, epoch_arg, expected_hash)
+    }
+
+    /// Unseal a bitmap so it can be written again, clearing the recorded hash.
+    ///
+    /// # Security Notes:
+    /// - Requires multisig authority as signer (or a Writer / signer set quorum)
+    pub fn unseal_sandwich_validators<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnsealSandwichValidators<'info>>,
+        epoch_arg: u64,
+    ) -> Result<()> {
+        instructions::unseal_sandwich_validators_handler(ctx, epoch_arg)
+    }
+
+
+
+
+    /// Expand the sandwich validators bitmap account to full size without writing data.
+    /// This is used to expand the account beyond the initial 10KB limit.
+    pub fn expand_sandwich_validators_bitmap(
+        ctx: Context<ExpandSandwichValidatorsBitmap>,
+        epoch_arg: u64,
+    ) -> Result<()> {
+        instructions::expand_sandwich_validators_bitmap_handler(ctx, epoch_arg)
+    }
+
+    /// Append data to the sandwich validators bitmap account.
+    /// This is a low-level utility for writing pre-computed bitmap data.
+    /// Most users should use `modify_sandwich_validators` instead.
+    pub fn append_data_sandwich_validators_bitmap(
+        ctx: Context<AppendDataSandwichValidatorsBitmap>,
+        epoch_arg: u64,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::append_data_sandwich_validators_bitmap_handler(ctx, epoch_arg, data)
+    }
+
+    /// Write a chunk of the bitmap at an explicit byte offset.
+    /// Unlike appending, the write does not depend on previous uploads, so retrying a
+    /// dropped transaction or patching a region in the middle of an epoch is safe.
+    ///
+    /// # Security Notes:
+    /// - Requires multisig authority as signer (or a Writer; an Ungater for `AndNot`)
+    /// - The chunk must lie within the allocated bitmap
+    /// - Rejected while the config is Frozen, or if the header's epoch is not `epoch_arg`
+    /// - Bits past the end of the epoch are masked off and never counted as gated
+    pub fn write_bitmap_chunk(
+        ctx: Context<WriteBitmapChunk>,
+        epoch_arg: u64,
+        offset: u32,
+        data: Vec<u8>,
+        mode: BitmapWriteMode,
+    ) -> Result<()> {
+        instructions::write_bitmap_chunk_handler(ctx, epoch_arg, offset, data, mode)
+    }
+
+    /// Overwrite a stretch of the bitmap from a run-length encoding, starting at slot
+    /// offset `offset` from the epoch start. A sparse epoch of leader windows and idle
+    /// stretches uploads in a few transactions instead of one per 10KB of raw bytes.
+    ///
+    /// # Security Notes:
+    /// - Requires multisig authority as signer (or a Writer)
+    /// - The expanded runs must lie within the allocated bitmap; any part past the end of
+    ///   the epoch is skipped rather than written
+    /// - Rejected while the config is Frozen, or if the header's epoch is not `epoch_arg`
+    pub fn write_bitmap_runs(
+        ctx: Context<WriteBitmapRuns>,
+        epoch_arg: u64,
+        offset: u32,
+        runs: Vec<u8>,
+    ) -> Result<()> {
+        instructions::write_bitmap_runs_handler(ctx, epoch_arg, offset, runs)
+    }
+
+    /// Clear all data in the sandwich validators bitmap account.
+    /// This sets all slots in the bitmap to ungated (false).
+    pub fn clear_data_sandwich_validators_bitmap<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClearDataSandwichValidatorsBitmap<'info>>,
+        epoch_arg: u64,
+    ) -> Result<()> {
+        instructions::clear_data_sandwich_validators_bitmap_handler(ctx, epoch_arg)
+    }
+
+    /// Create the program-wide GatekeeperConfig singleton.
+    ///
+    /// # Security Notes:
+    /// - Only the program upgrade authority may initialize the config
+    /// - Can only be called once (Anchor `init` constraint)
+    pub fn initialize_gatekeeper_config(
+        ctx: Context<InitializeGatekeeperConfig>,
+        params: GatekeeperConfigParams,
+    ) -> Result<()> {
+        instructions::initialize_gatekeeper_config_handler(ctx, params)
+    }
+
+    /// Replace the settings stored in the GatekeeperConfig singleton.
+    ///
+    /// # Security Notes:
+    /// - Requires the current config admin as signer
+    /// - Passing a different `admin` hands over control of the config
+    pub fn update_gatekeeper_config(
+        ctx: Context<UpdateGatekeeperConfig>,
+        params: GatekeeperConfigParams,
+    ) -> Result<()> {
+        instructions::update_gatekeeper_config_handler(ctx, params)
+    }
+
+    /// Switch the program-wide validation override (Normal, Paused or BlockAll).
+    ///
+    /// # Security Notes:
+    /// - Requires the current config admin as signer
+    /// - Affects every validation that receives the GatekeeperConfig account; callers of
+    ///   `validate_sandwich_validators`, `validate_shadow` or `query_slots` that omit it
+    ///   keep the default behaviour
+    /// - Bitmaps are not modified, so switching back to Normal restores them as they were
+    pub fn set_override_mode(
+        ctx: Context<SetOverrideMode>,
+        override_mode: OverrideMode,
+    ) -> Result<()> {
+        instructions::set_override_mode_handler(ctx, override_mode)
+    }
+
+    /// Create the AuthorityRecord for a `multisig_authority` namespace so its
+    /// active authority can later be rotated without changing PDA addresses.
+    ///
+    /// # Security Notes:
+    /// - Requires the namespace key (multisig authority) as signer
+    /// - The namespace key starts out as the active authority
+    pub fn initialize_authority_record(
+        ctx: Context<InitializeAuthorityRecord>,
+        guardian: Option<Pubkey>,
+        recovery_delay_seconds: i64,
+    ) -> Result<()> {
+        instructions::initialize_authority_record_handler(ctx, guardian, recovery_delay_seconds)
+    }
+
+    /// Propose a new active authority (step 1 of 2).
+    ///
+    /// # Security Notes:
+    /// - Requires the current active authority as signer
+    /// - Replaces any existing proposal, including a guardian recovery
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_authority_handler(ctx, new_authority)
+    }
+
+    /// Accept a pending authority proposal (step 2 of 2).
+    ///
+    /// # Security Notes:
+    /// - Requires the pending authority as signer
+    /// - Guardian recoveries can only be accepted after the recovery delay
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>,
+    ) -> Result<()> {
+        instructions::accept_authority_handler(ctx)
+    }
+
+    /// Cancel a pending authority proposal.
+    ///
+    /// # Security Notes:
+    /// - Requires the current active authority as signer
+    /// - Lets the authority veto a guardian recovery during its delay
+    pub fn cancel_authority_proposal(
+        ctx: Context<CancelAuthorityProposal>,
+    ) -> Result<()> {
+        instructions::cancel_authority_proposal_handler(ctx)
+    }
+
+    /// Start a time-locked recovery to a new authority when the current key is lost.
+    ///
+    /// # Security Notes:
+    /// - Requires the guardian as signer
+    /// - The proposal can only be accepted after `recovery_delay_seconds`
+    pub fn recover_authority(
+        ctx: Context<RecoverAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::recover_authority_handler(ctx, new_authority)
+    }
+
+    /// Set or remove the guardian and its recovery delay.
+    ///
+    /// # Security Notes:
+    /// - Requires the current active authority as signer
+    pub fn set_authority_guardian(
+        ctx: Context<SetAuthorityGuardian>,
+        guardian: Option<Pubkey>,
+        recovery_delay_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_authority_guardian_handler(ctx, guardian, recovery_delay_seconds)
+    }
+
+    /// Create the M-of-N signer set for a namespace.
+    ///
+    /// # Security Notes:
+    /// - Requires the active authority of the namespace as signer
+    /// - Members must be unique; `1 <= threshold <= members.len()`
+    pub fn initialize_signer_set(
+        ctx: Context<InitializeSignerSet>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::initialize_signer_set_handler(ctx, members, threshold)
+    }
+
+    /// Replace the members and threshold of a namespace's signer set.
+    ///
+    /// # Security Notes:
+    /// - Requires the active authority of the namespace as signer
+    pub fn update_signer_set(
+        ctx: Context<UpdateSignerSet>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::update_signer_set_handler(ctx, members, threshold)
+    }
+
+    /// Create the role table for a namespace.
+    ///
+    /// # Security Notes:
+    /// - Requires the active authority of the namespace as signer
+    /// - Role holders can only act through the `operator` account of write instructions
+    pub fn initialize_role_table(
+        ctx: Context<InitializeRoleTable>,
+        grants: Vec<RoleGrant>,
+    ) -> Result<()> {
+        instructions::initialize_role_table_handler(ctx, grants)
+    }
+
+    /// Replace all grants in a namespace's role table.
+    ///
+    /// # Security Notes:
+    /// - Requires the active authority of the namespace as signer
+    /// - Passing an empty list revokes every delegated role
+    pub fn update_role_table(
+        ctx: Context<UpdateRoleTable>,
+        grants: Vec<RoleGrant>,
+    ) -> Result<()> {
+        instructions::update_role_table_handler(ctx, grants)
+    }
+
+    /// Create a curator committee whose members each maintain their own bitmaps, for
+    /// k-of-n validation through `validate_sandwich_validators`.
+    ///
+    /// # Security Notes:
+    /// - Requires the committee namespace key as signer; it becomes the initial governance
+    /// - Members must be unique and `threshold` between 1 and the member count
+    pub fn initialize_committee(
+        ctx: Context<InitializeCommittee>,
+        params: CommitteeParams,
+    ) -> Result<()> {
+        instructions::initialize_committee_handler(ctx, params)
+    }
+
+    /// Replace the governance key, members and threshold of a curator committee.
+    ///
+    /// # Security Notes:
+    /// - Requires the current committee governance as signer
+    /// - Takes effect for the next validation; members' bitmaps are not touched
+    pub fn update_committee(
+        ctx: Context<UpdateCommittee>,
+        params: CommitteeParams,
+    ) -> Result<()> {
+        instructions::update_committee_handler(ctx, params)
+    }
+
+    /// Create the AuthorityRecord of a member's committee namespace, making the member its
+    /// active authority so it can publish bitmaps there with the ordinary write instructions
+    /// (signing as `operator`).
+    ///
+    /// # Security Notes:
+    /// - Requires a current committee member as signer; the namespace is derived from the
+    ///   committee and the member key, so a member can only claim its own
+    /// - Joining again is a no-op, so a rotated record is never reset
+    pub fn join_committee(ctx: Context<JoinCommittee>) -> Result<()> {
+        instructions::join_committee_handler(ctx)
+    }
+
+    /// Create or replace an integrator's allow and deny ranges for one epoch.
+    ///
+    /// # Security Notes:
+    /// - Requires the override owner as signer; the owner is part of the PDA seeds, so
+    ///   each owner can only write its own overrides
+    /// - Ranges must lie within the epoch and must not overlap, within or across lists
+    pub fn set_integrator_overrides(
+        ctx: Context<SetIntegratorOverrides>,
+        epoch_arg: u64,
+        allow: Vec<SlotRange>,
+        deny: Vec<SlotRange>,
+    ) -> Result<()> {
+        instructions::set_integrator_overrides_handler(ctx, epoch_arg, allow, deny)
+    }
+
+    /// Close an integrator's overrides for one epoch and refund the rent to the owner.
+    ///
+    /// # Security Notes:
+    /// - Requires the override owner as signer
+    pub fn close_integrator_overrides(
+        ctx: Context<CloseIntegratorOverrides>,
+        epoch_arg: u64,
+    ) -> Result<()> {
+        instructions::close_integrator_overrides_handler(ctx, epoch_arg)
+    }
+
+    /// Create the IntegratorProfile of a calling program, which selects the gate lists
+    /// and fail policy applied when it validates through `validate_sandwich_validators`.
+    ///
+    /// # Security Notes:
+    /// - Requires the upgrade authority of `integrator_program` as signer
+    /// - Can only be called once per program (Anchor `init` constraint)
+    pub fn initialize_integrator_profile(
+        ctx: Context<InitializeIntegratorProfile>,
+        params: IntegratorProfileParams,
+    ) -> Result<()> {
+        instructions::initialize_integrator_profile_handler(ctx, params)
+    }
+
+    /// Replace the settings stored in an IntegratorProfile.
+    ///
+    /// # Security Notes:
+    /// - Requires the current profile admin as signer
+    /// - Passing a different `admin` hands over control of the profile
+    pub fn update_integrator_profile(
+        ctx: Context<UpdateIntegratorProfile>,
+        params: IntegratorProfileParams,
+    ) -> Result<()> {
+        instructions::update_integrator_profile_handler(ctx, params)
+    }
+
+    /// Add a gate list publisher to the ProviderRegistry, creating the registry on first use.
+    ///
+    /// # Security Notes:
+    /// - Requires the GatekeeperConfig admin as signer, so listed authorities are curated
+    /// - Authorities and names must be unique within the registry
+    pub fn register_provider(
+        ctx: Context<RegisterProvider>,
+        authority: Pubkey,
+        metadata: ProviderMetadata,
+    ) -> Result<()> {
+        instructions::register_provider_handler(ctx, authority, metadata)
+    }
+
+    /// Replace the name, metadata URI and supported epochs of a registered provider.
+    ///
+    /// # Security Notes:
+    /// - Requires the GatekeeperConfig admin or the provider's authority as signer
+    /// - The authority and status of the record cannot be changed
+    pub fn update_provider(
+        ctx: Context<UpdateProvider>,
+        authority: Pubkey,
+        metadata: ProviderMetadata,
+    ) -> Result<()> {
+        instructions::update_provider_handler(ctx, authority, metadata)
+    }
+
+    /// Mark a registered provider as deprecated. The record stays in the registry.
+    ///
+    /// # Security Notes:
+    /// - Requires the GatekeeperConfig admin or the provider's authority as signer
+    /// - Deprecation is permanent; bitmaps of the provider keep validating as before
+    pub fn deprecate_provider(
+        ctx: Context<DeprecateProvider>,
+        authority: Pubkey,
+    ) -> Result<()> {
+        instructions::deprecate_provider_handler(ctx, authority)
+    }
+
+}
+
+
+/// Account storing the validator slot assignments for a specific epoch using a bitmap.
+/// Uses zero-copy patterns for efficient access to 432,000 slots. Data is accessed manually to avoid heap allocation issues.
+///
+/// The first 16 bytes match the legacy u16 layout so both can be told apart by
+/// `layout_version`, which occupies the legacy padding byte (always 0 in legacy accounts).
+/// Older layouts are described in `layout.rs` and upgraded by `migrate_sandwich_validators`.
+#[account(zero_copy)]
+#[repr(C)]
+pub struct SandwichValidators {
+    /// Legacy u16 epoch field; unused (zero) since epochs are stored as u64 below.
+    pub _legacy_epoch: [u8; 2], // 2 bytes
+    pub bump: u8,               // 1 byte
+    /// `LAYOUT_VERSION` for accounts under the u64 seed scheme, 0 for legacy accounts.
+    pub layout_version: u8,     // 1 byte
+    pub bitmap_len: u32,        // 4 bytes
+    /// The epoch number (u64) to which these slot assignments apply.
+    pub epoch: u64,             // 8 bytes
+    /// Lifecycle flags; see `FLAG_SEALED` and `FLAG_LEGACY_SEEDS`.
+    pub flags: u8,              // 1 byte
+    /// Consecutive slots covered by each bitmap bit; 0 in older accounts, read as 1.
+    pub slots_per_bit: u8,      // 1 byte
+    pub _reserved: [u8; 2],     // 2 bytes
+    /// Exact number of gated bits in the bitmap (slots, or leader windows when `slots_per_bit` is 4).
+    pub gated_count: u32,       // 4 bytes
+    /// SHA-256 of the bitmap recorded by `seal_sandwich_validators`; zero while unsealed.
+    pub bitmap_hash: [u8; 32],  // 32 bytes
+    // Total struct size: 56 bytes (2 + 1 + 1 + 4 + 8 + 1 + 1 + 2 + 4 + 32)
+    // With 8-byte discriminator, bitmap data begins at offset 64
+}
+
+impl SandwichValidators {
+    /// Seed prefix of the u64 epoch scheme: `[SEED_PREFIX, multisig_authority, epoch (u64 LE)]`.
+    pub const SEED_PREFIX: &'static [u8] = b"sandwich_validators_v2";
+    /// Seed prefix of the legacy u16 scheme: `[LEGACY_SEED_PREFIX, multisig_authority, epoch (u16 LE)]`.
+    /// Legacy accounts remain readable by validation and can be closed, but are no longer written.
+    pub const LEGACY_SEED_PREFIX: &'static [u8] = b"sandwich_validators";
+    pub const LAYOUT_VERSION: u8 = 4;
+    pub const DATA_OFFSET: usize = 64; // discriminator (8) + legacy epoch (2) + bump (1) + version (1) + bitmap_len (4) + epoch (8) + flags (1) + slots_per_bit (1) + reserved (2) + gated_count (4) + bitmap_hash (32)
+    pub const LEGACY_DATA_OFFSET: usize = 16; // discriminator (8) + epoch (2) + bump (1) + padding (1) + bitmap_len (4)
+    /// `flags` bit set while the account is sealed.
+    pub const FLAG_SEALED: u8 = 1;
+    /// `flags` bit set by migration on accounts living at the legacy u16-seeded address,
+    /// whose stored bump only derives that address.
+    pub const FLAG_LEGACY_SEEDS: u8 = 2;
+
+    /// Granularities `set_sandwich_validators` accepts: one bit per slot, or one bit per
+    /// leader window.
+    pub fn validate_slots_per_bit(slots_per_bit: u8) -> Result<()> {
+        if slots_per_bit != 1 && slots_per_bit != SLOTS_PER_LEADER_WINDOW {
+            return err!(GatekeeperError::InvalidSlotsPerBit);
+        }
+        Ok(())
+    }
+
+    /// Fully expanded account size: one bit for every `slots_per_bit` of the epoch's
+    /// `slots_in_epoch` slots.
+    pub fn target_account_size(slots_in_epoch: u64, slots_per_bit: u8) -> Result<usize> {
+        usize::try_from(slots_in_epoch.div_ceil(u64::from(slots_per_bit.max(1))).div_ceil(8))
+            .ok()
+            .and_then(|bitmap_bytes| bitmap_bytes.checked_add(Self::DATA_OFFSET))
+            .ok_or_else(|| error!(GatekeeperError::SlotOutOfRange))
+    }
+}
+
+/// A contiguous run of `len` slots beginning at absolute slot `start`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct SlotRange {
+    pub start: u64,
+    pub len: u32,
+}
+
+impl SlotRange {
+    /// Exclusive end slot, or `None` on overflow.
+    pub fn end(&self) -> Option<u64> {
+        self.start.checked_add(u64::from(self.len))
+    }
+
+    pub fn contains(&self, slot: u64) -> bool {
+        slot >= self.start && slot - self.start < u64::from(self.len)
+    }
+
+    pub fn overlaps(&self, other: &SlotRange) -> bool {
+        // Ranges are validated before comparison, so `end` cannot overflow
+        self.start < other.end().unwrap_or(u64::MAX) && other.start < self.end().unwrap_or(u64::MAX)
+    }
+}
+
+/// Return data of `query_slots`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SlotQueryResult {
+    /// Epoch the slots were looked up in (the current epoch).
+    pub epoch: u64,
+    /// Bit `i` is set when the `i`-th queried slot is gated.
+    pub gated_mask: u64,
+    /// First ungated slot of the epoch at or after the first queried slot, if any.
+    pub next_ungated_slot: Option<u64>,
+    /// Whether the answer comes from the bitmap rather than the override mode or fail policy.
+    pub from_bitmap: bool,
+}
+
+/// Return data of `validate_sources`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceValidationResult {
+    pub epoch: u64,
+    pub slot: u64,
+    /// Bit `i` is set when the `i`-th (authority, PDA) pair gates the slot.
+    pub flagged_mask: u8,
+    pub flagged_count: u8,
+    /// Whether `flagged_count` reached the quorum.
+    pub gated: bool,
+}
+
+/// How `write_bitmap_chunk` combines the written bytes with the bitmap.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitmapWriteMode {
+    /// Replace the bytes.
+    Overwrite,
+    /// Gate every slot whose bit is set in the chunk; other slots are unchanged.
+    Or,
+    /// Ungate every slot whose bit is set in the chunk; other slots are unchanged.
+    AndNot,
+}
+
+impl BitmapWriteMode {
+    /// Byte resulting from writing `chunk` over `existing`.
+    pub fn apply(self, existing: u8, chunk: u8) -> u8 {
+        match self {
+            BitmapWriteMode::Overwrite => chunk,
+            BitmapWriteMode::Or => existing | chunk,
+            BitmapWriteMode::AndNot => existing & !chunk,
+        }
+    }
+}
+
+/// Program-wide configuration singleton.
+/// Every write instruction requires it, and so does every validation added alongside it
+/// (`validate_v2`, `validate_with_overrides`, `validate_strict`, `validate_epoch_boundary`
+/// and `validate_sources`): its override mode, fail policy and epoch source apply to all
+/// of them, so callers cannot opt out by leaving it off. `validate_sandwich_validators`, `validate_shadow`
+/// and `query_slots` keep the baseline account layout with the Clock sysvar third and
+/// accept the config as an optional trailing account, as does `set_integrator_overrides`;
+/// they treat a missing config as `GatekeeperConfig::default()`, which reproduces the
+/// behaviour of the program before the config existed.
+#[account]
+#[derive(InitSpace, Default)]
+pub struct GatekeeperConfig {
+    /// Key allowed to update this configuration.
+    pub admin: Pubkey,
+    /// Operating mode applied to write instructions.
+    pub mode: OperatingMode,
+    /// Where epoch boundaries are taken from.
+    pub slots_per_epoch_source: SlotsPerEpochSource,
+    /// Validation outcome when no bitmap is configured for the current epoch.
+    pub fail_policy: FailPolicy,
+    /// Incident override checked by validation before any bitmap is read.
+    pub override_mode: OverrideMode,
+    /// Whether validation enforces bitmaps that have not been sealed.
+    pub unsealed_policy: UnsealedPolicy,
+    /// Layout version of this account.
+    pub layout_version: u8,
+    pub bump: u8,
+}
+
+impl GatekeeperConfig {
+    pub const SEED: &'static [u8] = b"gatekeeper_config";
+    pub const LAYOUT_VERSION: u8 = 1;
+
+    /// Returns the provided config, or the defaults when the caller omitted it.
+    pub fn effective(config: Option<&Self>) -> Self {
+        config.cloned().unwrap_or_default()
+    }
+
+    /// Rejects bitmap lifecycle instructions (see `OperatingMode::Frozen`) while the gatekeeper is frozen.
+    pub fn require_writable(&self) -> Result<()> {
+        if self.mode == OperatingMode::Frozen {
+            return err!(GatekeeperError::GatekeeperFrozen);
+        }
+        Ok(())
+    }
+}
+
+/// Operating mode of the gatekeeper.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum OperatingMode {
+    /// All instructions behave normally.
+    #[default]
+    Active,
+    /// No bitmap lifecycle instruction runs: bitmaps cannot be created, modified
+    /// (modify, ranges, compact, append, chunk and run writes, clear), expanded, sealed,
+    /// unsealed, migrated or closed, legacy closes included. Validation, queries and
+    /// config, authority, profile and committee management are unaffected.
+    Frozen,
+}
+
+/// Program-wide override applied by `validate_sandwich_validators` before bitmaps are consulted.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum OverrideMode {
+    /// Validation uses the slot bitmaps.
+    #[default]
+    Normal,
+    /// Every validation passes; bitmaps are left untouched so they can be restored.
+    Paused,
+    /// Every validation fails with `SlotIsGated`.
+    BlockAll,
+}
+
+impl OverrideMode {
+    /// Outcome forced by the override, or `None` when the bitmap decides.
+    pub fn forced_outcome(&self) -> Option<Result<()>> {
+        match self {
+            OverrideMode::Normal => None,
+            OverrideMode::Paused => Some(Ok(())),
+            OverrideMode::BlockAll => Some(err!(GatekeeperError::SlotIsGated)),
+        }
+    }
+}
+
+/// Source used to derive the first slot and length of an epoch.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum SlotsPerEpochSource {
+    /// `epoch * SLOTS_PER_EPOCH`. Only correct on clusters without warmup epochs
+    /// that use mainnet-beta's epoch length.
+    Fixed,
+    /// The cluster's EpochSchedule sysvar. Identical to `Fixed` on mainnet-beta.
+    #[default]
+    EpochSchedule,
+}
+
+impl SlotsPerEpochSource {
+    /// Returns the first slot of `epoch` and the number of slots it contains.
+    pub fn epoch_bounds(&self, epoch: u64) -> Result<(u64, u64)> {
+        match self {
+            SlotsPerEpochSource::Fixed => {
+                let first_slot = epoch
+                    .checked_mul(SLOTS_PER_EPOCH as u64)
+                    .ok_or(GatekeeperError::SlotOutOfRange)?;
+                Ok((first_slot, SLOTS_PER_EPOCH as u64))
+            }
+            SlotsPerEpochSource::EpochSchedule => {
+                let schedule = EpochSchedule::get()?;
+                Ok((schedule.get_first_slot_in_epoch(epoch), schedule.get_slots_in_epoch(epoch)))
+            }
+        }
+    }
+
+    /// Returns the offset of `slot` within `epoch`, or `None` if the slot lies outside it.
+    pub fn slot_offset(&self, epoch: u64, slot: u64) -> Result<Option<u64>> {
+        let (first_slot, slots_in_epoch) = self.epoch_bounds(epoch)?;
+        Ok(slot
+            .checked_sub(first_slot)
+            .filter(|&offset| offset < slots_in_epoch))
+    }
+}
+
+/// Validation outcome when no bitmap is configured for the current epoch.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum FailPolicy {
+    /// Treat the slot as ungated.
+    #[default]
+    Open,
+    /// Treat the slot as gated.
+    Closed,
+}
+
+impl FailPolicy {
+    /// Outcome of validation when the epoch has no usable bitmap.
+    pub fn unconfigured(&self) -> Result<()> {
+        match self {
+            FailPolicy::Open => Ok(()),
+            FailPolicy::Closed => err!(GatekeeperError::SlotIsGated),
+        }
+    }
+}
+
+/// How validation treats a bitmap that has not been sealed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum UnsealedPolicy {
+    /// Enforce every bitmap as soon as it exists.
+    #[default]
+    Enforce,
+    /// Treat an unsealed bitmap as unconfigured, so the fail policy applies.
+    Ignore,
+}
+
+/// Settings accepted by `initialize_gatekeeper_config` and `update_gatekeeper_config`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GatekeeperConfigParams {
+    pub admin: Pubkey,
+    pub mode: OperatingMode,
+    pub slots_per_epoch_source: SlotsPerEpochSource,
+    pub fail_policy: FailPolicy,
+    pub unsealed_policy: UnsealedPolicy,
+}
+
+/// Alias record decoupling the key that signs writes from the key used in PDA seeds.
+/// `SandwichValidators` PDAs are always derived from `namespace`, so integrators keep
+/// deriving the same addresses after `authority` is rotated.
+#[account]
+#[derive(InitSpace)]
+pub struct AuthorityRecord {
+    /// The original `multisig_authority` key used in PDA seeds. Never changes.
+    pub namespace: Pubkey,
+    /// Key currently allowed to write under `namespace`.
+    pub authority: Pubkey,
+    /// Key proposed to become `authority`, if any.
+    pub pending_authority: Option<Pubkey>,
+    /// Unix timestamp after which `pending_authority` may accept (0 when proposed by `authority`).
+    pub pending_unlock_timestamp: i64,
+    /// Key allowed to start a time-locked recovery when `authority` is lost.
+    pub guardian: Option<Pubkey>,
+    /// Seconds a guardian-initiated recovery waits before it can be accepted.
+    pub recovery_delay_seconds: i64,
+    pub bump: u8,
+}
+
+impl AuthorityRecord {
+    pub const SEED: &'static [u8] = b"authority_record";
+}
+
+/// Built-in M-of-N signer set for a namespace. When supplied to
+/// `modify_sandwich_validators`, `clear_data_sandwich_validators_bitmap` or
+/// `close_sandwich_validator`, any `threshold` members signing the same transaction
+/// (passed through `remaining_accounts`) authorise the instruction.
+#[account]
+#[derive(InitSpace)]
+pub struct SignerSet {
+    /// The `multisig_authority` namespace this set acts for.
+    pub namespace: Pubkey,
+    #[max_len(MAX_SIGNER_SET_MEMBERS)]
+    pub members: Vec<Pubkey>,
+    /// Number of distinct member signatures required.
+    pub threshold: u8,
+    pub bump: u8,
+}
+
+impl SignerSet {
+    pub const SEED: &'static [u8] = b"signer_set";
+
+    /// Checks member uniqueness, the size limit and the threshold range.
+    pub fn validate_members(members: &[Pubkey], threshold: u8) -> Result<()> {
+        if members.is_empty() || members.len() > MAX_SIGNER_SET_MEMBERS {
+            return err!(GatekeeperError::InvalidSignerSet);
+        }
+        if threshold == 0 || usize::from(threshold) > members.len() {
+            return err!(GatekeeperError::InvalidSignerSet);
+        }
+        for (i, member) in members.iter().enumerate() {
+            if members[i + 1..].contains(member) {
+                return err!(GatekeeperError::InvalidSignerSet);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Scoped permissions for keys other than the active authority of a namespace.
+/// When supplied to a write instruction, an `operator` holding the required role may
+/// sign instead of the active authority, which implicitly holds every role.
+#[account]
+#[derive(InitSpace)]
+pub struct RoleTable {
+    /// The `multisig_authority` namespace these grants apply to.
+    pub namespace: Pubkey,
+    #[max_len(MAX_ROLE_GRANTS)]
+    pub grants: Vec<RoleGrant>,
+    pub bump: u8,
+}
+
+impl RoleTable {
+    pub const SEED: &'static [u8] = b"role_table";
+
+    /// Checks key uniqueness, the size limit and that each grant holds known roles only.
+    pub fn validate_grants(grants: &[RoleGrant]) -> Result<()> {
+        if grants.len() > MAX_ROLE_GRANTS {
+            return err!(GatekeeperError::InvalidRoleTable);
+        }
+        for (i, grant) in grants.iter().enumerate() {
+            if grant.roles == 0 || grant.roles & !Role::ALL != 0 {
+                return err!(GatekeeperError::InvalidRoleTable);
+            }
+            if grants[i + 1..].iter().any(|other| other.key == grant.key) {
+                return err!(GatekeeperError::InvalidRoleTable);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if `key` holds at least one of `roles`.
+    pub fn has_any_role(&self, key: &Pubkey, roles: &[Role]) -> bool {
+        let wanted = roles.iter().fold(0u8, |mask, role| mask | role.mask());
+        self.grants
+            .iter()
+            .any(|grant| grant.key == *key && grant.roles & wanted != 0)
+    }
+}
+
+/// Roles granted to a single key in a `RoleTable`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct RoleGrant {
+    pub key: Pubkey,
+    /// Bitmask of `Role` values (see `Role::mask`).
+    pub roles: u8,
+}
+
+/// Write permissions that can be delegated through a `RoleTable`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// `set_sandwich_validators` and `expand_sandwich_validators_bitmap`.
+    Provisioner,
+    /// `modify_sandwich_validators` and `append_data_sandwich_validators_bitmap`.
+    Writer,
+    /// Ungating only: `modify_sandwich_validators` without `slots_to_gate`, and
+    /// `clear_data_sandwich_validators_bitmap`.
+    Ungater,
+    /// `close_sandwich_validator`.
+    Closer,
+}
+
+impl Role {
+    pub const PROVISIONER: u8 = 1 << 0;
+    pub const WRITER: u8 = 1 << 1;
+    pub const UNGATER: u8 = 1 << 2;
+    pub const CLOSER: u8 = 1 << 3;
+    pub const ALL: u8 = Self::PROVISIONER | Self::WRITER | Self::UNGATER | Self::CLOSER;
+
+    pub const fn mask(self) -> u8 {
+        match self {
+            Role::Provisioner => Self::PROVISIONER,
+            Role::Writer => Self::WRITER,
+            Role::Ungater => Self::UNGATER,
+            Role::Closer => Self::CLOSER,
+        }
+    }
+}
+
+/// Validation settings chosen by an integrating program. When the program passes its
+/// profile to `validate_sandwich_validators`, the profile's authorities and fail policy
+/// replace the single `multisig_authority` and the program-wide fail policy.
+/// The profile only applies to calls that pass it, and only from an integrator invoked
+/// directly by the transaction; see `validate_sandwich_validators` for both limits.
+#[account]
+#[derive(InitSpace)]
+pub struct IntegratorProfile {
+    /// The program this profile applies to, identified through the instructions sysvar.
+    pub integrator: Pubkey,
+    /// Key allowed to update this profile.
+    pub admin: Pubkey,
+    /// Namespaces whose gate lists are honoured.
+    #[max_len(MAX_PROFILE_AUTHORITIES)]
+    pub authorities: Vec<Pubkey>,
+    /// How the authorities' decisions are combined.
+    pub combination: SourceCombination,
+    /// Decision for an authority without a bitmap for the current epoch.
+    pub fail_policy: FailPolicy,
+    /// Absolute slots that always pass, whatever the gate lists say.
+    #[max_len(MAX_PROFILE_EXEMPT_RANGES)]
+    pub exempt_ranges: Vec<SlotRange>,
+    pub bump: u8,
+}
+
+impl IntegratorProfile {
+    pub const SEED: &'static [u8] = b"integrator_profile";
+
+    /// Checks authority uniqueness, the size limits and that exempt ranges are well formed.
+    pub fn validate_params(params: &IntegratorProfileParams) -> Result<()> {
+        let authorities = &params.authorities;
+        if authorities.is_empty() || authorities.len() > MAX_PROFILE_AUTHORITIES {
+            return err!(GatekeeperError::InvalidIntegratorProfile);
+        }
+        for (i, authority) in authorities.iter().enumerate() {
+            if authorities[i + 1..].contains(authority) {
+                return err!(GatekeeperError::InvalidIntegratorProfile);
+            }
+        }
+        if params.exempt_ranges.len() > MAX_PROFILE_EXEMPT_RANGES
+            || params.exempt_ranges.iter().any(|range| range.len == 0 || range.end().is_none())
+        {
+            return err!(GatekeeperError::InvalidIntegratorProfile);
+        }
+        Ok(())
+    }
+
+    pub fn apply(&mut self, params: IntegratorProfileParams) {
+        self.admin = params.admin;
+        self.authorities = params.authorities;
+        self.combination = params.combination;
+        self.fail_policy = params.fail_policy;
+        self.exempt_ranges = params.exempt_ranges;
+    }
+
+    pub fn is_exempt(&self, slot: u64) -> bool {
+        self.exempt_ranges.iter().any(|range| range.contains(slot))
+    }
+}
+
+/// A committee of curators. Each member publishes per-epoch bitmaps under a namespace
+/// derived from the committee (see `member_namespace`), so its own gate lists are never
+/// counted. Passing the committee as the `committee` account of
+/// `validate_sandwich_validators` gates a slot only when `threshold` members gate it.
+#[account]
+#[derive(InitSpace)]
+pub struct Committee {
+    /// Key in the PDA seeds. Never changes.
+    pub namespace: Pubkey,
+    /// Key allowed to change the members and threshold.
+    pub governance: Pubkey,
+    /// Curator keys; each writes under its member namespace after `join_committee`.
+    #[max_len(MAX_COMMITTEE_MEMBERS)]
+    pub members: Vec<Pubkey>,
+    /// Bump of each member's namespace PDA, in member order.
+    #[max_len(MAX_COMMITTEE_MEMBERS)]
+    pub member_bumps: Vec<u8>,
+    /// Number of members that must gate a slot.
+    pub threshold: u8,
+    pub bump: u8,
+}
+
+impl Committee {
+    pub const SEED: &'static [u8] = b"committee";
+    pub const MEMBER_SEED: &'static [u8] = b"committee_member";
+
+    /// Replaces the members, recording the bump of each member's namespace PDA.
+    pub fn set_members(&mut self, committee: &Pubkey, members: Vec<Pubkey>, program_id: &Pubkey) {
+        self.member_bumps = members.iter()
+            .map(|member| Pubkey::find_program_address(&[Self::MEMBER_SEED, committee.as_ref(), member.as_ref()], program_id).1)
+            .collect();
+        self.members = members;
+    }
+
+    /// The namespaces the members write under, in member order, derived with the stored bumps.
+    pub fn member_namespaces(&self, committee: &Pubkey, program_id: &Pubkey) -> Result<Vec<Pubkey>> {
+        self.members.iter().zip(&self.member_bumps)
+            .map(|(member, bump)| {
+                Pubkey::create_program_address(&[Self::MEMBER_SEED, committee.as_ref(), member.as_ref(), &[*bump]], program_id)
+                    .map_err(|_| error!(GatekeeperError::InvalidCommittee))
+            })
+            .collect()
+    }
+
+    /// Checks member uniqueness, the size limit and the threshold range.
+    pub fn validate_members(members: &[Pubkey], threshold: u8) -> Result<()> {
+        if members.is_empty() || members.len() > MAX_COMMITTEE_MEMBERS {
+            return err!(GatekeeperError::InvalidCommittee);
+        }
+        if threshold == 0 || usize::from(threshold) > members.len() {
+            return err!(GatekeeperError::InvalidCommittee);
+        }
+        for (i, member) in members.iter().enumerate() {
+            if members[i + 1..].contains(member) {
+                return err!(GatekeeperError::InvalidCommittee);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Settings accepted by `initialize_committee` and `update_committee`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CommitteeParams {
+    pub governance: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+/// Directory of gate list publishers, so integrators can look an authority up by name
+/// instead of hard-coding it. Curated by the GatekeeperConfig admin; it is informational
+/// only and no validation instruction reads it.
+#[account]
+#[derive(InitSpace)]
+pub struct ProviderRegistry {
+    #[max_len(MAX_PROVIDERS)]
+    pub providers: Vec<ProviderRecord>,
+    pub bump: u8,
+}
+
+impl ProviderRegistry {
+    pub const SEED: &'static [u8] = b"provider_registry";
+
+    /// Index of the record of `authority`.
+    pub fn position(&self, authority: &Pubkey) -> Result<usize> {
+        self.providers
+            .iter()
+            .position(|record| record.authority == *authority)
+            .ok_or_else(|| error!(GatekeeperError::ProviderNotFound))
+    }
+
+    /// Checks that no record other than `authority`'s already uses `name`.
+    pub fn check_name_available(&self, name: &str, authority: &Pubkey) -> Result<()> {
+        if self
+            .providers
+            .iter()
+            .any(|record| record.name == name && record.authority != *authority)
+        {
+            return err!(GatekeeperError::ProviderAlreadyRegistered);
+        }
+        Ok(())
+    }
+}
+
+/// One publisher listed in the ProviderRegistry.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct ProviderRecord {
+    /// Namespace key the provider publishes its bitmaps under. Never changes.
+    pub authority: Pubkey,
+    #[max_len(MAX_PROVIDER_NAME_LEN)]
+    pub name: String,
+    /// Off-chain description of the provider (methodology, contact, ...).
+    #[max_len(MAX_PROVIDER_URI_LEN)]
+    pub metadata_uri: String,
+    /// First epoch the provider publishes bitmaps for.
+    pub first_epoch: u64,
+    /// Last epoch the provider publishes bitmaps for; `None` while it is ongoing.
+    pub last_epoch: Option<u64>,
+    pub status: ProviderStatus,
+}
+
+/// Lifecycle of a ProviderRecord.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum ProviderStatus {
+    #[default]
+    Active,
+    /// The provider should no longer be relied on. Its record is kept so the
+    /// deprecation stays visible on chain.
+    Deprecated,
+}
+
+/// Descriptive fields accepted by `register_provider` and `update_provider`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProviderMetadata {
+    pub name: String,
+    pub metadata_uri: String,
+    pub first_epoch: u64,
+    pub last_epoch: Option<u64>,
+}
+
+impl ProviderMetadata {
+    /// Checks the string lengths and the epoch range.
+    pub fn validate(&self) -> Result<()> {
+        if self.name.is_empty() || self.name.len() > MAX_PROVIDER_NAME_LEN {
+            return err!(GatekeeperError::InvalidProvider);
+        }
+        if self.metadata_uri.len() > MAX_PROVIDER_URI_LEN {
+            return err!(GatekeeperError::InvalidProvider);
+        }
+        if self.last_epoch.is_some_and(|last| last < self.first_epoch) {
+            return err!(GatekeeperError::InvalidProvider);
+        }
+        Ok(())
+    }
+}
+
+/// Allow and deny ranges an integrator layers on top of an authority's bitmap for one
+/// epoch, applied by `validate_with_overrides`.
+#[account]
+#[derive(InitSpace)]
+pub struct IntegratorOverrides {
+    /// Key that writes these overrides; part of the PDA seeds.
+    pub owner: Pubkey,
+    pub epoch: u64,
+    /// Absolute slot ranges that pass even when the bitmap gates them.
+    #[max_len(MAX_OVERRIDE_RANGES)]
+    pub allow: Vec<SlotRange>,
+    /// Absolute slot ranges that are gated even when the bitmap does not gate them.
+    #[max_len(MAX_OVERRIDE_RANGES)]
+    pub deny: Vec<SlotRange>,
+    pub bump: u8,
+}
+
+impl IntegratorOverrides {
+    pub const SEED: &'static [u8] = b"integrator_overrides";
+
+    /// Checks the size limits and that every range is non-empty, lies within
+    /// [first_slot, first_slot + slots_in_epoch) and overlaps no other range.
+    pub fn validate_ranges(allow: &[SlotRange], deny: &[SlotRange], first_slot: u64, slots_in_epoch: u64) -> Result<()> {
+        if allow.len() > MAX_OVERRIDE_RANGES || deny.len() > MAX_OVERRIDE_RANGES {
+            return err!(GatekeeperError::TooManySlots);
+        }
+        let epoch_end = first_slot.saturating_add(slots_in_epoch);
+        for range in allow.iter().chain(deny) {
+            let in_bounds = range.len > 0
+                && range.start >= first_slot
+                && range.end().is_some_and(|end| end <= epoch_end);
+            if !in_bounds {
+                return err!(GatekeeperError::SlotOutOfRange);
+            }
+        }
+        for list in [allow, deny] {
+            for (i, a) in list.iter().enumerate() {
+                if list[i + 1..].iter().any(|b| a.overlaps(b)) {
+                    return err!(GatekeeperError::DuplicateSlots);
+                }
+            }
+        }
+        if allow.iter().any(|a| deny.iter().any(|b| a.overlaps(b))) {
+            return err!(GatekeeperError::OverlapSlots);
+        }
+        Ok(())
+    }
+
+    /// `Some(true)` if `slot` is denied, `Some(false)` if allowed, `None` if the bitmap decides.
+    pub fn decision(&self, slot: u64) -> Option<bool> {
+        if self.deny.iter().any(|range| range.contains(slot)) {
+            Some(true)
+        } else if self.allow.iter().any(|range| range.contains(slot)) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// How the decisions of several gate lists are combined.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum SourceCombination {
+    /// The slot is gated if any authority gates it.
+    #[default]
+    Union,
+    /// The slot is gated only if every authority gates it.
+    Intersection,
+}
+
+/// Settings accepted by `initialize_integrator_profile` and `update_integrator_profile`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct IntegratorProfileParams {
+    pub admin: Pubkey,
+    pub authorities: Vec<Pubkey>,
+    pub combination: SourceCombination,
+    pub fail_policy: FailPolicy,
+    pub exempt_ranges: Vec<SlotRange>,
+}
+
+/// Accounts for the `set_sandwich_validators` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64)]
+pub struct SetSandwichValidators<'info> {
+    /// CHECK: This account is manually validated and initialized in the instruction handler
+    #[account(
+        mut,
+        seeds = [SandwichValidators::SEED_PREFIX, multisig_authority.key().as_ref(), &epoch_arg.to_le_bytes()],
+        bump
+    )]
+    pub sandwich_validators: AccountInfo<'info>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    #[account(mut)]
+    pub multisig_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    #[account(mut)]
+    pub operator: Option<Signer<'info>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+}
+
+/// Accounts for the `validate_sandwich_validators` instruction.
+#[derive(Accounts)]
+pub struct ValidateSandwichValidators<'info> {
+    /// The PDA account to be validated.
+    /// CHECK: The address is manually validated in the instruction handler against the
+    /// multisig_authority and current epoch from the clock sysvar.
+    pub sandwich_validators: AccountInfo<'info>,
+    /// The multisig authority account used in PDA derivation.
+    /// Not a signer as validation is public.
+    /// CHECK: This is used for PDA derivation only and is not a signer.
+    pub multisig_authority: AccountInfo<'info>,
+    /// The Clock sysvar to get current epoch and slot.
+    pub clock: Sysvar<'info, Clock>,
+    /// Optional GatekeeperConfig singleton; defaults apply when omitted.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Option<Account<'info, GatekeeperConfig>>,
+    /// Instructions sysvar, used to identify the calling program; required with `integrator_profile`.
+    /// CHECK: Address constrained to the instructions sysvar.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+    /// Optional profile of the calling program. When supplied, the gate lists of its
+    /// authorities are read from `sandwich_validators` and `remaining_accounts`.
+    #[account(
+        seeds = [IntegratorProfile::SEED, integrator_profile.integrator.as_ref()],
+        bump = integrator_profile.bump
+    )]
+    pub integrator_profile: Option<Account<'info, IntegratorProfile>>,
+    /// Optional curator committee. When supplied, the gate lists of its member namespaces
+    /// are read from `sandwich_validators` and `remaining_accounts`.
+    #[account(
+        seeds = [Committee::SEED, committee.namespace.as_ref()],
+        bump = committee.bump
+    )]
+    pub committee: Option<Account<'info, Committee>>,
+}
+
+/// Accounts for the `validate_shadow` instruction. `#[event_cpi]` appends the
+/// `event_authority` and `program` accounts used by `emit_cpi!`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ValidateShadow<'info> {
+    /// The PDA account to be validated.
+    /// CHECK: The address is manually validated in the instruction handler against the
+    /// multisig_authority and current epoch from the clock sysvar.
+    pub sandwich_validators: AccountInfo<'info>,
+    /// The multisig authority account used in PDA derivation.
+    /// CHECK: This is used for PDA derivation only and is not a signer.
+    pub multisig_authority: AccountInfo<'info>,
+    /// The Clock sysvar to get current epoch and slot.
+    pub clock: Sysvar<'info, Clock>,
+    /// Optional GatekeeperConfig singleton; defaults apply when omitted.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Option<Account<'info, GatekeeperConfig>>,
+}
+
+/// Accounts for the `validate_v2` instruction.
+#[derive(Accounts)]
+pub struct ValidateV2<'info> {
+    /// The PDA account to be validated.
+    /// CHECK: The address is checked in the handler against the multisig_authority, the
+    /// current epoch and the bump stored in the account.
+    pub sandwich_validators: AccountInfo<'info>,
+    /// The multisig authority account used in PDA derivation.
+    /// CHECK: This is used for PDA derivation only and is not a signer.
+    pub multisig_authority: AccountInfo<'info>,
+    /// GatekeeperConfig singleton.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
+
+/// Accounts for the `validate_with_overrides` instruction.
+#[derive(Accounts)]
+pub struct ValidateWithOverrides<'info> {
+    /// The PDA account to be validated.
+    /// CHECK: The address is manually validated in the instruction handler against the
+    /// multisig_authority and current epoch.
+    pub sandwich_validators: AccountInfo<'info>,
+    /// The multisig authority account used in PDA derivation.
+    /// CHECK: This is used for PDA derivation only and is not a signer.
+    pub multisig_authority: AccountInfo<'info>,
+    /// The integrator's overrides for the current epoch; may never have been created.
+    /// CHECK: The address is checked in the handler against override_owner and the current epoch.
+    pub integrator_overrides: AccountInfo<'info>,
+    /// CHECK: Key owning `integrator_overrides`; used for PDA derivation only.
+    pub override_owner: AccountInfo<'info>,
+    /// GatekeeperConfig singleton.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
+
+/// Accounts for the `validate_strict` instruction.
+#[derive(Accounts)]
+pub struct ValidateStrict<'info> {
+    /// The PDA account to be validated.
+    /// CHECK: The address, owner and discriminator are checked in the handler.
+    pub sandwich_validators: AccountInfo<'info>,
+    /// The multisig authority account used in PDA derivation.
+    /// CHECK: This is used for PDA derivation only and is not a signer.
+    pub multisig_authority: AccountInfo<'info>,
+    /// GatekeeperConfig singleton.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
+
+/// Accounts for the `validate_epoch_boundary` instruction.
+/// The candidate SandwichValidators PDAs are passed in `remaining_accounts`.
+#[derive(Accounts)]
+pub struct ValidateEpochBoundary<'info> {
+    /// The multisig authority account used in PDA derivation.
+    /// CHECK: This is used for PDA derivation only and is not a signer.
+    pub multisig_authority: AccountInfo<'info>,
+    /// GatekeeperConfig singleton.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
+
+/// Accounts for the `validate_sources` instruction.
+/// The (authority, SandwichValidators PDA) pairs are passed in `remaining_accounts`.
+#[derive(Accounts)]
+pub struct ValidateSources<'info> {
+    /// GatekeeperConfig singleton.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
+
+/// Accounts for the `query_slots` instruction.
+#[derive(Accounts)]
+pub struct QuerySlots<'info> {
+    /// The PDA account holding the current epoch's bitmap.
+    /// CHECK: The address is manually validated in the instruction handler against the
+    /// multisig_authority and current epoch from the clock sysvar.
+    pub sandwich_validators: AccountInfo<'info>,
+    /// The multisig authority account used in PDA derivation.
+    /// CHECK: This is used for PDA derivation only and is not a signer.
+    pub multisig_authority: AccountInfo<'info>,
+    /// The Clock sysvar to get current epoch and slot.
+    pub clock: Sysvar<'info, Clock>,
+    /// Optional GatekeeperConfig singleton; defaults apply when omitted.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Option<Account<'info, GatekeeperConfig>>,
+}
+
+/// Accounts for the `modify_sandwich_validators` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64, slots_to_gate: Vec<u64>, slots_to_ungate: Vec<u64>)]
+pub struct ModifySandwichValidators<'info> {
+    /// CHECK: This account is manually validated in the instruction handler
+    #[account(
+        mut,
+        constraint = is_bitmap_pda(&multisig_authority.key(), &sandwich_validators.to_account_info(), epoch_arg, &ID) @ GatekeeperError::InvalidPda
+    )]
+    pub sandwich_validators: AccountInfo<'info>,
+
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    #[account(mut)]
+    pub multisig_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+    /// Optional M-of-N signer set; member signatures are read from `remaining_accounts`.
+    #[account(seeds = [SignerSet::SEED, multisig_authority.key().as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+}
+
+
+
+/// Accounts for the `modify_sandwich_validators_ranges` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64)]
+pub struct ModifySandwichValidatorsRanges<'info> {
+    /// CHECK: This account is manually validated in the instruction handler
+    #[account(
+        mut,
+        constraint = is_bitmap_pda(&multisig_authority.key(), &sandwich_validators.to_account_info(), epoch_arg, &ID) @ GatekeeperError::InvalidPda
+    )]
+    pub sandwich_validators: AccountInfo<'info>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    pub multisig_authority: UncheckedAccount<'info>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+    /// Optional M-of-N signer set; member signatures are read from `remaining_accounts`.
+    #[account(seeds = [SignerSet::SEED, multisig_authority.key().as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+}
+
+/// Accounts for the `modify_sandwich_validators_compact` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64)]
+pub struct ModifySandwichValidatorsCompact<'info> {
+    /// CHECK: This account is manually validated in the instruction handler
+    #[account(
+        mut,
+        constraint = is_bitmap_pda(&multisig_authority.key(), &sandwich_validators.to_account_info(), epoch_arg, &ID) @ GatekeeperError::InvalidPda
+    )]
+    pub sandwich_validators: AccountInfo<'info>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    pub multisig_authority: UncheckedAccount<'info>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+    /// Optional M-of-N signer set; member signatures are read from `remaining_accounts`.
+    #[account(seeds = [SignerSet::SEED, multisig_authority.key().as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+}
+
+/// Accounts for the `close_sandwich_validator` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_to_close: u64)]
+pub struct CloseSandwichValidator<'info> {
+    #[account(
+        mut,
+        seeds = [SandwichValidators::SEED_PREFIX, multisig_authority.key().as_ref(), &epoch_to_close.to_le_bytes()],
+        bump
+    )]
+    pub sandwich_validators: AccountLoader<'info, SandwichValidators>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    #[account(mut)]
+    pub multisig_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
+    /// CHECK: Must be the active authority of `multisig_authority`; receives the rent.
+    #[account(mut)]
+    pub rent_recipient: UncheckedAccount<'info>,
+    /// Optional M-of-N signer set; member signatures are read from `remaining_accounts`.
+    #[account(seeds = [SignerSet::SEED, multisig_authority.key().as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
+
+
+/// Accounts for the `close_legacy_sandwich_validator` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_to_close: u16)]
+pub struct CloseLegacySandwichValidator<'info> {
+    /// CHECK: Legacy u16-seeded account; the address is enforced by the seeds constraint
+    /// and ownership is checked in the handler. It is closed without being deserialized.
+    #[account(
+        mut,
+        seeds = [SandwichValidators::LEGACY_SEED_PREFIX, multisig_authority.key().as_ref(), &epoch_to_close.to_le_bytes()],
+        bump
+    )]
+    pub sandwich_validators: UncheckedAccount<'info>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    #[account(mut)]
+    pub multisig_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
+    /// CHECK: Must be the active authority of `multisig_authority`; receives the rent.
+    #[account(mut)]
+    pub rent_recipient: UncheckedAccount<'info>,
+    /// Optional M-of-N signer set; member signatures are read from `remaining_accounts`.
+    #[account(seeds = [SignerSet::SEED, multisig_authority.key().as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
+
+/// Accounts for the `migrate_sandwich_validators` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64)]
+pub struct MigrateSandwichValidators<'info> {
+    /// CHECK: Either the u64-seeded or the legacy u16-seeded PDA for `epoch_arg`; the address,
+    /// ownership and header are checked in the handler.
+    #[account(mut)]
+    pub sandwich_validators: UncheckedAccount<'info>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    #[account(mut)]
+    pub multisig_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    #[account(mut)]
+    pub operator: Option<Signer<'info>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
+
+/// Accounts for the `seal_sandwich_validators` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64)]
+pub struct SealSandwichValidators<'info> {
+    /// CHECK: This account is validated as the namespace's PDA for `epoch_arg` by
+    /// `is_bitmap_pda`, and the header is checked in the instruction handler
+    #[account(
+        mut,
+        constraint = is_bitmap_pda(&multisig_authority.key(), &sandwich_validators.to_account_info(), epoch_arg, &ID) @ GatekeeperError::InvalidPda
+    )]
+    pub sandwich_validators: AccountInfo<'info>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    pub multisig_authority: UncheckedAccount<'info>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
+    /// Optional M-of-N signer set; member signatures are read from `remaining_accounts`.
+    #[account(seeds = [SignerSet::SEED, multisig_authority.key().as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
+
+/// Accounts for the `unseal_sandwich_validators` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64)]
+pub struct UnsealSandwichValidators<'info> {
+    /// CHECK: This account is validated as the namespace's PDA for `epoch_arg` by
+    /// `is_bitmap_pda`, and the header is checked in the instruction handler
+    #[account(
+        mut,
+        constraint = is_bitmap_pda(&multisig_authority.key(), &sandwich_validators.to_account_info(), epoch_arg, &ID) @ GatekeeperError::InvalidPda
+    )]
+    pub sandwich_validators: AccountInfo<'info>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    pub multisig_authority: UncheckedAccount<'info>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
+    /// Optional M-of-N signer set; member signatures are read from `remaining_accounts`.
+    #[account(seeds = [SignerSet::SEED, multisig_authority.key().as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
+
+/// Accounts for the `expand_sandwich_validators_bitmap` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64)]
+pub struct ExpandSandwichValidatorsBitmap<'info> {
+    /// CHECK: This account is validated as the namespace's PDA for `epoch_arg` by
+    /// `is_bitmap_pda`, with additional epoch validation in the instruction handler
+    #[account(
+        mut,
+        constraint = is_bitmap_pda(&multisig_authority.key(), &sandwich_validators.to_account_info(), epoch_arg, &ID) @ GatekeeperError::InvalidPda
+    )]
+    pub sandwich_validators: AccountInfo<'info>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    #[account(mut)]
+    pub multisig_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    #[account(mut)]
+    pub operator: Option<Signer<'info>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
+
+/// Accounts for the `append_data_sandwich_validators_bitmap` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64)]
+pub struct AppendDataSandwichValidatorsBitmap<'info> {
+    #[account(
+        mut,
+        constraint = is_bitmap_pda(&multisig_authority.key(), &sandwich_validators.to_account_info(), epoch_arg, &ID) @ GatekeeperError::InvalidPda
+    )]
+    pub sandwich_validators: AccountLoader<'info, SandwichValidators>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    #[account(mut)]
+    pub multisig_authority: UncheckedAccount<'info>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
+
+/// Accounts for the `write_bitmap_chunk` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64)]
+pub struct WriteBitmapChunk<'info> {
+    #[account(
+        mut,
+        constraint = is_bitmap_pda(&multisig_authority.key(), &sandwich_validators.to_account_info(), epoch_arg, &ID) @ GatekeeperError::InvalidPda
+    )]
+    pub sandwich_validators: AccountLoader<'info, SandwichValidators>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    #[account(mut)]
+    pub multisig_authority: UncheckedAccount<'info>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
 
+/// Accounts for the `write_bitmap_runs` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64)]
+pub struct WriteBitmapRuns<'info> {
+    #[account(
+        mut,
+        constraint = is_bitmap_pda(&multisig_authority.key(), &sandwich_validators.to_account_info(), epoch_arg, &ID) @ GatekeeperError::InvalidPda
+    )]
+    pub sandwich_validators: AccountLoader<'info, SandwichValidators>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    #[account(mut)]
+    pub multisig_authority: UncheckedAccount<'info>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
 
+/// Accounts for the `clear_data_sandwich_validators_bitmap` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64)]
+pub struct ClearDataSandwichValidatorsBitmap<'info> {
+    #[account(
+        mut,
+        constraint = is_bitmap_pda(&multisig_authority.key(), &sandwich_validators.to_account_info(), epoch_arg, &ID) @ GatekeeperError::InvalidPda
+    )]
+    pub sandwich_validators: AccountLoader<'info, SandwichValidators>,
+    /// Namespace key used in the PDA seeds; signs directly until the authority is rotated.
+    /// CHECK: The active authority's signature is verified in the handler via `authority_record`.
+    #[account(mut)]
+    pub multisig_authority: UncheckedAccount<'info>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
+    /// Optional M-of-N signer set; member signatures are read from `remaining_accounts`.
+    #[account(seeds = [SignerSet::SEED, multisig_authority.key().as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+    /// Optional role table letting `operator` sign with a delegated role.
+    #[account(seeds = [RoleTable::SEED, multisig_authority.key().as_ref()], bump = role_table.bump)]
+    pub role_table: Option<Account<'info, RoleTable>>,
+    /// Program-wide config; write instructions are rejected while it is frozen.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+}
 
-    /// Expand the sandwich validators bitmap account to full size without writing data.
-    /// This is used to expand the account beyond the initial 10KB limit.
-    pub fn expand_sandwich_validators_bitmap(
-        ctx: Context<ExpandSandwichValidatorsBitmap>,
-        epoch_arg: u16,
-    ) -> Result<()> {
-        instructions::expand_sandwich_validators_bitmap_handler(ctx, epoch_arg)
-    }
+/// Accounts for the `initialize_gatekeeper_config` instruction.
+#[derive(Accounts)]
+pub struct InitializeGatekeeperConfig<'info> {
+    #[account(
+        init,
+        payer = upgrade_authority,
+        space = 8 + GatekeeperConfig::INIT_SPACE,
+        seeds = [GatekeeperConfig::SEED],
+        bump
+    )]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ GatekeeperError::InvalidAuthority)]
+    pub program: Program<'info, crate::program::SaguaroGatekeeper>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(upgrade_authority.key()) @ GatekeeperError::InvalidAuthority)]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
 
-    /// Append data to the sandwich validators bitmap account.
-    /// This is a low-level utility for writing pre-computed bitmap data.
-    /// Most users should use `modify_sandwich_validators` instead.
-    pub fn append_data_sandwich_validators_bitmap(
-        ctx: Context<AppendDataSandwichValidatorsBitmap>,
-        epoch_arg: u16,
-        data: Vec<u8>,
-    ) -> Result<()> {
-        instructions::append_data_sandwich_validators_bitmap_handler(ctx, epoch_arg, data)
-    }
+/// Accounts for the `update_gatekeeper_config` instruction.
+#[derive(Accounts)]
+pub struct UpdateGatekeeperConfig<'info> {
+    #[account(
+        mut,
+        seeds = [GatekeeperConfig::SEED],
+        bump = gatekeeper_config.bump,
+        has_one = admin @ GatekeeperError::InvalidAuthority
+    )]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+    pub admin: Signer<'info>,
+}
 
-    /// Clear all data in the sandwich validators bitmap account.
-    /// This sets all slots in the bitmap to ungated (false).
-    pub fn clear_data_sandwich_validators_bitmap(
-        ctx: Context<ClearDataSandwichValidatorsBitmap>,
-        epoch_arg: u16,
-    ) -> Result<()> {
-        instructions::clear_data_sandwich_validators_bitmap_handler(ctx, epoch_arg)
-    }
+/// Accounts for the `set_override_mode` instruction.
+#[derive(Accounts)]
+pub struct SetOverrideMode<'info> {
+    #[account(
+        mut,
+        seeds = [GatekeeperConfig::SEED],
+        bump = gatekeeper_config.bump,
+        has_one = admin @ GatekeeperError::InvalidAuthority
+    )]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts for the `initialize_authority_record` instruction.
+#[derive(Accounts)]
+pub struct InitializeAuthorityRecord<'info> {
+    #[account(
+        init,
+        payer = multisig_authority,
+        space = 8 + AuthorityRecord::INIT_SPACE,
+        seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()],
+        bump
+    )]
+    pub authority_record: Account<'info, AuthorityRecord>,
+    #[account(mut)]
+    pub multisig_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
 
+/// Accounts for the `propose_authority` instruction.
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [AuthorityRecord::SEED, authority_record.namespace.as_ref()],
+        bump = authority_record.bump,
+        has_one = authority @ GatekeeperError::InvalidAuthority
+    )]
+    pub authority_record: Account<'info, AuthorityRecord>,
+    pub authority: Signer<'info>,
 }
 
+/// Accounts for the `accept_authority` instruction.
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [AuthorityRecord::SEED, authority_record.namespace.as_ref()],
+        bump = authority_record.bump
+    )]
+    pub authority_record: Account<'info, AuthorityRecord>,
+    pub pending_authority: Signer<'info>,
+}
 
-/// Account storing the validator slot assignments for a specific epoch using a bitmap.
-/// Uses zero-copy patterns for efficient access to 432,000 slots. Data is accessed manually to avoid heap allocation issues.
-#[account(zero_copy)]
-#[repr(C)]
-pub struct SandwichValidators {
-    /// The epoch number (u16) to which these slot assignments apply.
-    pub epoch: u16,          // 2 bytes
-    pub bump: u8,            // 1 byte
-    pub _padding: [u8; 1],   // 1 byte to align `bitmap_len` to 4-byte boundary
-    pub bitmap_len: u32,     // 4 bytes
-    // Total struct size: 8 bytes (2 + 1 + 1 + 4)
-    // With 8-byte discriminator, bitmap data begins at offset 16
+/// Accounts for the `cancel_authority_proposal` instruction.
+#[derive(Accounts)]
+pub struct CancelAuthorityProposal<'info> {
+    #[account(
+        mut,
+        seeds = [AuthorityRecord::SEED, authority_record.namespace.as_ref()],
+        bump = authority_record.bump,
+        has_one = authority @ GatekeeperError::InvalidAuthority
+    )]
+    pub authority_record: Account<'info, AuthorityRecord>,
+    pub authority: Signer<'info>,
 }
 
-impl SandwichValidators {
-    pub const SEED_PREFIX: &'static [u8] = b"sandwich_validators";
-    pub const DATA_OFFSET: usize = 16; // discriminator (8) + epoch (2) + bump (1) + padding (1) + bitmap_len (4)
+/// Accounts for the `recover_authority` instruction.
+#[derive(Accounts)]
+pub struct RecoverAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [AuthorityRecord::SEED, authority_record.namespace.as_ref()],
+        bump = authority_record.bump,
+        constraint = authority_record.guardian == Some(guardian.key()) @ GatekeeperError::InvalidAuthority
+    )]
+    pub authority_record: Account<'info, AuthorityRecord>,
+    pub guardian: Signer<'info>,
 }
 
-/// Accounts for the `set_sandwich_validators` instruction.
+/// Accounts for the `set_authority_guardian` instruction.
 #[derive(Accounts)]
-#[instruction(epoch_arg: u16)]
-pub struct SetSandwichValidators<'info> {
-    /// CHECK: This account is manually validated and initialized in the instruction handler
+pub struct SetAuthorityGuardian<'info> {
     #[account(
         mut,
-        seeds = [SandwichValidators::SEED_PREFIX, multisig_authority.key().as_ref(), &epoch_arg.to_le_bytes()],
+        seeds = [AuthorityRecord::SEED, authority_record.namespace.as_ref()],
+        bump = authority_record.bump,
+        has_one = authority @ GatekeeperError::InvalidAuthority
+    )]
+    pub authority_record: Account<'info, AuthorityRecord>,
+    pub authority: Signer<'info>,
+}
+
+/// Accounts for the `initialize_signer_set` instruction.
+#[derive(Accounts)]
+pub struct InitializeSignerSet<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SignerSet::INIT_SPACE,
+        seeds = [SignerSet::SEED, multisig_authority.key().as_ref()],
         bump
     )]
-    pub sandwich_validators: AccountInfo<'info>,
+    pub signer_set: Account<'info, SignerSet>,
+    /// CHECK: Namespace key; the active authority's signature is verified in the handler.
+    pub multisig_authority: UncheckedAccount<'info>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
     #[account(mut)]
-    pub multisig_authority: Signer<'info>,
+    pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-/// Accounts for the `validate_sandwich_validators` instruction.
+/// Accounts for the `update_signer_set` instruction.
 #[derive(Accounts)]
-pub struct ValidateSandwichValidators<'info> {
-    /// The PDA account to be validated.
-    /// CHECK: The address is manually validated in the instruction handler against the
-    /// multisig_authority and current epoch from the clock sysvar.
-    pub sandwich_validators: AccountInfo<'info>,
-    /// The multisig authority account used in PDA derivation.
-    /// Not a signer as validation is public.
-    /// CHECK: This is used for PDA derivation only and is not a signer.
-    pub multisig_authority: AccountInfo<'info>,
-    /// The Clock sysvar to get current epoch and slot.
-    pub clock: Sysvar<'info, Clock>,
+pub struct UpdateSignerSet<'info> {
+    #[account(
+        mut,
+        seeds = [SignerSet::SEED, multisig_authority.key().as_ref()],
+        bump = signer_set.bump
+    )]
+    pub signer_set: Account<'info, SignerSet>,
+    /// CHECK: Namespace key; the active authority's signature is verified in the handler.
+    pub multisig_authority: UncheckedAccount<'info>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
 }
 
-/// Accounts for the `modify_sandwich_validators` instruction.
+/// Accounts for the `initialize_role_table` instruction.
 #[derive(Accounts)]
-#[instruction(epoch_arg: u16, slots_to_gate: Vec<u64>, slots_to_ungate: Vec<u64>)]
-pub struct ModifySandwichValidators<'info> {
-    /// CHECK: This account is manually validated in the instruction handler
+pub struct InitializeRoleTable<'info> {
     #[account(
-        mut,
-        seeds = [SandwichValidators::SEED_PREFIX, multisig_authority.key().as_ref(), &epoch_arg.to_le_bytes()],
+        init,
+        payer = payer,
+        space = 8 + RoleTable::INIT_SPACE,
+        seeds = [RoleTable::SEED, multisig_authority.key().as_ref()],
         bump
     )]
-    pub sandwich_validators: AccountInfo<'info>,
-
+    pub role_table: Account<'info, RoleTable>,
+    /// CHECK: Namespace key; the active authority's signature is verified in the handler.
+    pub multisig_authority: UncheckedAccount<'info>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
     #[account(mut)]
-    pub multisig_authority: Signer<'info>,
+    pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+/// Accounts for the `update_role_table` instruction.
+#[derive(Accounts)]
+pub struct UpdateRoleTable<'info> {
+    #[account(
+        mut,
+        seeds = [RoleTable::SEED, multisig_authority.key().as_ref()],
+        bump = role_table.bump
+    )]
+    pub role_table: Account<'info, RoleTable>,
+    /// CHECK: Namespace key; the active authority's signature is verified in the handler.
+    pub multisig_authority: UncheckedAccount<'info>,
+    /// CHECK: AuthorityRecord PDA of `multisig_authority`; uninitialized until the authority is rotated.
+    #[account(seeds = [AuthorityRecord::SEED, multisig_authority.key().as_ref()], bump)]
+    pub authority_record: UncheckedAccount<'info>,
+    /// Active authority signing for a rotated `multisig_authority`.
+    pub operator: Option<Signer<'info>>,
+}
 
+/// Accounts for the `initialize_committee` instruction.
+#[derive(Accounts)]
+pub struct InitializeCommittee<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Committee::INIT_SPACE,
+        seeds = [Committee::SEED, namespace.key().as_ref()],
+        bump
+    )]
+    pub committee: Account<'info, Committee>,
+    pub namespace: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
 
-/// Accounts for the `close_sandwich_validator` instruction.
+/// Accounts for the `update_committee` instruction.
 #[derive(Accounts)]
-#[instruction(epoch_to_close: u16)]
-pub struct CloseSandwichValidator<'info> {
+pub struct UpdateCommittee<'info> {
     #[account(
         mut,
-        close = multisig_authority,
-        seeds = [SandwichValidators::SEED_PREFIX, multisig_authority.key().as_ref(), &epoch_to_close.to_le_bytes()],
+        seeds = [Committee::SEED, committee.namespace.as_ref()],
+        bump = committee.bump,
+        has_one = governance @ GatekeeperError::InvalidAuthority
+    )]
+    pub committee: Account<'info, Committee>,
+    pub governance: Signer<'info>,
+}
+
+/// Accounts for the `join_committee` instruction.
+#[derive(Accounts)]
+pub struct JoinCommittee<'info> {
+    #[account(
+        seeds = [Committee::SEED, committee.namespace.as_ref()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    /// CHECK: The member's namespace under `committee`; only its address is used.
+    #[account(seeds = [Committee::MEMBER_SEED, committee.key().as_ref(), member.key().as_ref()], bump)]
+    pub member_namespace: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = 8 + AuthorityRecord::INIT_SPACE,
+        seeds = [AuthorityRecord::SEED, member_namespace.key().as_ref()],
         bump
     )]
-    pub sandwich_validators: AccountLoader<'info, SandwichValidators>,
+    pub authority_record: Account<'info, AuthorityRecord>,
     #[account(mut)]
-    pub multisig_authority: Signer<'info>,
+    pub member: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-
-/// Accounts for the `expand_sandwich_validators_bitmap` instruction.
+/// Accounts for the `register_provider` instruction.
 #[derive(Accounts)]
-#[instruction(epoch_arg: u16)]
-pub struct ExpandSandwichValidatorsBitmap<'info> {
-    /// CHECK: This account is validated through PDA derivation with seeds constraint
-    /// and additional epoch validation in the instruction handler
+pub struct RegisterProvider<'info> {
     #[account(
-        mut,
-        seeds = [SandwichValidators::SEED_PREFIX, multisig_authority.key().as_ref(), &epoch_arg.to_le_bytes()],
+        init_if_needed,
+        payer = admin,
+        space = 8 + ProviderRegistry::INIT_SPACE,
+        seeds = [ProviderRegistry::SEED],
         bump
     )]
-    pub sandwich_validators: AccountInfo<'info>,
+    pub provider_registry: Account<'info, ProviderRegistry>,
+    #[account(
+        seeds = [GatekeeperConfig::SEED],
+        bump = gatekeeper_config.bump,
+        has_one = admin @ GatekeeperError::InvalidAuthority
+    )]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
     #[account(mut)]
-    pub multisig_authority: Signer<'info>,
+    pub admin: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-/// Accounts for the `append_data_sandwich_validators_bitmap` instruction.
+/// Accounts for the `update_provider` instruction.
 #[derive(Accounts)]
-#[instruction(epoch_arg: u16)]
-pub struct AppendDataSandwichValidatorsBitmap<'info> {
+pub struct UpdateProvider<'info> {
     #[account(
         mut,
-        seeds = [SandwichValidators::SEED_PREFIX, multisig_authority.key().as_ref(), &epoch_arg.to_le_bytes()],
+        seeds = [ProviderRegistry::SEED],
+        bump = provider_registry.bump
+    )]
+    pub provider_registry: Account<'info, ProviderRegistry>,
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+    /// Either the GatekeeperConfig admin or the provider's authority; checked in the handler.
+    pub signer: Signer<'info>,
+}
+
+/// Accounts for the `deprecate_provider` instruction.
+#[derive(Accounts)]
+pub struct DeprecateProvider<'info> {
+    #[account(
+        mut,
+        seeds = [ProviderRegistry::SEED],
+        bump = provider_registry.bump
+    )]
+    pub provider_registry: Account<'info, ProviderRegistry>,
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Account<'info, GatekeeperConfig>,
+    /// Either the GatekeeperConfig admin or the provider's authority; checked in the handler.
+    pub signer: Signer<'info>,
+}
+
+/// Accounts for the `set_integrator_overrides` instruction.
+#[derive(Accounts)]
+#[instruction(epoch_arg: u64)]
+pub struct SetIntegratorOverrides<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + IntegratorOverrides::INIT_SPACE,
+        seeds = [IntegratorOverrides::SEED, owner.key().as_ref(), &epoch_arg.to_le_bytes()],
         bump
     )]
-    pub sandwich_validators: AccountLoader<'info, SandwichValidators>,
+    pub integrator_overrides: Account<'info, IntegratorOverrides>,
+    pub owner: Signer<'info>,
     #[account(mut)]
-    pub multisig_authority: Signer<'info>,
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Optional program-wide config; only its epoch source is used.
+    #[account(seeds = [GatekeeperConfig::SEED], bump = gatekeeper_config.bump)]
+    pub gatekeeper_config: Option<Account<'info, GatekeeperConfig>>,
 }
 
-/// Accounts for the `clear_data_sandwich_validators_bitmap` instruction.
+/// Accounts for the `close_integrator_overrides` instruction.
 #[derive(Accounts)]
-#[instruction(epoch_arg: u16)]
-pub struct ClearDataSandwichValidatorsBitmap<'info> {
+#[instruction(epoch_arg: u64)]
+pub struct CloseIntegratorOverrides<'info> {
     #[account(
         mut,
-        seeds = [SandwichValidators::SEED_PREFIX, multisig_authority.key().as_ref(), &epoch_arg.to_le_bytes()],
+        close = owner,
+        seeds = [IntegratorOverrides::SEED, owner.key().as_ref(), &epoch_arg.to_le_bytes()],
+        bump = integrator_overrides.bump
+    )]
+    pub integrator_overrides: Account<'info, IntegratorOverrides>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Accounts for the `initialize_integrator_profile` instruction.
+#[derive(Accounts)]
+pub struct InitializeIntegratorProfile<'info> {
+    #[account(
+        init,
+        payer = upgrade_authority,
+        space = 8 + IntegratorProfile::INIT_SPACE,
+        seeds = [IntegratorProfile::SEED, integrator_program.key().as_ref()],
         bump
     )]
-    pub sandwich_validators: AccountLoader<'info, SandwichValidators>,
+    pub integrator_profile: Account<'info, IntegratorProfile>,
+    /// CHECK: The program the profile is created for; tied to `program_data` by its seeds.
+    pub integrator_program: UncheckedAccount<'info>,
     #[account(mut)]
-    pub multisig_authority: Signer<'info>,
+    pub upgrade_authority: Signer<'info>,
+    #[account(
+        seeds = [integrator_program.key().as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(upgrade_authority.key()) @ GatekeeperError::InvalidAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
 }
 
+/// Accounts for the `update_integrator_profile` instruction.
+#[derive(Accounts)]
+pub struct UpdateIntegratorProfile<'info> {
+    #[account(
+        mut,
+        seeds = [IntegratorProfile::SEED, integrator_profile.integrator.as_ref()],
+        bump = integrator_profile.bump,
+        has_one = admin @ GatekeeperError::InvalidAuthority
+    )]
+    pub integrator_profile: Account<'info, IntegratorProfile>,
+    pub admin: Signer<'info>,
+}
 
 /// Events emitted by the Saguaro Gatekeeper program for monitoring
 #[event]
 pub struct SandwichValidatorsSet {
     pub authority: Pubkey,
-    pub epoch: u16,
+    pub epoch: u64,
     pub slot_count: u16,
 }
 
 #[event]
 pub struct SandwichValidatorsUpdated {
     pub authority: Pubkey,
-    pub epoch: u16,
-    pub slots_added: u16,
-    pub slots_removed: u16,
-    pub total_slots: u16,
+    pub epoch: u64,
+    pub slots_added: u32,
+    pub slots_removed: u32,
+    /// Exact number of gated bits (slots, or leader windows) in the bitmap after the change.
+    pub total_slots: u32,
+}
+
+/// Emitted by `validate_shadow` where `validate_sandwich_validators` would have failed.
+#[event]
+pub struct SlotGatedShadow {
+    pub authority: Pubkey,
+    pub epoch: u64,
+    pub slot: u64,
 }
 
 #[event]
 pub struct SandwichValidatorsClosed {
     pub authority: Pubkey,
-    pub epoch: u16,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct SandwichValidatorsMigrated {
+    pub authority: Pubkey,
+    pub epoch: u64,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct SandwichValidatorsSealed {
+    pub authority: Pubkey,
+    pub epoch: u64,
+    pub bitmap_hash: [u8; 32],
+}
+
+#[event]
+pub struct SandwichValidatorsUnsealed {
+    pub authority: Pubkey,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub namespace: Pubkey,
+    pub proposed_by: Pubkey,
+    pub pending_authority: Pubkey,
+    pub unlock_timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityAccepted {
+    pub namespace: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityProposalCancelled {
+    pub namespace: Pubkey,
+    pub cancelled_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityGuardianUpdated {
+    pub namespace: Pubkey,
+    pub guardian: Option<Pubkey>,
+    pub recovery_delay_seconds: i64,
+}
+
+#[event]
+pub struct SignerSetUpdated {
+    pub namespace: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct OverrideModeChanged {
+    pub admin: Pubkey,
+    pub previous_mode: OverrideMode,
+    pub override_mode: OverrideMode,
+    pub slot: u64,
+}
+
+#[event]
+pub struct RoleTableUpdated {
+    pub namespace: Pubkey,
+    pub grants: Vec<RoleGrant>,
+}
+
+#[event]
+pub struct GatekeeperConfigUpdated {
+    pub admin: Pubkey,
+    pub mode: OperatingMode,
+    pub slots_per_epoch_source: SlotsPerEpochSource,
+    pub fail_policy: FailPolicy,
+    pub unsealed_policy: UnsealedPolicy,
+    pub layout_version: u8,
+}
+
+#[event]
+pub struct CommitteeUpdated {
+    pub committee: Pubkey,
+    pub governance: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct CommitteeMemberJoined {
+    pub committee: Pubkey,
+    pub member: Pubkey,
+    pub namespace: Pubkey,
+}
+
+#[event]
+pub struct ProviderUpdated {
+    pub authority: Pubkey,
+    pub name: String,
+    pub metadata_uri: String,
+    pub first_epoch: u64,
+    pub last_epoch: Option<u64>,
+    pub status: ProviderStatus,
+}
+
+#[event]
+pub struct IntegratorOverridesUpdated {
+    pub owner: Pubkey,
+    pub epoch: u64,
+    pub allow: Vec<SlotRange>,
+    pub deny: Vec<SlotRange>,
+}
+
+#[event]
+pub struct IntegratorOverridesClosed {
+    pub owner: Pubkey,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct IntegratorProfileUpdated {
+    pub integrator: Pubkey,
+    pub admin: Pubkey,
+    pub authorities: Vec<Pubkey>,
+    pub combination: SourceCombination,
+    pub fail_policy: FailPolicy,
+    pub exempt_ranges: Vec<SlotRange>,
 }
 
 
@@ -302,4 +2524,52 @@ pub enum GatekeeperError {
     MissingSandwichValidatorsAccount,
     #[msg("Invalid sandwich validators PDA provided.")]
     InvalidSandwichValidatorsPDA,
+    #[msg("The gatekeeper is frozen and slot bitmaps cannot be modified.")]
+    GatekeeperFrozen,
+    #[msg("There is no pending authority proposal.")]
+    NoPendingAuthority,
+    #[msg("The pending authority cannot accept until the recovery delay has elapsed.")]
+    RecoveryDelayNotElapsed,
+    #[msg("The recovery delay must not be negative.")]
+    InvalidRecoveryDelay,
+    #[msg("Signer set members must be unique, non-empty and within the size limit, with 1 <= threshold <= members.")]
+    InvalidSignerSet,
+    #[msg("Not enough signer set members signed the transaction.")]
+    SignerSetQuorumNotMet,
+    #[msg("Role grants must have unique keys, known non-empty roles and stay within the size limit.")]
+    InvalidRoleTable,
+    #[msg("The operator does not hold a role permitting this instruction.")]
+    MissingRole,
+    #[msg("The account layout version is not supported by this instruction; migrate the account first.")]
+    UnsupportedLayoutVersion,
+    #[msg("The SandwichValidators account is sealed and cannot be modified until it is unsealed.")]
+    AccountSealed,
+    #[msg("The bitmap does not match the expected hash.")]
+    BitmapHashMismatch,
+    #[msg("The bitmap must be expanded to the full epoch size before it can be sealed.")]
+    BitmapIncomplete,
+    #[msg("Compact slot data is malformed.")]
+    InvalidSlotEncoding,
+    #[msg("No sandwich validators bitmap is configured for the current epoch.")]
+    EpochNotConfigured,
+    #[msg("Profile authorities must be unique and non-empty, and exempt ranges well formed, within the size limits.")]
+    InvalidIntegratorProfile,
+    #[msg("The integrator profile does not belong to the program calling this instruction.")]
+    IntegratorProfileMismatch,
+    #[msg("Sources must be unique (authority, PDA) pairs within the size limit, and the quorum between 1 and their count.")]
+    InvalidValidationSources,
+    #[msg("Committee members must be unique and within the size limit, and the threshold between 1 and their count.")]
+    InvalidCommittee,
+    #[msg("Provider names must be 1-32 bytes, metadata URIs at most 128 bytes, and the last epoch not before the first.")]
+    InvalidProvider,
+    #[msg("A provider with this authority or name is already registered.")]
+    ProviderAlreadyRegistered,
+    #[msg("No provider with this authority is registered.")]
+    ProviderNotFound,
+    #[msg("The provider registry is full.")]
+    ProviderRegistryFull,
+    #[msg("Each bitmap bit must cover either one slot or one 4-slot leader window.")]
+    InvalidSlotsPerBit,
+    #[msg("The calling program cannot be identified: an integrator profile needs the instructions sysvar and a CPI made directly by the top-level instruction.")]
+    IntegratorProfileUnverifiable,
 }
\ No newline at end of file
@@ -0,0 +1,103 @@
+//! k-of-n evaluation of several gate lists for the current slot.
+//!
+//! `validate_sources`, integrator profiles and curator committees all ask how many of a
+//! set of authorities gate the current slot. Each locates the authorities' accounts in
+//! its own way, then hands them to [`evaluate`] in authority order.
+
+use anchor_lang::prelude::*;
+use crate::{GatekeeperConfig, GatekeeperError, UnsealedPolicy};
+use crate::bitmap::is_gated;
+use crate::layout::{bitmap_pda, is_bitmap_pda, Header};
+
+/// Which sources flag the current slot, and whether enough of them do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quorum {
+    /// Bit `i` is set when the `i`-th source flags the slot.
+    pub flagged_mask: u32,
+    pub flagged_count: u32,
+    /// Whether `flagged_count` reached the quorum.
+    pub gated: bool,
+}
+
+/// The account among `candidates` that is `authority`'s PDA for `epoch`, failing with
+/// `MissingSandwichValidatorsAccount` if there is none. Existing accounts are matched with
+/// their stored bump; the bump search runs only when none of them matches.
+pub fn find_source<'a, 'info>(
+    authority: &Pubkey,
+    candidates: &[&'a AccountInfo<'info>],
+    epoch: u64,
+    program_id: &Pubkey,
+) -> Result<&'a AccountInfo<'info>> {
+    if let Some(source) = candidates.iter()
+        .find(|candidate| !candidate.data_is_empty() && is_bitmap_pda(authority, candidate, epoch, program_id))
+    {
+        return Ok(source);
+    }
+    let expected_pda = bitmap_pda(authority, epoch, program_id);
+    candidates.iter()
+        .find(|candidate| candidate.key() == expected_pda)
+        .copied()
+        .ok_or_else(|| GatekeeperError::MissingSandwichValidatorsAccount.into())
+}
+
+/// Counts the `sources` that flag the current slot against a quorum. Each source must
+/// already be verified as its authority's PDA for the current epoch (see
+/// [`is_bitmap_pda`]).
+///
+/// # Behavior
+/// 0. The config's override applies first: `Paused` flags no source, `BlockAll` every one
+/// 1. A source flags the slot when its bitmap gates it, or when it has no usable bitmap
+///    (never created, or unsealed under `UnsealedPolicy::Ignore`) and `unconfigured_gated`
+/// 2. The slot is gated once at least `quorum` sources flag it
+pub fn evaluate(
+    sources: &[&AccountInfo],
+    quorum: usize,
+    config: &GatekeeperConfig,
+    clock: &Clock,
+    unconfigured_gated: bool,
+) -> Result<Quorum> {
+    let flagged_mask = match config.override_mode.forced_outcome() {
+        Some(outcome) => if outcome.is_err() { u32::MAX.checked_shr(32 - sources.len() as u32).unwrap_or(0) } else { 0 },
+        None => {
+            let slot_offset = config.slots_per_epoch_source.slot_offset(clock.epoch, clock.slot)?;
+            let mut mask = 0u32;
+            for (i, source) in sources.iter().enumerate() {
+                if source_flags_slot(source, slot_offset, config, unconfigured_gated)? {
+                    mask |= 1 << i;
+                }
+            }
+            mask
+        }
+    };
+
+    let flagged_count = flagged_mask.count_ones();
+    Ok(Quorum {
+        flagged_mask,
+        flagged_count,
+        gated: flagged_count as usize >= quorum,
+    })
+}
+
+/// Whether one verified source flags the slot `slot_offset` slots into the epoch.
+fn source_flags_slot(
+    source: &AccountInfo,
+    slot_offset: Option<u64>,
+    config: &GatekeeperConfig,
+    unconfigured_gated: bool,
+) -> Result<bool> {
+    if source.data_is_empty() {
+        return Ok(unconfigured_gated);
+    }
+    let data = source.try_borrow_data()?;
+    let header = Header::read(&data)?;
+
+    // Bitmaps still being uploaded can be skipped until they are sealed
+    if config.unsealed_policy == UnsealedPolicy::Ignore && !header.is_sealed() {
+        return Ok(unconfigured_gated);
+    }
+    // A slot outside the epoch's bounds cannot be looked up
+    Ok(match slot_offset {
+        Some(offset) => is_gated(&data[header.data_offset()..], header.bit_index(offset)),
+        None => unconfigured_gated,
+    })
+}
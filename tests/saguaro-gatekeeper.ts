@@ -4,6 +4,7 @@ import { SaguaroGatekeeper } from "../target/types/saguaro_gatekeeper";
 import { assert } from "chai";
 import {
   getSandwichValidatorsPda,
+  getLegacySandwichValidatorsPda,
   setSandwichValidators,
   modifySandwichValidators,
   validateSandwichValidators,
@@ -11,8 +12,77 @@ import {
   expandSandwichValidatorsBitmap,
   appendDataSandwichValidatorsBitmap,
   clearDataSandwichValidatorsBitmap,
+  getGatekeeperConfigPda,
+  initializeGatekeeperConfig,
+  updateGatekeeperConfig,
+  OperatingMode,
+  SlotsPerEpochSource,
+  FailPolicy,
+  OverrideMode,
+  setOverrideMode,
+  getAuthorityRecordPda,
+  initializeAuthorityRecord,
+  proposeAuthority,
+  acceptAuthority,
+  cancelAuthorityProposal,
+  recoverAuthority,
+  initializeSignerSet,
+  updateSignerSet,
+  initializeRoleTable,
+  Role,
   SLOTS_PER_EPOCH,
   TARGET_ACCOUNT_SIZE,
+  SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE,
+  SANDWICH_VALIDATORS_LAYOUT_VERSION,
+  UnsealedPolicy,
+  sealSandwichValidators,
+  unsealSandwichValidators,
+  computeBitmapHash,
+  getBitmapData,
+  getGatedCount,
+  modifySandwichValidatorsRanges,
+  modifySandwichValidatorsCompact,
+  encodeSlotOffsets,
+  writeBitmapChunk,
+  BitmapWriteMode,
+  encodeBitmapRuns,
+  writeBitmapRuns,
+  querySlots,
+  isQueriedSlotGated,
+  validateV2,
+  validateEpochBoundary,
+  validateStrict,
+  validateShadow,
+  getShadowEvents,
+  SourceCombination,
+  getIntegratorProfilePda,
+  initializeIntegratorProfile,
+  updateIntegratorProfile,
+  setIntegratorOverrides,
+  closeIntegratorOverrides,
+  validateWithOverrides,
+  validateSources,
+  SourceValidationResult,
+  getCommitteePda,
+  getCommitteeMemberNamespacePda,
+  initializeCommittee,
+  updateCommittee,
+  joinCommittee,
+  ProviderMetadata,
+  ProviderStatus,
+  registerProvider,
+  updateProvider,
+  deprecateProvider,
+  fetchProviders,
+  findProviderByName,
+  SLOTS_PER_LEADER_WINDOW,
+  getSlotsPerBit,
+  SANDWICH_VALIDATORS_FLAG_SEALED,
+  getEpochBounds,
+  getTargetAccountSize,
+  migrateSandwichValidators,
+  getBitmapDataOffset,
+  createBitmapForSlots,
   FULL_BITMAP_SIZE_BYTES,
   INITIAL_ACCOUNT_SIZE,
 } from "../ts/sdk";
@@ -41,7 +111,6 @@ describe("saguaro-gatekeeper", () => {
       return baseEpoch; // Use fixed epochs for localnet (predictable)
     } else {
       // For devnet/mainnet, use smaller range to avoid BN buffer issues
-      // Epoch is u16, so max value is 65535
       const timestamp = Date.now() % 1000; // Last 3 digits of timestamp
       const random = Math.floor(Math.random() * 100);
       return Math.min(50000 + timestamp + random + baseEpoch, 65000); // Keep under u16 max
@@ -186,6 +255,25 @@ describe("saguaro-gatekeeper", () => {
     } else {
       console.warn("Insufficient balance to fund unauthorized user");
     }
+
+    // Every write instruction requires the config, so create it before the first bitmap
+    if (isLocalnet) {
+      const { pda: configPda } = getGatekeeperConfigPda(program.programId);
+      if (!(await provider.connection.getAccountInfo(configPda))) {
+        await initializeGatekeeperConfig(program, {
+          upgradeAuthority: multisigAuthority.publicKey,
+          params: {
+            admin: multisigAuthority.publicKey,
+            mode: OperatingMode.Active,
+            slotsPerEpochSource: SlotsPerEpochSource.Fixed,
+            failPolicy: FailPolicy.Open,
+            unsealedPolicy: UnsealedPolicy.Enforce,
+          },
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+      }
+    }
   });
 
   it("should create the sandwich_validators PDA for a specific epoch", async () => {
@@ -231,7 +319,7 @@ describe("saguaro-gatekeeper", () => {
     const account = await program.account.sandwichValidators.fetch(pda);
     // Account has epoch, bump, and padding fields
     // The multisig authority is derived from PDA seeds, not stored in the account
-    assert.strictEqual(account.epoch, epochArg.toNumber());
+    assert.strictEqual(account.epoch.toNumber(), epochArg.toNumber());
 
     // Verify the PDA bump is correct
     assert.strictEqual(account.bump, bump);
@@ -374,7 +462,7 @@ describe("saguaro-gatekeeper", () => {
         program.programId
       );
       const account = await program.account.sandwichValidators.fetch(pda);
-      assert.strictEqual(account.epoch, actualEpoch, "Account should be created with correct epoch");
+      assert.strictEqual(account.epoch.toNumber(), actualEpoch, "Account should be created with correct epoch");
     }
   });
 
@@ -516,11 +604,11 @@ describe("saguaro-gatekeeper", () => {
     // since the bitmap is stored as raw bytes after the account header
     const accountInfo = await provider.connection.getAccountInfo(pda);
     const bitmapData = accountInfo.data;
-    const dataStart = 16; // Skip discriminator (8) + epoch (2) + bump (1) + padding (5)
+    const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE; // Skip discriminator + header
 
     // Check that the new slots are gated in the bitmap
     for (const slot of newSlots) {
-      const epochStart = (updatedAccount.epoch as number) * SLOTS_PER_EPOCH;
+      const epochStart = updatedAccount.epoch.toNumber() * SLOTS_PER_EPOCH;
       const slotOffset = slot.toNumber() - epochStart;
       const byteIndex = Math.floor(slotOffset / 8);
       const bitIndex = slotOffset % 8;
@@ -534,7 +622,7 @@ describe("saguaro-gatekeeper", () => {
 
     // Check that the old slots are no longer gated
     for (const slot of initialSlots) {
-      const epochStart = (updatedAccount.epoch as number) * SLOTS_PER_EPOCH;
+      const epochStart = updatedAccount.epoch.toNumber() * SLOTS_PER_EPOCH;
       const slotOffset = slot.toNumber() - epochStart;
       const byteIndex = Math.floor(slotOffset / 8);
       const bitIndex = slotOffset % 8;
@@ -798,7 +886,7 @@ describe("saguaro-gatekeeper", () => {
     const account = await program.account.sandwichValidators.fetch(pda);
 
     // Verify the account was created
-    assert.strictEqual(account.epoch, epochArg.toNumber());
+    assert.strictEqual(account.epoch.toNumber(), epochArg.toNumber());
     assert.strictEqual(
       account.bump,
       (
@@ -813,7 +901,7 @@ describe("saguaro-gatekeeper", () => {
     // Verify all bits are zero by checking the raw account data
     const accountInfo = await provider.connection.getAccountInfo(pda);
     const bitmapData = accountInfo.data;
-    const dataStart = 16; // Skip discriminator + epoch + bump + padding
+    const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE; // Skip discriminator + header
 
     // Check that the bitmap portion is all zeros
     const bitmapBytes = bitmapData.slice(dataStart);
@@ -862,7 +950,7 @@ describe("saguaro-gatekeeper", () => {
     // Get the raw bitmap data
     const accountInfo = await provider.connection.getAccountInfo(pda);
     const bitmapData = accountInfo.data;
-    const dataStart = 16; // Skip discriminator + epoch + bump + padding
+    const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE; // Skip discriminator + header
 
     // Verify that exactly 100 slots are gated
     let gatedSlotsCount = 0;
@@ -980,7 +1068,7 @@ describe("saguaro-gatekeeper", () => {
     // Get the raw bitmap data
     const accountInfo = await provider.connection.getAccountInfo(pda);
     const bitmapData = accountInfo.data;
-    const dataStart = 16; // Skip discriminator + epoch + bump + padding
+    const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE; // Skip discriminator + header
 
     // Verify that exactly 100 slots are gated (the larger slots)
     let gatedSlotsCount = 0;
@@ -1062,7 +1150,7 @@ describe("saguaro-gatekeeper", () => {
     // Get the raw bitmap data
     const accountInfo = await provider.connection.getAccountInfo(pda);
     const bitmapData = accountInfo.data;
-    const dataStart = 16; // Skip discriminator + epoch + bump + padding
+    const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE; // Skip discriminator + header
 
     // Verify that exactly 2 slots are gated (slots 200 and 201)
     const slot200 = 200;
@@ -1122,7 +1210,7 @@ describe("saguaro-gatekeeper", () => {
     );
 
     const accountTest = await program.account.sandwichValidators.fetch(pdaTest);
-    assert.strictEqual(accountTest.epoch, 107);
+    assert.strictEqual(accountTest.epoch.toNumber(), 107);
 
     // Test with maximum epoch value (u16::MAX = 65535)
     const epochMax = new BN(65535);
@@ -1146,7 +1234,7 @@ describe("saguaro-gatekeeper", () => {
     );
 
     const accountMax = await program.account.sandwichValidators.fetch(pdaMax);
-    assert.strictEqual(accountMax.epoch, 65535);
+    assert.strictEqual(accountMax.epoch.toNumber(), 65535);
   });
 
   // === Add Slots Functionality Tests ===
@@ -1202,7 +1290,7 @@ describe("saguaro-gatekeeper", () => {
     // Get the raw bitmap data
     const accountInfo = await provider.connection.getAccountInfo(pda);
     const bitmapData = accountInfo.data;
-    const dataStart = 16; // Skip discriminator + epoch + bump + padding
+    const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE; // Skip discriminator + header
 
     // Verify that exactly 5 slots are gated
     let gatedSlotsCount = 0;
@@ -1220,7 +1308,7 @@ describe("saguaro-gatekeeper", () => {
     // Verify all expected slots are gated in the bitmap
     const allExpectedSlots = [...initialSlots, ...additionalSlots];
     for (const slot of allExpectedSlots) {
-      const epochStart = (account.epoch as number) * SLOTS_PER_EPOCH;
+      const epochStart = account.epoch.toNumber() * SLOTS_PER_EPOCH;
       const slotOffset = slot.toNumber() - epochStart;
       const byteIndex = Math.floor(slotOffset / 8);
       const bitIndex = slotOffset % 8;
@@ -1398,7 +1486,7 @@ describe("saguaro-gatekeeper", () => {
     // Get the raw bitmap data
     const accountInfo = await provider.connection.getAccountInfo(pda);
     const bitmapData = accountInfo.data;
-    const dataStart = 16; // Skip discriminator + epoch + bump + padding
+    const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE; // Skip discriminator + header
 
     // Count gated slots to verify 200 total
     let gatedSlotsCount = 0;
@@ -1509,7 +1597,7 @@ describe("saguaro-gatekeeper", () => {
     // Get the raw bitmap data
     const accountInfo = await provider.connection.getAccountInfo(pda);
     const bitmapData = accountInfo.data;
-    const dataStart = 16; // Skip discriminator + epoch + bump + padding
+    const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE; // Skip discriminator + header
 
     // Count gated slots to verify 3 remaining
     let gatedSlotsCount = 0;
@@ -1526,7 +1614,7 @@ describe("saguaro-gatekeeper", () => {
 
     // Verify removed slot is not gated
     const removedSlot = 300 * SLOTS_PER_EPOCH + 1;
-    const epochStart = (account.epoch as number) * SLOTS_PER_EPOCH;
+    const epochStart = account.epoch.toNumber() * SLOTS_PER_EPOCH;
     const slotOffset = removedSlot - epochStart;
     const byteIndex = Math.floor(slotOffset / 8);
     const bitIndex = slotOffset % 8;
@@ -1621,7 +1709,7 @@ describe("saguaro-gatekeeper", () => {
     // Get the raw bitmap data
     const accountInfo = await provider.connection.getAccountInfo(pda);
     const bitmapData = accountInfo.data;
-    const dataStart = 16; // Skip discriminator + epoch + bump + padding
+    const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE; // Skip discriminator + header
 
     // Count gated slots to verify 4 total (3 - 1 + 2)
     let gatedSlotsCount = 0;
@@ -1641,7 +1729,7 @@ describe("saguaro-gatekeeper", () => {
     );
 
     // Verify specific slots using bitmap checks
-    const epochStart = (account.epoch as number) * SLOTS_PER_EPOCH;
+    const epochStart = account.epoch.toNumber() * SLOTS_PER_EPOCH;
 
     // Verify removed slot is not gated
     const removedSlot = 301 * SLOTS_PER_EPOCH + 1;
@@ -1726,7 +1814,7 @@ describe("saguaro-gatekeeper", () => {
     // Get the raw bitmap data
     const accountInfo = await provider.connection.getAccountInfo(pda);
     const bitmapData = accountInfo.data;
-    const dataStart = 16; // Skip discriminator + epoch + bump + padding
+    const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE; // Skip discriminator + header
 
     // Count gated slots to verify 1 remaining
     let gatedSlotsCount = 0;
@@ -1746,7 +1834,7 @@ describe("saguaro-gatekeeper", () => {
     );
 
     // Verify specific slots using bitmap checks
-    const epochStart = (account.epoch as number) * SLOTS_PER_EPOCH;
+    const epochStart = account.epoch.toNumber() * SLOTS_PER_EPOCH;
 
     // Verify removed slot is not gated
     const removedSlot = 302 * SLOTS_PER_EPOCH;
@@ -2015,7 +2103,7 @@ describe("saguaro-gatekeeper", () => {
 
       // Verify specific bits are set correctly according to our test pattern
       const accountData = accountInfo.data;
-      const dataStart = 16; // Skip discriminator (8) + epoch (2) + bump (1) + padding (5)
+      const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE; // Skip discriminator + header
 
       // Check slots 0 and 7 (first byte: 0b10000001)
       const firstByte = accountData[dataStart];
@@ -2057,7 +2145,7 @@ describe("saguaro-gatekeeper", () => {
 
       const accountInfo = await provider.connection.getAccountInfo(pda);
       const accountData = accountInfo.data;
-      const dataStart = 16;
+      const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE;
 
       // Check first few bytes match our pattern
       for (let i = 0; i < 10; i++) {
@@ -2104,7 +2192,7 @@ describe("saguaro-gatekeeper", () => {
 
       const accountInfo = await provider.connection.getAccountInfo(pda);
       const accountData = accountInfo.data;
-      const dataStart = 16;
+      const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE;
 
       // Check that bitmap data is all zeros
       for (let i = 0; i < 16; i++) {
@@ -2176,7 +2264,7 @@ describe("saguaro-gatekeeper", () => {
 
       const accountInfo = await provider.connection.getAccountInfo(pda);
       const accountData = accountInfo.data;
-      const dataStart = 16;
+      const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE;
 
       // Check first slot (which we wrote)
       assert.equal(accountData[dataStart] & 1, 1, "First slot should be set");
@@ -2231,7 +2319,7 @@ describe("saguaro-gatekeeper", () => {
 
       // Verify our test pattern was written correctly
       const accountData = accountInfo.data;
-      const dataStart = 16;
+      const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE;
 
       // Check first byte (should be 0xFF)
       assert.equal(
@@ -2359,7 +2447,7 @@ describe("saguaro-gatekeeper", () => {
       console.log("\nStep 3: Verifying written data...");
       accountInfo = await provider.connection.getAccountInfo(pda);
       const accountData = accountInfo.data;
-      const dataStart = 16; // Skip metadata
+      const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE; // Skip metadata
 
       // Verify the test pattern we actually wrote (only the first pattern)
       const readData = accountData.slice(
@@ -2374,7 +2462,7 @@ describe("saguaro-gatekeeper", () => {
       console.log(`  ✓ Verified pattern: ${firstPattern.description}`);
 
       // Calculate and display capacity statistics
-      const bitmapSizeBytes = accountInfo.data.length - 16;
+      const bitmapSizeBytes = accountInfo.data.length - SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE;
       const totalSlots = bitmapSizeBytes * 8;
 
       console.log("\n=== Storage Capacity Verified ===");
@@ -2409,8 +2497,8 @@ describe("saguaro-gatekeeper", () => {
       const epochArg = new BN(500);
       const epochStart = epochArg.toNumber() * SLOTS_PER_EPOCH;
       
-      // Calculate initial bitmap capacity (10KB - 16 bytes header = 10,224 bytes = 81,792 slots)
-      const initialBitmapCapacity = (INITIAL_ACCOUNT_SIZE - 16) * 8;
+      // Calculate initial bitmap capacity (10KB - 64 bytes header = 10,176 bytes = 81,408 slots)
+      const initialBitmapCapacity = (INITIAL_ACCOUNT_SIZE - SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE) * 8;
       const maxInitialSlot = epochStart + initialBitmapCapacity - 1;
 
       await safeCreateAccount(epochArg.toNumber());
@@ -2519,7 +2607,7 @@ describe("saguaro-gatekeeper", () => {
 
       const accountInfo = await provider.connection.getAccountInfo(pda);
       const bitmapData = accountInfo.data;
-      const dataStart = 16;
+      const dataStart = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE;
 
       // Check both slots are set
       assert.equal(bitmapData[dataStart] & 1, 1, "First slot should be set");
@@ -2605,4 +2693,2683 @@ describe("saguaro-gatekeeper", () => {
       }
     });
   });
+
+  describe("Gatekeeper Config", () => {
+    const { pda: configPda } = getGatekeeperConfigPda(program.programId);
+
+    const defaultParams = () => ({
+      admin: multisigAuthority.publicKey,
+      mode: OperatingMode.Active,
+      slotsPerEpochSource: SlotsPerEpochSource.Fixed,
+      failPolicy: FailPolicy.Open,
+      unsealedPolicy: UnsealedPolicy.Enforce,
+    });
+
+    it("should initialize the config with the upgrade authority", async () => {
+      if (skipOnDevnet("requires program upgrade authority")) return;
+
+      const existing = await provider.connection.getAccountInfo(configPda);
+      if (!existing) {
+        await initializeGatekeeperConfig(program, {
+          upgradeAuthority: multisigAuthority.publicKey,
+          params: defaultParams(),
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+      }
+
+      const config = await program.account.gatekeeperConfig.fetch(configPda);
+      assert.isTrue(config.admin.equals(multisigAuthority.publicKey));
+      assert.deepEqual(config.mode, OperatingMode.Active);
+      assert.equal(config.layoutVersion, 1);
+    });
+
+    it("should NOT allow a non-admin to update the config", async () => {
+      if (skipOnDevnet("requires program upgrade authority")) return;
+
+      try {
+        await updateGatekeeperConfig(program, {
+          admin: unauthorizedUser.publicKey,
+          params: { ...defaultParams(), admin: unauthorizedUser.publicKey },
+        })
+          .signers([unauthorizedUser])
+          .rpc();
+        assert.fail("Non-admin update should have failed");
+      } catch (error) {
+        assert.isTrue(
+          error.toString().includes("InvalidAuthority"),
+          `Expected 'InvalidAuthority' error, but got: ${error}`
+        );
+      }
+    });
+
+    it("should reject modifications while frozen", async () => {
+      if (skipOnDevnet("requires program upgrade authority")) return;
+
+      const epoch = 600;
+      await safeCreateAccount(epoch);
+
+      await updateGatekeeperConfig(program, {
+        admin: multisigAuthority.publicKey,
+        params: { ...defaultParams(), mode: OperatingMode.Frozen },
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      try {
+        await modifySandwichValidators(program, {
+          epoch,
+          slotsToGate: [new BN(epoch * SLOTS_PER_EPOCH)],
+          multisigAuthority: multisigAuthority.publicKey,
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+        assert.fail("Modification should have failed while frozen");
+      } catch (error) {
+        assert.isTrue(
+          error.toString().includes("GatekeeperFrozen"),
+          `Expected 'GatekeeperFrozen' error, but got: ${error}`
+        );
+      } finally {
+        await updateGatekeeperConfig(program, {
+          admin: multisigAuthority.publicKey,
+          params: defaultParams(),
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+      }
+    });
+
+    it("should reject raw writes and closes while frozen", async () => {
+      if (skipOnDevnet("requires program upgrade authority")) return;
+
+      const epoch = 601;
+      await safeCreateAccount(epoch);
+
+      await updateGatekeeperConfig(program, {
+        admin: multisigAuthority.publicKey,
+        params: { ...defaultParams(), mode: OperatingMode.Frozen },
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const attempts = [
+        writeBitmapChunk(program, {
+          epoch,
+          multisigAuthority: multisigAuthority.publicKey,
+          offset: 0,
+          data: Buffer.from([0xff]),
+        }),
+        closeSandwichValidator(program, {
+          epoch,
+          multisigAuthority: multisigAuthority.publicKey,
+        }),
+      ];
+
+      try {
+        for (const attempt of attempts) {
+          try {
+            await attempt.signers([multisigAuthority.payer]).rpc();
+            assert.fail("Write should have failed while frozen");
+          } catch (error) {
+            assert.isTrue(
+              error.toString().includes("GatekeeperFrozen"),
+              `Expected 'GatekeeperFrozen' error, but got: ${error}`
+            );
+          }
+        }
+      } finally {
+        await updateGatekeeperConfig(program, {
+          admin: multisigAuthority.publicKey,
+          params: defaultParams(),
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+      }
+    });
+
+    it("should fail closed for an unconfigured epoch when configured to", async () => {
+      if (skipOnDevnet("requires program upgrade authority")) return;
+
+      const freshAuthority = anchor.web3.Keypair.generate();
+
+      await updateGatekeeperConfig(program, {
+        admin: multisigAuthority.publicKey,
+        params: { ...defaultParams(), failPolicy: FailPolicy.Closed },
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      try {
+        const tx = await validateSandwichValidators(program, {
+          multisigAuthority: freshAuthority.publicKey,
+        });
+        await tx.rpc();
+        assert.fail("Validation should fail closed without a bitmap");
+      } catch (error) {
+        assert.isTrue(
+          error.toString().includes("SlotIsGated"),
+          `Expected 'SlotIsGated' error, but got: ${error}`
+        );
+      } finally {
+        await updateGatekeeperConfig(program, {
+          admin: multisigAuthority.publicKey,
+          params: defaultParams(),
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+      }
+    });
+  });
+
+  describe("Override Mode", () => {
+    const { pda: configPda } = getGatekeeperConfigPda(program.programId);
+
+    const setMode = (overrideMode) =>
+      setOverrideMode(program, {
+        admin: multisigAuthority.publicKey,
+        overrideMode,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+    it("should NOT allow a non-admin to switch the override", async () => {
+      if (skipOnDevnet("requires program upgrade authority")) return;
+
+      try {
+        await setOverrideMode(program, {
+          admin: unauthorizedUser.publicKey,
+          overrideMode: OverrideMode.Paused,
+        })
+          .signers([unauthorizedUser])
+          .rpc();
+        assert.fail("Non-admin override switch should have failed");
+      } catch (error) {
+        assert.isTrue(
+          error.toString().includes("InvalidAuthority"),
+          `Expected 'InvalidAuthority' error, but got: ${error}`
+        );
+      }
+    });
+
+    it("should gate every validation under BlockAll", async () => {
+      if (skipOnDevnet("requires program upgrade authority")) return;
+
+      const freshAuthority = anchor.web3.Keypair.generate();
+      await setMode(OverrideMode.BlockAll);
+
+      try {
+        const tx = await validateSandwichValidators(program, {
+          multisigAuthority: freshAuthority.publicKey,
+        });
+        await tx.rpc();
+        assert.fail("Validation should fail under BlockAll");
+      } catch (error) {
+        assert.isTrue(
+          error.toString().includes("SlotIsGated"),
+          `Expected 'SlotIsGated' error, but got: ${error}`
+        );
+      } finally {
+        await setMode(OverrideMode.Normal);
+      }
+    });
+
+    it("should keep accepting the baseline 3-account CPI layout", async () => {
+      if (skipOnDevnet("requires program upgrade authority")) return;
+
+      // Integrators built before the config existed pass only the PDA, the authority and
+      // the Clock sysvar; they keep the default behaviour, and the override applies once
+      // the config is appended
+      const freshAuthority = anchor.web3.Keypair.generate();
+      const ix = await (
+        await validateSandwichValidators(program, {
+          multisigAuthority: freshAuthority.publicKey,
+        })
+      ).instruction();
+      const baselineIx = new web3.TransactionInstruction({
+        programId: ix.programId,
+        keys: ix.keys.slice(0, 3),
+        data: ix.data,
+      });
+      const withConfigIx = new web3.TransactionInstruction({
+        programId: ix.programId,
+        keys: ix.keys.slice(0, 4),
+        data: ix.data,
+      });
+      assert.isTrue(baselineIx.keys[2].pubkey.equals(web3.SYSVAR_CLOCK_PUBKEY));
+      assert.isTrue(withConfigIx.keys[3].pubkey.equals(configPda));
+
+      await setMode(OverrideMode.BlockAll);
+      try {
+        await provider.sendAndConfirm(new web3.Transaction().add(baselineIx), []);
+
+        try {
+          await provider.sendAndConfirm(new web3.Transaction().add(withConfigIx), []);
+          assert.fail("Validation with the config should fail under BlockAll");
+        } catch (error) {
+          assert.isTrue(
+            `${error} ${error.logs ?? ""}`.includes("SlotIsGated"),
+            `Expected 'SlotIsGated' error, but got: ${error}`
+          );
+        }
+      } finally {
+        await setMode(OverrideMode.Normal);
+      }
+    });
+
+    it("should pass validation under Paused even when failing closed", async () => {
+      if (skipOnDevnet("requires program upgrade authority")) return;
+
+      const freshAuthority = anchor.web3.Keypair.generate();
+      await updateGatekeeperConfig(program, {
+        admin: multisigAuthority.publicKey,
+        params: {
+          admin: multisigAuthority.publicKey,
+          mode: OperatingMode.Active,
+          slotsPerEpochSource: SlotsPerEpochSource.Fixed,
+          failPolicy: FailPolicy.Closed,
+          unsealedPolicy: UnsealedPolicy.Enforce,
+        },
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+      await setMode(OverrideMode.Paused);
+
+      try {
+        const tx = await validateSandwichValidators(program, {
+          multisigAuthority: freshAuthority.publicKey,
+        });
+        await tx.rpc();
+
+        const config = await program.account.gatekeeperConfig.fetch(configPda);
+        assert.deepEqual(config.overrideMode, OverrideMode.Paused);
+        assert.deepEqual(config.failPolicy, FailPolicy.Closed);
+      } finally {
+        await setMode(OverrideMode.Normal);
+        await updateGatekeeperConfig(program, {
+          admin: multisigAuthority.publicKey,
+          params: {
+            admin: multisigAuthority.publicKey,
+            mode: OperatingMode.Active,
+            slotsPerEpochSource: SlotsPerEpochSource.Fixed,
+            failPolicy: FailPolicy.Open,
+            unsealedPolicy: UnsealedPolicy.Enforce,
+          },
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+      }
+    });
+  });
+
+  describe("Authority Rotation", () => {
+    const namespace = anchor.web3.Keypair.generate();
+    const newAuthority = anchor.web3.Keypair.generate();
+    const guardian = anchor.web3.Keypair.generate();
+    const epoch = 610;
+
+    before(async () => {
+      if (isDevnet || isMainnet) return;
+
+      const fundTx = new web3.Transaction();
+      for (const key of [namespace.publicKey, newAuthority.publicKey, guardian.publicKey]) {
+        fundTx.add(
+          web3.SystemProgram.transfer({
+            fromPubkey: multisigAuthority.publicKey,
+            toPubkey: key,
+            lamports: web3.LAMPORTS_PER_SOL,
+          })
+        );
+      }
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+
+      await setSandwichValidators(program, {
+        epoch,
+        multisigAuthority: namespace.publicKey,
+      })
+        .signers([namespace])
+        .rpc();
+
+      await initializeAuthorityRecord(program, {
+        multisigAuthority: namespace.publicKey,
+        guardian: guardian.publicKey,
+        recoveryDelaySeconds: 3600,
+      })
+        .signers([namespace])
+        .rpc();
+    });
+
+    it("should rotate the active authority in two steps", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      await proposeAuthority(program, {
+        multisigAuthority: namespace.publicKey,
+        authority: namespace.publicKey,
+        newAuthority: newAuthority.publicKey,
+      })
+        .signers([namespace])
+        .rpc();
+
+      await acceptAuthority(program, {
+        multisigAuthority: namespace.publicKey,
+        pendingAuthority: newAuthority.publicKey,
+      })
+        .signers([newAuthority])
+        .rpc();
+
+      const { pda } = getAuthorityRecordPda(namespace.publicKey, program.programId);
+      const record = await program.account.authorityRecord.fetch(pda);
+      assert.isTrue(record.authority.equals(newAuthority.publicKey));
+      assert.isNull(record.pendingAuthority);
+    });
+
+    it("should let the new authority write under the unchanged PDA", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      const slot = new BN(epoch * SLOTS_PER_EPOCH + 7);
+      await modifySandwichValidators(program, {
+        epoch,
+        slotsToGate: [slot],
+        multisigAuthority: namespace.publicKey,
+        operator: newAuthority.publicKey,
+      })
+        .signers([newAuthority])
+        .rpc();
+
+      const { pda } = getSandwichValidatorsPda(namespace.publicKey, new BN(epoch), program.programId);
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      assert.equal(accountInfo.data[SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE] & (1 << 7), 1 << 7);
+    });
+
+    it("should reject writes from the rotated-out key", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      try {
+        await modifySandwichValidators(program, {
+          epoch,
+          slotsToUngate: [new BN(epoch * SLOTS_PER_EPOCH + 7)],
+          multisigAuthority: namespace.publicKey,
+        })
+          .signers([namespace])
+          .rpc();
+        assert.fail("Rotated-out key should not be able to write");
+      } catch (error) {
+        assert.isTrue(
+          error.toString().includes("InvalidAuthority"),
+          `Expected 'InvalidAuthority' error, but got: ${error}`
+        );
+      }
+    });
+
+    it("should delay guardian recovery and allow the authority to cancel it", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      const replacement = anchor.web3.Keypair.generate();
+      await recoverAuthority(program, {
+        multisigAuthority: namespace.publicKey,
+        guardian: guardian.publicKey,
+        newAuthority: replacement.publicKey,
+      })
+        .signers([guardian])
+        .rpc();
+
+      try {
+        await acceptAuthority(program, {
+          multisigAuthority: namespace.publicKey,
+          pendingAuthority: replacement.publicKey,
+        })
+          .signers([replacement])
+          .rpc();
+        assert.fail("Recovery should not be acceptable before the delay");
+      } catch (error) {
+        assert.isTrue(
+          error.toString().includes("RecoveryDelayNotElapsed"),
+          `Expected 'RecoveryDelayNotElapsed' error, but got: ${error}`
+        );
+      }
+
+      await cancelAuthorityProposal(program, {
+        multisigAuthority: namespace.publicKey,
+        authority: newAuthority.publicKey,
+      })
+        .signers([newAuthority])
+        .rpc();
+
+      const { pda } = getAuthorityRecordPda(namespace.publicKey, program.programId);
+      const record = await program.account.authorityRecord.fetch(pda);
+      assert.isNull(record.pendingAuthority);
+    });
+  });
+
+  describe("Signer Set", () => {
+    const namespace = anchor.web3.Keypair.generate();
+    const members = [
+      anchor.web3.Keypair.generate(),
+      anchor.web3.Keypair.generate(),
+      anchor.web3.Keypair.generate(),
+    ];
+    const epoch = 620;
+
+    before(async () => {
+      if (isDevnet || isMainnet) return;
+
+      const fundTx = new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: multisigAuthority.publicKey,
+          toPubkey: namespace.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL,
+        })
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+
+      await setSandwichValidators(program, {
+        epoch,
+        multisigAuthority: namespace.publicKey,
+      })
+        .signers([namespace])
+        .rpc();
+
+      await initializeSignerSet(program, {
+        multisigAuthority: namespace.publicKey,
+        payer: namespace.publicKey,
+        members: members.map((m) => m.publicKey),
+        threshold: 2,
+      })
+        .signers([namespace])
+        .rpc();
+    });
+
+    it("should allow 2-of-3 members to modify slots", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      await modifySandwichValidators(program, {
+        epoch,
+        slotsToGate: [new BN(epoch * SLOTS_PER_EPOCH + 3)],
+        multisigAuthority: namespace.publicKey,
+        signerSet: true,
+        signerSetMembers: [members[0].publicKey, members[2].publicKey],
+      })
+        .signers([members[0], members[2]])
+        .rpc();
+
+      const { pda } = getSandwichValidatorsPda(namespace.publicKey, new BN(epoch), program.programId);
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      assert.equal(accountInfo.data[SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE] & (1 << 3), 1 << 3);
+    });
+
+    it("should reject a single member signature", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      try {
+        await clearDataSandwichValidatorsBitmap(program, {
+          epoch,
+          multisigAuthority: namespace.publicKey,
+          signerSet: true,
+          signerSetMembers: [members[1].publicKey],
+        })
+          .signers([members[1]])
+          .rpc();
+        assert.fail("A single member should not meet the threshold");
+      } catch (error) {
+        assert.isTrue(
+          error.toString().includes("SignerSetQuorumNotMet"),
+          `Expected 'SignerSetQuorumNotMet' error, but got: ${error}`
+        );
+      }
+    });
+
+    it("should reject an invalid threshold", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      try {
+        await updateSignerSet(program, {
+          multisigAuthority: namespace.publicKey,
+          members: members.map((m) => m.publicKey),
+          threshold: 4,
+        })
+          .signers([namespace])
+          .rpc();
+        assert.fail("Threshold above member count should be rejected");
+      } catch (error) {
+        assert.isTrue(
+          error.toString().includes("InvalidSignerSet"),
+          `Expected 'InvalidSignerSet' error, but got: ${error}`
+        );
+      }
+    });
+
+    it("should allow 2-of-3 members to seal and unseal the bitmap", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      const { pda } = getSandwichValidatorsPda(namespace.publicKey, new BN(epoch), program.programId);
+      const { slotsInEpoch } = await getEpochBounds(provider.connection, epoch);
+      while ((await provider.connection.getAccountInfo(pda)).data.length < getTargetAccountSize(slotsInEpoch)) {
+        await expandSandwichValidatorsBitmap(program, { epoch, multisigAuthority: namespace.publicKey })
+          .signers([namespace])
+          .rpc();
+      }
+
+      const quorum = {
+        multisigAuthority: namespace.publicKey,
+        signerSet: true,
+        signerSetMembers: [members[0].publicKey, members[1].publicKey],
+      };
+      const expectedHash = computeBitmapHash(getBitmapData((await provider.connection.getAccountInfo(pda)).data));
+      await sealSandwichValidators(program, { epoch, expectedHash, ...quorum })
+        .signers([members[0], members[1]])
+        .rpc();
+      let account = await program.account.sandwichValidators.fetch(pda);
+      assert.equal(account.flags & SANDWICH_VALIDATORS_FLAG_SEALED, SANDWICH_VALIDATORS_FLAG_SEALED);
+
+      await unsealSandwichValidators(program, { epoch, ...quorum })
+        .signers([members[0], members[1]])
+        .rpc();
+      account = await program.account.sandwichValidators.fetch(pda);
+      assert.equal(account.flags & SANDWICH_VALIDATORS_FLAG_SEALED, 0);
+    });
+  });
+
+  describe("Role Permissions", () => {
+    const namespace = anchor.web3.Keypair.generate();
+    const writer = anchor.web3.Keypair.generate();
+    const ungater = anchor.web3.Keypair.generate();
+    const closer = anchor.web3.Keypair.generate();
+    const epoch = 630;
+    const slot = new BN(epoch * SLOTS_PER_EPOCH + 5);
+
+    before(async () => {
+      if (isDevnet || isMainnet) return;
+
+      const fundTx = new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: multisigAuthority.publicKey,
+          toPubkey: namespace.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL,
+        })
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+
+      await setSandwichValidators(program, {
+        epoch,
+        multisigAuthority: namespace.publicKey,
+      })
+        .signers([namespace])
+        .rpc();
+
+      await initializeRoleTable(program, {
+        multisigAuthority: namespace.publicKey,
+        payer: namespace.publicKey,
+        grants: [
+          { key: writer.publicKey, roles: Role.Writer },
+          { key: ungater.publicKey, roles: Role.Ungater },
+          { key: closer.publicKey, roles: Role.Closer },
+        ],
+      })
+        .signers([namespace])
+        .rpc();
+    });
+
+    it("should let a Writer gate slots", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      await modifySandwichValidators(program, {
+        epoch,
+        slotsToGate: [slot],
+        multisigAuthority: namespace.publicKey,
+        operator: writer.publicKey,
+        roleTable: true,
+      })
+        .signers([writer])
+        .rpc();
+    });
+
+    it("should not let an Ungater gate slots", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      try {
+        await modifySandwichValidators(program, {
+          epoch,
+          slotsToGate: [slot.addn(1)],
+          multisigAuthority: namespace.publicKey,
+          operator: ungater.publicKey,
+          roleTable: true,
+        })
+          .signers([ungater])
+          .rpc();
+        assert.fail("An Ungater should not be able to gate slots");
+      } catch (error) {
+        assert.isTrue(
+          error.toString().includes("MissingRole"),
+          `Expected 'MissingRole' error, but got: ${error}`
+        );
+      }
+    });
+
+    it("should let an Ungater remove gates", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      await modifySandwichValidators(program, {
+        epoch,
+        slotsToUngate: [slot],
+        multisigAuthority: namespace.publicKey,
+        operator: ungater.publicKey,
+        roleTable: true,
+      })
+        .signers([ungater])
+        .rpc();
+
+      const { pda } = getSandwichValidatorsPda(namespace.publicKey, new BN(epoch), program.programId);
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      assert.equal(accountInfo.data[SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE] & (1 << 5), 0);
+    });
+
+    it("should not let a Writer clear the bitmap", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      try {
+        await clearDataSandwichValidatorsBitmap(program, {
+          epoch,
+          multisigAuthority: namespace.publicKey,
+          operator: writer.publicKey,
+          roleTable: true,
+        })
+          .signers([writer])
+          .rpc();
+        assert.fail("A Writer should not be able to clear the bitmap");
+      } catch (error) {
+        assert.isTrue(
+          error.toString().includes("MissingRole"),
+          `Expected 'MissingRole' error, but got: ${error}`
+        );
+      }
+    });
+
+    it("should refund a Closer's close to the namespace, not the Closer", async () => {
+      if (skipOnDevnet("requires funded throwaway keys")) return;
+
+      const currentEpoch = (await provider.connection.getEpochInfo()).epoch;
+      if (currentEpoch === 0) {
+        console.log("Skipping test for closing past epoch PDA: current epoch is 0.");
+        return;
+      }
+      const pastEpoch = currentEpoch - 1;
+      await setSandwichValidators(program, { epoch: pastEpoch, multisigAuthority: namespace.publicKey })
+        .signers([namespace])
+        .rpc();
+
+      try {
+        await closeSandwichValidator(program, {
+          epoch: pastEpoch,
+          multisigAuthority: namespace.publicKey,
+          operator: closer.publicKey,
+          roleTable: true,
+          rentRecipient: closer.publicKey,
+        })
+          .signers([closer])
+          .rpc();
+        assert.fail("The Closer should not be able to collect the rent");
+      } catch (error) {
+        assert.isTrue(
+          error.toString().includes("InvalidAuthority"),
+          `Expected 'InvalidAuthority' error, but got: ${error}`
+        );
+      }
+
+      const { pda } = getSandwichValidatorsPda(namespace.publicKey, new BN(pastEpoch), program.programId);
+      const rent = await provider.connection.getBalance(pda);
+      const namespaceBefore = await provider.connection.getBalance(namespace.publicKey);
+
+      await closeSandwichValidator(program, {
+        epoch: pastEpoch,
+        multisigAuthority: namespace.publicKey,
+        operator: closer.publicKey,
+        roleTable: true,
+      })
+        .signers([closer])
+        .rpc();
+
+      assert.equal(await provider.connection.getBalance(namespace.publicKey), namespaceBefore + rent);
+      assert.equal(await provider.connection.getBalance(closer.publicKey), 0);
+    });
+  });
+
+  describe("Epoch Schedule", () => {
+    it("should size bitmaps from the epoch length", () => {
+      assert.equal(getTargetAccountSize(SLOTS_PER_EPOCH), TARGET_ACCOUNT_SIZE);
+      assert.equal(getTargetAccountSize(8192), SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE + 1024);
+      assert.equal(getTargetAccountSize(33), SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE + 5);
+
+      const bitmap = createBitmapForSlots([100, 131], 3, { firstSlot: 100, slotsInEpoch: 32 });
+      assert.equal(bitmap.length, 4);
+      assert.equal(bitmap[0], 1);
+      assert.equal(bitmap[3], 1 << 7);
+    });
+
+    it("should gate slots at offsets derived from the cluster's EpochSchedule", async () => {
+      if (skipOnDevnet("creates test accounts")) return;
+
+      const epoch = await safeCreateAccount(650);
+      const { firstSlot } = await getEpochBounds(provider.connection, epoch);
+
+      await modifySandwichValidators(program, {
+        epoch,
+        slotsToGate: [new BN(firstSlot + 9)],
+        multisigAuthority: multisigAuthority.publicKey,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const { pda } = getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(epoch), program.programId);
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      assert.equal(accountInfo.data[SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE + 1] & (1 << 1), 1 << 1);
+    });
+  });
+
+  describe("u64 Epochs", () => {
+    it("should derive distinct v2 and legacy PDAs", () => {
+      const v2 = getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(816), program.programId).pda;
+      const legacy = getLegacySandwichValidatorsPda(multisigAuthority.publicKey, 816, program.programId).pda;
+      assert.isFalse(v2.equals(legacy));
+      assert.throws(() => getLegacySandwichValidatorsPda(multisigAuthority.publicKey, 65536, program.programId));
+    });
+
+    it("should create and modify an account for an epoch beyond u16::MAX", async () => {
+      if (skipOnDevnet("creates test accounts")) return;
+
+      const epoch = 70_000;
+      await setSandwichValidators(program, {
+        epoch,
+        multisigAuthority: multisigAuthority.publicKey,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      await modifySandwichValidators(program, {
+        epoch,
+        slotsToGate: [new BN(epoch * SLOTS_PER_EPOCH + 2)],
+        multisigAuthority: multisigAuthority.publicKey,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const { pda } = getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(epoch), program.programId);
+      const account = await program.account.sandwichValidators.fetch(pda);
+      assert.strictEqual(account.epoch.toNumber(), epoch);
+      assert.strictEqual(account.layoutVersion, SANDWICH_VALIDATORS_LAYOUT_VERSION);
+
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      assert.equal(accountInfo.data[SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE] & (1 << 2), 1 << 2);
+
+      // The wrapped u16 epoch must not alias this account
+      const wrapped = getLegacySandwichValidatorsPda(multisigAuthority.publicKey, epoch % 65536, program.programId).pda;
+      assert.isFalse(wrapped.equals(pda));
+    });
+  });
+
+  describe("Layout Migration", () => {
+    const epoch = 660;
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      await setSandwichValidators(program, {
+        epoch,
+        multisigAuthority: multisigAuthority.publicKey,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+    });
+
+    it("should leave an account on the current layout unchanged", async () => {
+      const { pda } = getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(epoch), program.programId);
+      const before = await provider.connection.getAccountInfo(pda);
+      assert.equal(getBitmapDataOffset(before.data), SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE);
+
+      await migrateSandwichValidators(program, {
+        epoch,
+        multisigAuthority: multisigAuthority.publicKey,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const after = await provider.connection.getAccountInfo(pda);
+      assert.isTrue(after.data.equals(before.data));
+    });
+
+    it("should reject an epoch that does not match the account address", async () => {
+      const { pda } = getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(epoch), program.programId);
+      try {
+        await program.methods
+          .migrateSandwichValidators(new BN(epoch + 1))
+          .accountsStrict({
+            sandwichValidators: pda,
+            multisigAuthority: multisigAuthority.publicKey,
+            authorityRecord: getAuthorityRecordPda(multisigAuthority.publicKey, program.programId).pda,
+            operator: null,
+            roleTable: null,
+            systemProgram: web3.SystemProgram.programId,
+            gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+          })
+          .signers([multisigAuthority.payer])
+          .rpc();
+        assert.fail("Should have rejected the mismatched epoch");
+      } catch (error) {
+        assert.isTrue(error.toString().includes("InvalidPda"));
+      }
+    });
+
+    it("should report a missing legacy account", async () => {
+      try {
+        await migrateSandwichValidators(program, {
+          epoch: epoch + 1,
+          multisigAuthority: multisigAuthority.publicKey,
+          legacy: true,
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+        assert.fail("Should have rejected a legacy address with no account");
+      } catch (error) {
+        assert.isTrue(error.toString().includes("InvalidPda"));
+      }
+    });
+
+    it("should reject writes at a legacy address that holds no migrated account", async () => {
+      // Writers accept the legacy address only for an existing account flagged by migration
+      try {
+        await modifySandwichValidators(program, {
+          epoch,
+          slotsToGate: [new BN(epoch * SLOTS_PER_EPOCH)],
+          multisigAuthority: multisigAuthority.publicKey,
+          legacy: true,
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+        assert.fail("Should have rejected the legacy address");
+      } catch (error) {
+        assert.isTrue(error.toString().includes("InvalidPda"));
+      }
+    });
+  });
+
+  describe("Seal Lifecycle", () => {
+    const namespace = anchor.web3.Keypair.generate();
+    const epoch = 670;
+    const { pda } = getSandwichValidatorsPda(namespace.publicKey, new BN(epoch), program.programId);
+
+    const expectError = async (promise: Promise<unknown>, name: string) => {
+      try {
+        await promise;
+        assert.fail(`Expected '${name}' error`);
+      } catch (error) {
+        assert.isTrue(error.toString().includes(name), `Expected '${name}' error, but got: ${error}`);
+      }
+    };
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      const fundTx = new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: multisigAuthority.publicKey,
+          toPubkey: namespace.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL,
+        })
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+
+      await setSandwichValidators(program, { epoch, multisigAuthority: namespace.publicKey })
+        .signers([namespace])
+        .rpc();
+    });
+
+    it("should not seal a partially expanded bitmap", async () => {
+      const { slotsInEpoch } = await getEpochBounds(provider.connection, epoch);
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      if (accountInfo.data.length >= getTargetAccountSize(slotsInEpoch)) return;
+
+      await expectError(
+        sealSandwichValidators(program, {
+          epoch,
+          expectedHash: computeBitmapHash(getBitmapData(accountInfo.data)),
+          multisigAuthority: namespace.publicKey,
+        })
+          .signers([namespace])
+          .rpc(),
+        "BitmapIncomplete"
+      );
+    });
+
+    it("should reject a hash that does not match the bitmap", async () => {
+      const { slotsInEpoch } = await getEpochBounds(provider.connection, epoch);
+      const targetSize = getTargetAccountSize(slotsInEpoch);
+      while ((await provider.connection.getAccountInfo(pda)).data.length < targetSize) {
+        await expandSandwichValidatorsBitmap(program, { epoch, multisigAuthority: namespace.publicKey })
+          .signers([namespace])
+          .rpc();
+      }
+      await modifySandwichValidators(program, {
+        epoch,
+        slotsToGate: [new BN(epoch * SLOTS_PER_EPOCH + 3)],
+        multisigAuthority: namespace.publicKey,
+      })
+        .signers([namespace])
+        .rpc();
+
+      // Hash of the artifact without the gated slot
+      const artifact = Buffer.alloc(targetSize - SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE);
+      await expectError(
+        sealSandwichValidators(program, {
+          epoch,
+          expectedHash: computeBitmapHash(artifact),
+          multisigAuthority: namespace.publicKey,
+        })
+          .signers([namespace])
+          .rpc(),
+        "BitmapHashMismatch"
+      );
+    });
+
+    it("should seal a bitmap matching the off-chain artifact", async () => {
+      const { slotsInEpoch } = await getEpochBounds(provider.connection, epoch);
+      const artifact = Buffer.alloc(getTargetAccountSize(slotsInEpoch) - SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE);
+      artifact[0] = 1 << 3;
+      const expectedHash = computeBitmapHash(artifact);
+
+      await sealSandwichValidators(program, { epoch, expectedHash, multisigAuthority: namespace.publicKey })
+        .signers([namespace])
+        .rpc();
+
+      const account = await program.account.sandwichValidators.fetch(pda);
+      assert.equal(account.flags & SANDWICH_VALIDATORS_FLAG_SEALED, SANDWICH_VALIDATORS_FLAG_SEALED);
+      assert.isTrue(Buffer.from(account.bitmapHash).equals(expectedHash));
+
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      assert.isTrue(computeBitmapHash(getBitmapData(accountInfo.data)).equals(expectedHash));
+    });
+
+    it("should reject writes while sealed", async () => {
+      await expectError(
+        modifySandwichValidators(program, {
+          epoch,
+          slotsToGate: [new BN(epoch * SLOTS_PER_EPOCH + 4)],
+          multisigAuthority: namespace.publicKey,
+        })
+          .signers([namespace])
+          .rpc(),
+        "AccountSealed"
+      );
+      await expectError(
+        clearDataSandwichValidatorsBitmap(program, { epoch, multisigAuthority: namespace.publicKey })
+          .signers([namespace])
+          .rpc(),
+        "AccountSealed"
+      );
+    });
+
+    it("should accept writes again after unsealing", async () => {
+      await unsealSandwichValidators(program, { epoch, multisigAuthority: namespace.publicKey })
+        .signers([namespace])
+        .rpc();
+
+      const account = await program.account.sandwichValidators.fetch(pda);
+      assert.equal(account.flags & SANDWICH_VALIDATORS_FLAG_SEALED, 0);
+      assert.isTrue(Buffer.from(account.bitmapHash).equals(Buffer.alloc(32)));
+
+      await modifySandwichValidators(program, {
+        epoch,
+        slotsToGate: [new BN(epoch * SLOTS_PER_EPOCH + 4)],
+        multisigAuthority: namespace.publicKey,
+      })
+        .signers([namespace])
+        .rpc();
+    });
+
+    it("should treat an unsealed bitmap as unconfigured when the config ignores it", async () => {
+      if (skipOnDevnet("requires program upgrade authority")) return;
+
+      const { pda: configPda } = getGatekeeperConfigPda(program.programId);
+      const currentEpoch = (await provider.connection.getEpochInfo()).epoch;
+      await setSandwichValidators(program, { epoch: currentEpoch, multisigAuthority: namespace.publicKey })
+        .signers([namespace])
+        .rpc();
+
+      const updateParams = (unsealedPolicy) =>
+        updateGatekeeperConfig(program, {
+          admin: multisigAuthority.publicKey,
+          params: {
+            admin: multisigAuthority.publicKey,
+            mode: OperatingMode.Active,
+            slotsPerEpochSource: SlotsPerEpochSource.Fixed,
+            failPolicy: FailPolicy.Closed,
+            unsealedPolicy,
+          },
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+
+      try {
+        // The empty bitmap passes while it is enforced...
+        await updateParams(UnsealedPolicy.Enforce);
+        await (
+          await validateSandwichValidators(program, {
+            multisigAuthority: namespace.publicKey,
+          })
+        ).rpc();
+
+        // ...but falls through to the closed fail policy once unsealed bitmaps are ignored
+        await updateParams(UnsealedPolicy.Ignore);
+        await expectError(
+          (
+            await validateSandwichValidators(program, {
+              multisigAuthority: namespace.publicKey,
+            })
+          ).rpc(),
+          "SlotIsGated"
+        );
+      } finally {
+        await updateGatekeeperConfig(program, {
+          admin: multisigAuthority.publicKey,
+          params: {
+            admin: multisigAuthority.publicKey,
+            mode: OperatingMode.Active,
+            slotsPerEpochSource: SlotsPerEpochSource.Fixed,
+            failPolicy: FailPolicy.Open,
+            unsealedPolicy: UnsealedPolicy.Enforce,
+          },
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+      }
+    });
+  });
+
+  describe("Gated Count", () => {
+    const epoch = 680;
+    const epochStart = epoch * SLOTS_PER_EPOCH;
+    const { pda } = getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(epoch), program.programId);
+
+    const gatedCount = async () => {
+      const account = await program.account.sandwichValidators.fetch(pda);
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      assert.equal(getGatedCount(accountInfo.data), account.gatedCount);
+      return account.gatedCount;
+    };
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      await setSandwichValidators(program, { epoch, multisigAuthority: multisigAuthority.publicKey })
+        .signers([multisigAuthority.payer])
+        .rpc();
+    });
+
+    it("should track slots gated and ungated by modify", async () => {
+      await modifySandwichValidators(program, {
+        epoch,
+        slotsToGate: [0, 1, 2].map((offset) => new BN(epochStart + offset)),
+        multisigAuthority: multisigAuthority.publicKey,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+      assert.equal(await gatedCount(), 3);
+
+      // Ungating a slot that is not gated does not change the count
+      await modifySandwichValidators(program, {
+        epoch,
+        slotsToUngate: [new BN(epochStart + 2), new BN(epochStart + 7)],
+        multisigAuthority: multisigAuthority.publicKey,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+      assert.equal(await gatedCount(), 2);
+    });
+
+    it("should recount the bytes overwritten by append", async () => {
+      // Byte 0 holds slots 0 and 1; writing 0x0c ungates them and gates slots 2 and 3
+      await appendDataSandwichValidatorsBitmap(program, {
+        epoch,
+        multisigAuthority: multisigAuthority.publicKey,
+        data: Buffer.from([0x0c, 0xff]),
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+      assert.equal(await gatedCount(), 2 + 8); // Two slots in byte 0, eight in byte 1
+    });
+
+    it("should reset the count when the bitmap is cleared", async () => {
+      await clearDataSandwichValidatorsBitmap(program, { epoch, multisigAuthority: multisigAuthority.publicKey })
+        .signers([multisigAuthority.payer])
+        .rpc();
+      assert.equal(await gatedCount(), 0);
+    });
+
+    it("should count only the in-epoch bits of an appended tail byte", async () => {
+      const tailEpoch = epoch + 1;
+      const tailPda = getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(tailEpoch), program.programId).pda;
+      await setSandwichValidators(program, {
+        epoch: tailEpoch,
+        multisigAuthority: multisigAuthority.publicKey,
+        slotsPerBit: SLOTS_PER_LEADER_WINDOW,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const { slotsInEpoch } = await getEpochBounds(provider.connection, tailEpoch);
+      const targetSize = getTargetAccountSize(slotsInEpoch, SLOTS_PER_LEADER_WINDOW);
+      while ((await provider.connection.getAccountInfo(tailPda)).data.length < targetSize) {
+        await expandSandwichValidatorsBitmap(program, { epoch: tailEpoch, multisigAuthority: multisigAuthority.publicKey })
+          .signers([multisigAuthority.payer])
+          .rpc();
+      }
+
+      // Move the append cursor to the last byte of the epoch, then append a full byte there
+      const bitsInEpoch = Math.ceil(slotsInEpoch / SLOTS_PER_LEADER_WINDOW);
+      const lastByte = Math.ceil(bitsInEpoch / 8) - 1;
+      await writeBitmapChunk(program, {
+        epoch: tailEpoch,
+        multisigAuthority: multisigAuthority.publicKey,
+        offset: lastByte - 1,
+        data: Buffer.from([0]),
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+      await appendDataSandwichValidatorsBitmap(program, {
+        epoch: tailEpoch,
+        multisigAuthority: multisigAuthority.publicKey,
+        data: Buffer.from([0xff]),
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const inEpochBits = Math.min(8, bitsInEpoch - lastByte * 8);
+      const accountInfo = await provider.connection.getAccountInfo(tailPda);
+      const account = await program.account.sandwichValidators.fetch(tailPda);
+      assert.equal(getBitmapData(accountInfo.data)[lastByte], 0xff >> (8 - inEpochBits));
+      assert.equal(getGatedCount(accountInfo.data), inEpochBits);
+      assert.equal(account.gatedCount, inEpochBits);
+    });
+  });
+
+  describe("Slot Ranges", () => {
+    const epoch = 690;
+    const epochStart = epoch * SLOTS_PER_EPOCH;
+    const { pda } = getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(epoch), program.programId);
+
+    const range = (offset: number, len: number) => ({ start: new BN(epochStart + offset), len });
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      await setSandwichValidators(program, { epoch, multisigAuthority: multisigAuthority.publicKey })
+        .signers([multisigAuthority.payer])
+        .rpc();
+    });
+
+    it("should gate a range spanning partial and whole bytes", async () => {
+      await modifySandwichValidatorsRanges(program, {
+        epoch,
+        rangesToGate: [range(5, 20)], // Slots 5..25: bits 5-7 of byte 0, bytes 1-2, bit 0 of byte 3
+        multisigAuthority: multisigAuthority.publicKey,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      const bitmap = getBitmapData(accountInfo.data);
+      assert.deepEqual([...bitmap.subarray(0, 4)], [0xe0, 0xff, 0xff, 0x01]);
+      assert.equal(getGatedCount(accountInfo.data), 20);
+    });
+
+    it("should count only slots whose state changes", async () => {
+      await modifySandwichValidatorsRanges(program, {
+        epoch,
+        rangesToGate: [range(0, 8)], // Slots 5..7 are already gated
+        rangesToUngate: [range(16, 4)],
+        multisigAuthority: multisigAuthority.publicKey,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      assert.equal(getBitmapData(accountInfo.data)[2], 0xf0);
+      assert.equal(getGatedCount(accountInfo.data), 20 + 5 - 4);
+    });
+
+    it("should reject overlapping ranges", async () => {
+      try {
+        await modifySandwichValidatorsRanges(program, {
+          epoch,
+          rangesToGate: [range(100, 10), range(109, 5)],
+          multisigAuthority: multisigAuthority.publicKey,
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+        assert.fail("Expected DuplicateSlots error");
+      } catch (error) {
+        assert.isTrue(error.toString().includes("DuplicateSlots"));
+      }
+
+      try {
+        await modifySandwichValidatorsRanges(program, {
+          epoch,
+          rangesToGate: [range(100, 10)],
+          rangesToUngate: [range(105, 1)],
+          multisigAuthority: multisigAuthority.publicKey,
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+        assert.fail("Expected OverlapSlots error");
+      } catch (error) {
+        assert.isTrue(error.toString().includes("OverlapSlots"));
+      }
+    });
+
+    it("should reject empty and out-of-range ranges", async () => {
+      for (const bad of [range(0, 0), range(-1, 2), range(SLOTS_PER_EPOCH - 1, 2)]) {
+        try {
+          await modifySandwichValidatorsRanges(program, {
+            epoch,
+            rangesToGate: [bad],
+            multisigAuthority: multisigAuthority.publicKey,
+          })
+            .signers([multisigAuthority.payer])
+            .rpc();
+          assert.fail("Expected SlotOutOfRange error");
+        } catch (error) {
+          assert.isTrue(error.toString().includes("SlotOutOfRange"));
+        }
+      }
+    });
+  });
+
+  describe("Compact Slot Encoding", () => {
+    const epoch = 700;
+    const epochStart = epoch * SLOTS_PER_EPOCH;
+    const { pda } = getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(epoch), program.programId);
+
+    const modifyRaw = (gateOffsets: Buffer, ungateOffsets: Buffer = Buffer.alloc(0)) =>
+      program.methods
+        .modifySandwichValidatorsCompact(new BN(epoch), gateOffsets, ungateOffsets)
+        .accountsStrict({
+          sandwichValidators: pda,
+          multisigAuthority: multisigAuthority.publicKey,
+          authorityRecord: getAuthorityRecordPda(multisigAuthority.publicKey, program.programId).pda,
+          operator: null,
+          roleTable: null,
+          signerSet: null,
+          gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+        })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      await setSandwichValidators(program, { epoch, multisigAuthority: multisigAuthority.publicKey })
+        .signers([multisigAuthority.payer])
+        .rpc();
+    });
+
+    it("should encode slots as LEB128 gaps from the epoch start", () => {
+      const encoded = encodeSlotOffsets([epochStart + 200, epochStart + 3, epochStart + 4], epochStart);
+      // 3, then gap 1, then gap 196 (0xc4 0x01)
+      assert.deepEqual([...encoded], [3, 1, 0xc4, 0x01]);
+    });
+
+    it("should gate several times more slots than modify in one transaction", async () => {
+      // 100 leader windows of 4 consecutive slots each, far beyond MAX_SLOTS_PER_TRANSACTION
+      const slots = [];
+      for (let window = 0; window < 100; window++) {
+        for (let i = 0; i < 4; i++) slots.push(new BN(epochStart + window * 40 + i));
+      }
+      await modifySandwichValidatorsCompact(program, {
+        epoch,
+        epochStart,
+        slotsToGate: slots,
+        multisigAuthority: multisigAuthority.publicKey,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      assert.equal(getGatedCount(accountInfo.data), 400);
+      assert.equal(getBitmapData(accountInfo.data)[5], 0x0f); // Window 1 starts at slot 40
+    });
+
+    it("should ungate and gate in the same transaction", async () => {
+      await modifySandwichValidatorsCompact(program, {
+        epoch,
+        epochStart,
+        slotsToGate: [epochStart + 4],
+        slotsToUngate: [epochStart, epochStart + 1],
+        multisigAuthority: multisigAuthority.publicKey,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      assert.equal(getBitmapData(accountInfo.data)[0], 0x1c);
+      assert.equal(getGatedCount(accountInfo.data), 400 + 1 - 2);
+    });
+
+    it("should reject zero gaps, malformed varints and overlaps", async () => {
+      const cases: [Buffer, Buffer, string][] = [
+        [Buffer.from([50, 0]), Buffer.alloc(0), "DuplicateSlots"],
+        [Buffer.from([0x80, 0x00]), Buffer.alloc(0), "InvalidSlotEncoding"],
+        [Buffer.from([0x80]), Buffer.alloc(0), "InvalidSlotEncoding"],
+        [Buffer.from([50]), Buffer.from([49, 1]), "OverlapSlots"],
+      ];
+      for (const [gate, ungate, expected] of cases) {
+        try {
+          await modifyRaw(gate, ungate);
+          assert.fail(`Expected ${expected} error`);
+        } catch (error) {
+          assert.isTrue(error.toString().includes(expected), error.toString());
+        }
+      }
+    });
+
+    it("should reject offsets beyond the bitmap capacity", async () => {
+      try {
+        await modifySandwichValidatorsCompact(program, {
+          epoch,
+          epochStart,
+          slotsToGate: [epochStart + SLOTS_PER_EPOCH - 1],
+          multisigAuthority: multisigAuthority.publicKey,
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+        assert.fail("Expected SlotOutOfRange error");
+      } catch (error) {
+        assert.isTrue(error.toString().includes("SlotOutOfRange"));
+      }
+    });
+  });
+
+  describe("Bitmap Chunk Writes", () => {
+    const epoch = 710;
+    const { pda } = getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(epoch), program.programId);
+
+    const readBitmap = async () => {
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      return { bitmap: getBitmapData(accountInfo.data), gatedCount: getGatedCount(accountInfo.data) };
+    };
+
+    const write = (offset: number, data: number[], mode = BitmapWriteMode.Overwrite) =>
+      writeBitmapChunk(program, {
+        epoch,
+        multisigAuthority: multisigAuthority.publicKey,
+        offset,
+        data: Buffer.from(data),
+        mode,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      await setSandwichValidators(program, { epoch, multisigAuthority: multisigAuthority.publicKey })
+        .signers([multisigAuthority.payer])
+        .rpc();
+    });
+
+    it("should overwrite a chunk in the middle of the bitmap idempotently", async () => {
+      await write(100, [0xff, 0x0f]);
+      await write(100, [0xff, 0x0f]); // A retried transaction changes nothing
+
+      const { bitmap, gatedCount } = await readBitmap();
+      assert.deepEqual([...bitmap.subarray(99, 103)], [0, 0xff, 0x0f, 0]);
+      assert.equal(gatedCount, 12);
+
+      const account = await program.account.sandwichValidators.fetch(pda);
+      assert.equal(account.bitmapLen, 102); // The append cursor follows the highest write
+    });
+
+    it("should add gates with Or and remove them with AndNot", async () => {
+      await write(101, [0xf0], BitmapWriteMode.Or);
+      await write(100, [0x01], BitmapWriteMode.AndNot);
+
+      const { bitmap, gatedCount } = await readBitmap();
+      assert.deepEqual([...bitmap.subarray(100, 102)], [0xfe, 0xff]);
+      assert.equal(gatedCount, 15);
+    });
+
+    it("should reject a chunk past the allocated bitmap", async () => {
+      const { bitmap } = await readBitmap();
+      try {
+        await write(bitmap.length - 1, [0x01, 0x01]);
+        assert.fail("Expected SlotOutOfRange error");
+      } catch (error) {
+        assert.isTrue(error.toString().includes("SlotOutOfRange"));
+      }
+    });
+  });
+
+  describe("Run-Length Bitmap Upload", () => {
+    const epoch = 720;
+    const { pda } = getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(epoch), program.programId);
+
+    const writeRuns = (offset: number, runs: Buffer) =>
+      writeBitmapRuns(program, { epoch, multisigAuthority: multisigAuthority.publicKey, offset, runs })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      await setSandwichValidators(program, { epoch, multisigAuthority: multisigAuthority.publicKey })
+        .signers([multisigAuthority.payer])
+        .rpc();
+    });
+
+    it("should encode alternating ungated and gated runs", () => {
+      // Slots 0-3 gated, 4-19 ungated, 20-23 gated; a piece starting gated opens with a zero run
+      const [piece] = encodeBitmapRuns(Buffer.from([0x0f, 0x00, 0xf0]));
+      assert.equal(piece.offset, 0);
+      assert.deepEqual([...piece.runs], [0, 4, 16, 4]);
+
+      // Small pieces split at run boundaries and carry their starting slot
+      const pieces = encodeBitmapRuns(Buffer.from([0x0f, 0x00, 0xf0]), 2);
+      assert.deepEqual(pieces.map((p) => p.offset), [0, 4]);
+      assert.deepEqual([...pieces[1].runs], [16, 4]);
+    });
+
+    it("should upload a sparse bitmap in a single transaction", async () => {
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      const bitmap = Buffer.alloc(getBitmapData(accountInfo.data).length);
+      let gated = 0;
+      for (let slot = 0; slot + 4 <= bitmap.length * 8; slot += 400) {
+        for (let i = slot; i < slot + 4; i++) bitmap[i >> 3] |= 1 << (i & 7);
+        gated += 4;
+      }
+
+      const pieces = encodeBitmapRuns(bitmap);
+      assert.equal(pieces.length, 1); // Raw appends would need a dozen transactions
+      await writeRuns(pieces[0].offset, pieces[0].runs);
+      await writeRuns(pieces[0].offset, pieces[0].runs); // Replaying is harmless
+
+      const updated = await provider.connection.getAccountInfo(pda);
+      assert.isTrue(getBitmapData(updated.data).equals(bitmap));
+      assert.equal(getGatedCount(updated.data), gated);
+    });
+
+    it("should reject runs past the bitmap and empty runs after the first", async () => {
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      const capacity = getBitmapData(accountInfo.data).length * 8;
+      const cases: [number, Buffer, string][] = [
+        [capacity - 1, Buffer.from([2]), "SlotOutOfRange"],
+        [0, Buffer.from([1, 0]), "InvalidSlotEncoding"],
+      ];
+      for (const [offset, runs, expected] of cases) {
+        try {
+          await writeRuns(offset, runs);
+          assert.fail(`Expected ${expected} error`);
+        } catch (error) {
+          assert.isTrue(error.toString().includes(expected), error.toString());
+        }
+      }
+    });
+
+    it("should skip the part of a run past the end of the epoch", async () => {
+      // A fully expanded leader-window account holds exactly one bit per window of the epoch
+      const windowEpoch = epoch + 1;
+      const windowPda = getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(windowEpoch), program.programId).pda;
+      await setSandwichValidators(program, {
+        epoch: windowEpoch,
+        multisigAuthority: multisigAuthority.publicKey,
+        slotsPerBit: SLOTS_PER_LEADER_WINDOW,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+      await expandSandwichValidatorsBitmap(program, { epoch: windowEpoch, multisigAuthority: multisigAuthority.publicKey })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const bitsInEpoch = getBitmapData((await provider.connection.getAccountInfo(windowPda)).data).length * 8;
+      // 12 gated windows starting 4 before the end: only the 4 inside the epoch are written
+      await writeBitmapRuns(program, {
+        epoch: windowEpoch,
+        multisigAuthority: multisigAuthority.publicKey,
+        offset: bitsInEpoch - 4,
+        runs: Buffer.from([0, 12]),
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const updated = await provider.connection.getAccountInfo(windowPda);
+      const bitmap = getBitmapData(updated.data);
+      assert.equal(bitmap[bitmap.length - 1], 0xf0);
+      assert.equal(getGatedCount(updated.data), 4);
+      assert.equal((await program.account.sandwichValidators.fetch(windowPda)).bitmapLen, bitmap.length);
+
+      // A run starting at or past the epoch's last bit writes nothing and is rejected
+      try {
+        await writeBitmapRuns(program, {
+          epoch: windowEpoch,
+          multisigAuthority: multisigAuthority.publicKey,
+          offset: bitsInEpoch,
+          runs: Buffer.from([0, 8]),
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+        assert.fail("Expected SlotOutOfRange error");
+      } catch (error) {
+        assert.isTrue(error.toString().includes("SlotOutOfRange"), error.toString());
+      }
+    });
+  });
+
+  describe("Slot Queries", () => {
+    const namespace = anchor.web3.Keypair.generate();
+    let epoch: number;
+    let epochStart: number;
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      const fundTx = new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: multisigAuthority.publicKey,
+          toPubkey: namespace.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL,
+        })
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+
+      epoch = (await provider.connection.getEpochInfo()).epoch;
+      epochStart = epoch * SLOTS_PER_EPOCH;
+      await setSandwichValidators(program, { epoch, multisigAuthority: namespace.publicKey })
+        .signers([namespace])
+        .rpc();
+      await modifySandwichValidatorsRanges(program, {
+        epoch,
+        rangesToGate: [{ start: new BN(epochStart + 10), len: 20 }],
+        multisigAuthority: namespace.publicKey,
+      })
+        .signers([namespace])
+        .rpc();
+    });
+
+    it("should report gated slots and the next ungated slot without failing", async () => {
+      const slots = [epochStart + 10, epochStart + 9, epochStart + 29];
+      const result = await (await querySlots(program, { multisigAuthority: namespace.publicKey, slots })).view();
+
+      assert.equal(result.epoch.toNumber(), epoch);
+      assert.isTrue(result.fromBitmap);
+      assert.isTrue(isQueriedSlotGated(result, 0));
+      assert.isFalse(isQueriedSlotGated(result, 1));
+      assert.isTrue(isQueriedSlotGated(result, 2));
+      // Slots 10..30 are gated, so the first ungated slot from slot 10 is 30
+      assert.equal(result.nextUngatedSlot.toNumber(), epochStart + 30);
+    });
+
+    it("should query the current slot by default", async () => {
+      const result = await (await querySlots(program, { multisigAuthority: namespace.publicKey })).view();
+      assert.equal(result.gatedMask.toNumber() & ~1, 0);
+      assert.isNotNull(result.nextUngatedSlot);
+    });
+
+    it("should fall back to the fail policy when no bitmap exists", async () => {
+      const unconfigured = anchor.web3.Keypair.generate();
+      const result = await (
+        await querySlots(program, { multisigAuthority: unconfigured.publicKey, slots: [epochStart + 10] })
+      ).view();
+
+      assert.isFalse(result.fromBitmap);
+      assert.equal(result.gatedMask.toNumber(), 0);
+      assert.equal(result.nextUngatedSlot.toNumber(), epochStart + 10);
+    });
+
+    it("should report no next ungated slot past the epoch when no bitmap exists", async () => {
+      const unconfigured = anchor.web3.Keypair.generate();
+      const result = await (
+        await querySlots(program, { multisigAuthority: unconfigured.publicKey, slots: [epochStart + SLOTS_PER_EPOCH] })
+      ).view();
+
+      assert.isFalse(result.fromBitmap);
+      assert.isNull(result.nextUngatedSlot);
+    });
+  });
+
+  describe("Validate V2", () => {
+    const namespace = anchor.web3.Keypair.generate();
+
+    const expectError = async (promise: Promise<unknown>, name: string) => {
+      try {
+        await promise;
+        assert.fail(`Expected '${name}' error`);
+      } catch (error) {
+        assert.isTrue(error.toString().includes(name), `Expected '${name}' error, but got: ${error}`);
+      }
+    };
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      const fundTx = new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: multisigAuthority.publicKey,
+          toPubkey: namespace.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL,
+        })
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+    });
+
+    it("should pass when the namespace has no bitmap for the current epoch", async () => {
+      await (await validateV2(program, { multisigAuthority: namespace.publicKey })).rpc();
+    });
+
+    it("should treat another epoch's account as unconfigured", async () => {
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      await setSandwichValidators(program, { epoch: epoch + 1, multisigAuthority: namespace.publicKey })
+        .signers([namespace])
+        .rpc();
+      await (await validateV2(program, { multisigAuthority: namespace.publicKey, epoch: epoch + 1 })).rpc();
+    });
+
+    it("should fail with SlotIsGated like validateSandwichValidators", async () => {
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      const currentSlot = await provider.connection.getSlot();
+      await setSandwichValidators(program, { epoch, multisigAuthority: namespace.publicKey })
+        .signers([namespace])
+        .rpc();
+      // Gate a window ahead of the current slot so it is still gated when the validation lands
+      await modifySandwichValidatorsRanges(program, {
+        epoch,
+        rangesToGate: [{ start: new BN(currentSlot), len: 500 }],
+        multisigAuthority: namespace.publicKey,
+      })
+        .signers([namespace])
+        .rpc();
+
+      await expectError(
+        (await validateV2(program, { multisigAuthority: namespace.publicKey })).rpc(),
+        "SlotIsGated"
+      );
+      await expectError(
+        (await validateSandwichValidators(program, { multisigAuthority: namespace.publicKey })).rpc(),
+        "SlotIsGated"
+      );
+    });
+  });
+
+  describe("Epoch Boundary Validation", () => {
+    const namespace = anchor.web3.Keypair.generate();
+
+    const expectError = async (promise: Promise<unknown>, name: string) => {
+      try {
+        await promise;
+        assert.fail(`Expected '${name}' error`);
+      } catch (error) {
+        assert.isTrue(error.toString().includes(name), `Expected '${name}' error, but got: ${error}`);
+      }
+    };
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      const fundTx = new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: multisigAuthority.publicKey,
+          toPubkey: namespace.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL,
+        })
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+    });
+
+    it("should apply the fail policy when the current epoch has no bitmap", async () => {
+      await (await validateEpochBoundary(program, { multisigAuthority: namespace.publicKey })).rpc();
+    });
+
+    it("should accept the empty legacy address as the unconfigured current epoch", async () => {
+      // Same fallback as validateSandwichValidators while the epoch fits in a u16
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      await program.methods
+        .validateEpochBoundary()
+        .accountsStrict({
+          multisigAuthority: namespace.publicKey,
+          gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+        })
+        .remainingAccounts([
+          {
+            pubkey: getLegacySandwichValidatorsPda(namespace.publicKey, epoch, program.programId).pda,
+            isSigner: false,
+            isWritable: false,
+          },
+        ])
+        .rpc();
+    });
+
+    it("should fail when no candidate is the current epoch's PDA", async () => {
+      // Candidates for two future epochs, as if the transaction had been built far ahead
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      await expectError(
+        (await validateEpochBoundary(program, { multisigAuthority: namespace.publicKey, epoch: epoch + 1 })).rpc(),
+        "MissingSandwichValidatorsAccount"
+      );
+    });
+
+    it("should pick the candidate for the epoch the transaction lands in", async () => {
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      const currentSlot = await provider.connection.getSlot();
+      await setSandwichValidators(program, { epoch, multisigAuthority: namespace.publicKey })
+        .signers([namespace])
+        .rpc();
+      await modifySandwichValidatorsRanges(program, {
+        epoch,
+        rangesToGate: [{ start: new BN(currentSlot), len: 500 }],
+        multisigAuthority: namespace.publicKey,
+      })
+        .signers([namespace])
+        .rpc();
+
+      // Built for the previous epoch: its N+1 candidate is the current epoch's PDA
+      if (epoch > 0) {
+        await expectError(
+          (await validateEpochBoundary(program, { multisigAuthority: namespace.publicKey, epoch: epoch - 1 })).rpc(),
+          "SlotIsGated"
+        );
+      }
+      await expectError(
+        (await validateEpochBoundary(program, { multisigAuthority: namespace.publicKey })).rpc(),
+        "SlotIsGated"
+      );
+    });
+  });
+
+  describe("Strict Validation", () => {
+    const namespace = anchor.web3.Keypair.generate();
+
+    const expectError = async (promise: Promise<unknown>, name: string) => {
+      try {
+        await promise;
+        assert.fail(`Expected '${name}' error`);
+      } catch (error) {
+        assert.isTrue(error.toString().includes(name), `Expected '${name}' error, but got: ${error}`);
+      }
+    };
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      const fundTx = new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: multisigAuthority.publicKey,
+          toPubkey: namespace.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL,
+        })
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+    });
+
+    it("should apply the fail policy to an unconfigured epoch unless configuration is required", async () => {
+      await (await validateStrict(program, { multisigAuthority: namespace.publicKey })).rpc();
+      await expectError(
+        (await validateStrict(program, { multisigAuthority: namespace.publicKey, requireConfigured: true })).rpc(),
+        "EpochNotConfigured"
+      );
+    });
+
+    it("should reject a PDA for the wrong epoch or authority", async () => {
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      await expectError(
+        (await validateStrict(program, { multisigAuthority: namespace.publicKey, epoch: epoch + 1 })).rpc(),
+        "InvalidSandwichValidatorsPDA"
+      );
+
+      // The namespace's PDA presented with another authority
+      const { pda } = getSandwichValidatorsPda(namespace.publicKey, new BN(epoch), program.programId);
+      await expectError(
+        (
+          await validateStrict(program, {
+            multisigAuthority: multisigAuthority.publicKey,
+            sandwichValidators: pda,
+          })
+        ).rpc(),
+        "InvalidSandwichValidatorsPDA"
+      );
+    });
+
+    it("should reject accounts that are not SandwichValidators accounts", async () => {
+      // Owned by the system program
+      await expectError(
+        (
+          await validateStrict(program, {
+            multisigAuthority: namespace.publicKey,
+            sandwichValidators: multisigAuthority.publicKey,
+          })
+        ).rpc(),
+        "InvalidSandwichValidatorsPDA"
+      );
+
+      // Owned by this program, but with another discriminator
+      await expectError(
+        (
+          await validateStrict(program, {
+            multisigAuthority: namespace.publicKey,
+            sandwichValidators: getGatekeeperConfigPda(program.programId).pda,
+          })
+        ).rpc(),
+        "InvalidSandwichValidatorsPDA"
+      );
+    });
+
+    it("should validate the current epoch's bitmap", async () => {
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      const currentSlot = await provider.connection.getSlot();
+      await setSandwichValidators(program, { epoch, multisigAuthority: namespace.publicKey })
+        .signers([namespace])
+        .rpc();
+      await (await validateStrict(program, { multisigAuthority: namespace.publicKey, requireConfigured: true })).rpc();
+
+      await modifySandwichValidatorsRanges(program, {
+        epoch,
+        rangesToGate: [{ start: new BN(currentSlot), len: 500 }],
+        multisigAuthority: namespace.publicKey,
+      })
+        .signers([namespace])
+        .rpc();
+      await expectError(
+        (await validateStrict(program, { multisigAuthority: namespace.publicKey })).rpc(),
+        "SlotIsGated"
+      );
+    });
+  });
+
+  describe("Shadow Validation", () => {
+    const namespace = anchor.web3.Keypair.generate();
+
+    const sendShadow = async () => {
+      const signature = await (await validateShadow(program, { multisigAuthority: namespace.publicKey })).rpc({
+        commitment: "confirmed",
+      });
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return getShadowEvents(program, tx);
+    };
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      const fundTx = new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: multisigAuthority.publicKey,
+          toPubkey: namespace.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL,
+        })
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+    });
+
+    it("should pass without an event when the slot is not gated", async () => {
+      const events = await sendShadow();
+      assert.lengthOf(events, 0);
+    });
+
+    it("should pass and emit SlotGatedShadow where validation would fail", async () => {
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      const currentSlot = await provider.connection.getSlot();
+      await setSandwichValidators(program, { epoch, multisigAuthority: namespace.publicKey })
+        .signers([namespace])
+        .rpc();
+      await modifySandwichValidatorsRanges(program, {
+        epoch,
+        rangesToGate: [{ start: new BN(currentSlot), len: 500 }],
+        multisigAuthority: namespace.publicKey,
+      })
+        .signers([namespace])
+        .rpc();
+
+      const events = await sendShadow();
+      assert.lengthOf(events, 1);
+      assert.isTrue(events[0].authority.equals(namespace.publicKey));
+      assert.equal(events[0].epoch.toNumber(), epoch);
+      assert.isAtLeast(events[0].slot.toNumber(), currentSlot);
+      assert.isBelow(events[0].slot.toNumber(), currentSlot + 500);
+    });
+  });
+
+  describe("Integrator Profiles", () => {
+    // The gatekeeper program doubles as the integrator, since its upgrade authority is the test wallet
+    const integratorProgram = program.programId;
+    const { pda: profilePda } = getIntegratorProfilePda(integratorProgram, program.programId);
+    const secondAuthority = anchor.web3.Keypair.generate();
+
+    const expectError = async (promise: Promise<unknown>, name: string) => {
+      try {
+        await promise;
+        assert.fail(`Expected '${name}' error`);
+      } catch (error) {
+        assert.isTrue(error.toString().includes(name), `Expected '${name}' error, but got: ${error}`);
+      }
+    };
+
+    const defaultParams = () => ({
+      admin: multisigAuthority.publicKey,
+      authorities: [multisigAuthority.publicKey, secondAuthority.publicKey],
+      combination: SourceCombination.Union,
+      failPolicy: FailPolicy.Open,
+      exemptRanges: [],
+    });
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      if (!(await provider.connection.getAccountInfo(profilePda))) {
+        await initializeIntegratorProfile(program, {
+          integratorProgram,
+          upgradeAuthority: multisigAuthority.publicKey,
+          params: defaultParams(),
+        })
+          .signers([multisigAuthority.payer])
+          .rpc();
+      }
+    });
+
+    it("should store the profile for the integrating program", async () => {
+      await updateIntegratorProfile(program, {
+        integratorProgram,
+        admin: multisigAuthority.publicKey,
+        params: {
+          ...defaultParams(),
+          combination: SourceCombination.Intersection,
+          exemptRanges: [{ start: new BN(1_000), len: 10 }],
+        },
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      const profile = await program.account.integratorProfile.fetch(profilePda);
+      assert.isTrue(profile.integrator.equals(integratorProgram));
+      assert.lengthOf(profile.authorities, 2);
+      assert.deepEqual(profile.combination, SourceCombination.Intersection);
+      assert.equal(profile.exemptRanges[0].start.toNumber(), 1_000);
+    });
+
+    it("should NOT allow a non-admin to update the profile", async () => {
+      await expectError(
+        updateIntegratorProfile(program, {
+          integratorProgram,
+          admin: unauthorizedUser.publicKey,
+          params: { ...defaultParams(), admin: unauthorizedUser.publicKey },
+        })
+          .signers([unauthorizedUser])
+          .rpc(),
+        "InvalidAuthority"
+      );
+    });
+
+    it("should reject duplicate authorities and empty exempt ranges", async () => {
+      await expectError(
+        updateIntegratorProfile(program, {
+          integratorProgram,
+          admin: multisigAuthority.publicKey,
+          params: { ...defaultParams(), authorities: [multisigAuthority.publicKey, multisigAuthority.publicKey] },
+        })
+          .signers([multisigAuthority.payer])
+          .rpc(),
+        "InvalidIntegratorProfile"
+      );
+      await expectError(
+        updateIntegratorProfile(program, {
+          integratorProgram,
+          admin: multisigAuthority.publicKey,
+          params: { ...defaultParams(), exemptRanges: [{ start: new BN(1_000), len: 0 }] },
+        })
+          .signers([multisigAuthority.payer])
+          .rpc(),
+        "InvalidIntegratorProfile"
+      );
+    });
+
+    it("should reject a profile when validation is not invoked by its program", async () => {
+      // A top-level call has no calling program, so no profile can apply
+      await expectError(
+        (
+          await validateSandwichValidators(program, {
+            multisigAuthority: multisigAuthority.publicKey,
+            integratorProfile: {
+              integrator: integratorProgram,
+              authorities: defaultParams().authorities,
+            },
+          })
+        ).rpc(),
+        "IntegratorProfileMismatch"
+      );
+    });
+
+    it("should fail closed when a profile is passed without the instructions sysvar", async () => {
+      // Without the sysvar the caller cannot be identified, so the profile is not silently skipped
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      await expectError(
+        program.methods
+          .validateSandwichValidators()
+          .accountsStrict({
+            sandwichValidators: getSandwichValidatorsPda(multisigAuthority.publicKey, new BN(epoch), program.programId).pda,
+            multisigAuthority: multisigAuthority.publicKey,
+            clock: web3.SYSVAR_CLOCK_PUBKEY,
+            gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+            instructionsSysvar: null,
+            integratorProfile: profilePda,
+            committee: null,
+          })
+          .rpc(),
+        "IntegratorProfileUnverifiable"
+      );
+    });
+  });
+
+  describe("Integrator Overrides", () => {
+    const namespace = anchor.web3.Keypair.generate();
+    const integrator = anchor.web3.Keypair.generate();
+
+    const expectError = async (promise: Promise<unknown>, name: string) => {
+      try {
+        await promise;
+        assert.fail(`Expected '${name}' error`);
+      } catch (error) {
+        assert.isTrue(error.toString().includes(name), `Expected '${name}' error, but got: ${error}`);
+      }
+    };
+
+    const validate = async () =>
+      (
+        await validateWithOverrides(program, {
+          multisigAuthority: namespace.publicKey,
+          overrideOwner: integrator.publicKey,
+        })
+      ).rpc();
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      const fundTx = new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: multisigAuthority.publicKey,
+          toPubkey: namespace.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL,
+        }),
+        web3.SystemProgram.transfer({
+          fromPubkey: multisigAuthority.publicKey,
+          toPubkey: integrator.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL,
+        })
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+    });
+
+    it("should deny slots the authority leaves open", async () => {
+      await validate();
+
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      const currentSlot = await provider.connection.getSlot();
+      await setIntegratorOverrides(program, {
+        owner: integrator.publicKey,
+        epoch,
+        deny: [{ start: new BN(currentSlot), len: 500 }],
+      })
+        .signers([integrator])
+        .rpc();
+
+      await expectError(validate(), "SlotIsGated");
+    });
+
+    it("should allow slots the authority gates", async () => {
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      const currentSlot = await provider.connection.getSlot();
+      await setSandwichValidators(program, { epoch, multisigAuthority: namespace.publicKey })
+        .signers([namespace])
+        .rpc();
+      await modifySandwichValidatorsRanges(program, {
+        epoch,
+        rangesToGate: [{ start: new BN(currentSlot), len: 500 }],
+        multisigAuthority: namespace.publicKey,
+      })
+        .signers([namespace])
+        .rpc();
+
+      await setIntegratorOverrides(program, {
+        owner: integrator.publicKey,
+        epoch,
+        allow: [{ start: new BN(currentSlot), len: 500 }],
+      })
+        .signers([integrator])
+        .rpc();
+      await validate();
+
+      // Without the overrides the authority's decision applies again
+      await closeIntegratorOverrides(program, { owner: integrator.publicKey, epoch })
+        .signers([integrator])
+        .rpc();
+      await expectError(validate(), "SlotIsGated");
+    });
+
+    it("should reject overlapping or out-of-epoch ranges", async () => {
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      const epochStart = epoch * SLOTS_PER_EPOCH;
+      await expectError(
+        setIntegratorOverrides(program, {
+          owner: integrator.publicKey,
+          epoch,
+          allow: [{ start: new BN(epochStart), len: 10 }],
+          deny: [{ start: new BN(epochStart + 5), len: 10 }],
+        })
+          .signers([integrator])
+          .rpc(),
+        "OverlapSlots"
+      );
+      await expectError(
+        setIntegratorOverrides(program, {
+          owner: integrator.publicKey,
+          epoch,
+          deny: [{ start: new BN(epochStart + SLOTS_PER_EPOCH - 5), len: 10 }],
+        })
+          .signers([integrator])
+          .rpc(),
+        "SlotOutOfRange"
+      );
+    });
+  });
+
+  describe("Multi-Source Validation", () => {
+    const quietSource = anchor.web3.Keypair.generate();
+    const flaggingSource = anchor.web3.Keypair.generate();
+
+    const expectError = async (promise: Promise<unknown>, name: string) => {
+      try {
+        await promise;
+        assert.fail(`Expected '${name}' error`);
+      } catch (error) {
+        assert.isTrue(error.toString().includes(name), `Expected '${name}' error, but got: ${error}`);
+      }
+    };
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      const fundTx = new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: multisigAuthority.publicKey,
+          toPubkey: flaggingSource.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL,
+        })
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+
+      // Only the second source gates the upcoming slots; the first has no bitmap at all
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      const currentSlot = await provider.connection.getSlot();
+      await setSandwichValidators(program, { epoch, multisigAuthority: flaggingSource.publicKey })
+        .signers([flaggingSource])
+        .rpc();
+      await modifySandwichValidatorsRanges(program, {
+        epoch,
+        rangesToGate: [{ start: new BN(currentSlot), len: 500 }],
+        multisigAuthority: flaggingSource.publicKey,
+      })
+        .signers([flaggingSource])
+        .rpc();
+    });
+
+    const authorities = () => [quietSource.publicKey, flaggingSource.publicKey];
+
+    it("should report which sources flag the slot without enforcing", async () => {
+      const result: SourceValidationResult = await (
+        await validateSources(program, { authorities: authorities(), enforce: false })
+      ).view();
+      assert.equal(result.flaggedMask, 0b10);
+      assert.equal(result.flaggedCount, 1);
+      assert.isTrue(result.gated);
+    });
+
+    it("should combine sources by union or k-of-n quorum", async () => {
+      await expectError(
+        (await validateSources(program, { authorities: authorities(), quorum: 1 })).rpc(),
+        "SlotIsGated"
+      );
+      // One flag does not reach a 2-of-2 quorum
+      await (await validateSources(program, { authorities: authorities(), quorum: 2 })).rpc();
+    });
+
+    it("should reject repeated sources and an unreachable quorum", async () => {
+      await expectError(
+        (
+          await validateSources(program, {
+            authorities: [flaggingSource.publicKey, flaggingSource.publicKey],
+            quorum: 2,
+          })
+        ).rpc(),
+        "InvalidValidationSources"
+      );
+      await expectError(
+        (await validateSources(program, { authorities: authorities(), quorum: 3 })).rpc(),
+        "InvalidValidationSources"
+      );
+    });
+  });
+
+  describe("Curator Committee", () => {
+    const namespace = anchor.web3.Keypair.generate();
+    const curators = [0, 1, 2].map(() => anchor.web3.Keypair.generate());
+    const { pda: committeePda } = getCommitteePda(namespace.publicKey, program.programId);
+
+    const expectError = async (promise: Promise<unknown>, name: string) => {
+      try {
+        await promise;
+        assert.fail(`Expected '${name}' error`);
+      } catch (error) {
+        assert.isTrue(error.toString().includes(name), `Expected '${name}' error, but got: ${error}`);
+      }
+    };
+
+    const members = () => curators.map((curator) => curator.publicKey);
+    const memberNamespace = (curator: anchor.web3.Keypair) =>
+      getCommitteeMemberNamespacePda(committeePda, curator.publicKey, program.programId).pda;
+
+    const validate = async () =>
+      (
+        await validateSandwichValidators(program, {
+          multisigAuthority: committeePda,
+          committee: { namespace: namespace.publicKey, members: members() },
+        })
+      ).rpc();
+
+    // Members write under their committee namespace, signing as its active authority
+    const gateUpcomingSlots = async (curator: anchor.web3.Keypair) => {
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      const currentSlot = await provider.connection.getSlot();
+      await setSandwichValidators(program, {
+        epoch,
+        multisigAuthority: memberNamespace(curator),
+        operator: curator.publicKey,
+      })
+        .signers([curator])
+        .rpc();
+      await modifySandwichValidatorsRanges(program, {
+        epoch,
+        rangesToGate: [{ start: new BN(currentSlot), len: 500 }],
+        multisigAuthority: memberNamespace(curator),
+        operator: curator.publicKey,
+      })
+        .signers([curator])
+        .rpc();
+    };
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      const fundTx = new web3.Transaction().add(
+        ...[namespace, ...curators].map((keypair) =>
+          web3.SystemProgram.transfer({
+            fromPubkey: multisigAuthority.publicKey,
+            toPubkey: keypair.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+          })
+        )
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+
+      await initializeCommittee(program, {
+        namespace: namespace.publicKey,
+        params: { governance: namespace.publicKey, members: members(), threshold: 2 },
+      })
+        .signers([namespace])
+        .rpc();
+      for (const curator of curators) {
+        await joinCommittee(program, { namespace: namespace.publicKey, member: curator.publicKey })
+          .signers([curator])
+          .rpc();
+      }
+    });
+
+    it("should make each member the active authority of its member namespace", async () => {
+      const record = await program.account.authorityRecord.fetch(
+        getAuthorityRecordPda(memberNamespace(curators[0]), program.programId).pda
+      );
+      assert.isTrue(record.namespace.equals(memberNamespace(curators[0])));
+      assert.isTrue(record.authority.equals(curators[0].publicKey));
+    });
+
+    it("should NOT let a non-member join", async () => {
+      const outsider = anchor.web3.Keypair.generate();
+      await provider.sendAndConfirm(
+        new web3.Transaction().add(
+          web3.SystemProgram.transfer({
+            fromPubkey: multisigAuthority.publicKey,
+            toPubkey: outsider.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+          })
+        ),
+        [multisigAuthority.payer]
+      );
+      await expectError(
+        joinCommittee(program, { namespace: namespace.publicKey, member: outsider.publicKey })
+          .signers([outsider])
+          .rpc(),
+        "InvalidAuthority"
+      );
+    });
+
+    it("should pass with k-1 member flags and gate with k on the same slot", async () => {
+      // Threshold is 2: the same gated range from one member passes, from two it blocks
+      await gateUpcomingSlots(curators[0]);
+      await validate();
+
+      await gateUpcomingSlots(curators[1]);
+      await expectError(validate(), "SlotIsGated");
+    });
+
+    it("should NOT count a member's own gate lists outside its member namespace", async () => {
+      // curators[2] gates under its own key, which the committee never reads
+      const epoch = (await provider.connection.getEpochInfo()).epoch;
+      const currentSlot = await provider.connection.getSlot();
+      await setSandwichValidators(program, { epoch, multisigAuthority: curators[2].publicKey })
+        .signers([curators[2]])
+        .rpc();
+      await modifySandwichValidatorsRanges(program, {
+        epoch,
+        rangesToGate: [{ start: new BN(currentSlot), len: 500 }],
+        multisigAuthority: curators[2].publicKey,
+      })
+        .signers([curators[2]])
+        .rpc();
+
+      await updateCommittee(program, {
+        namespace: namespace.publicKey,
+        governance: namespace.publicKey,
+        params: { governance: namespace.publicKey, members: members(), threshold: 3 },
+      })
+        .signers([namespace])
+        .rpc();
+      await validate();
+    });
+
+    it("should apply membership and threshold changes from governance", async () => {
+      await updateCommittee(program, {
+        namespace: namespace.publicKey,
+        governance: namespace.publicKey,
+        params: { governance: namespace.publicKey, members: members(), threshold: 3 },
+      })
+        .signers([namespace])
+        .rpc();
+      await validate();
+
+      const committee = await program.account.committee.fetch(committeePda);
+      assert.equal(committee.threshold, 3);
+      assert.lengthOf(committee.members, 3);
+    });
+
+    it("should NOT allow anyone but governance to update the committee", async () => {
+      await expectError(
+        updateCommittee(program, {
+          namespace: namespace.publicKey,
+          governance: curators[0].publicKey,
+          params: { governance: curators[0].publicKey, members: members(), threshold: 1 },
+        })
+          .signers([curators[0]])
+          .rpc(),
+        "InvalidAuthority"
+      );
+    });
+
+    it("should reject duplicate members", async () => {
+      await expectError(
+        updateCommittee(program, {
+          namespace: namespace.publicKey,
+          governance: namespace.publicKey,
+          params: {
+            governance: namespace.publicKey,
+            members: [curators[0].publicKey, curators[0].publicKey],
+            threshold: 1,
+          },
+        })
+          .signers([namespace])
+          .rpc(),
+        "InvalidCommittee"
+      );
+    });
+  });
+
+
+  describe("Provider Registry", () => {
+    const providerAuthority = anchor.web3.Keypair.generate();
+    const outsider = anchor.web3.Keypair.generate();
+    const name = `provider-${providerAuthority.publicKey.toBase58().slice(0, 8)}`;
+
+    const expectError = async (promise: Promise<unknown>, errorName: string) => {
+      try {
+        await promise;
+        assert.fail(`Expected '${errorName}' error`);
+      } catch (error) {
+        assert.isTrue(error.toString().includes(errorName), `Expected '${errorName}' error, but got: ${error}`);
+      }
+    };
+
+    const metadata = (overrides: Partial<ProviderMetadata> = {}): ProviderMetadata => ({
+      name,
+      metadataUri: "https://example.com/provider.json",
+      firstEpoch: new BN(0),
+      lastEpoch: null,
+      ...overrides,
+    });
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      const fundTx = new web3.Transaction().add(
+        ...[providerAuthority, outsider].map((keypair) =>
+          web3.SystemProgram.transfer({
+            fromPubkey: multisigAuthority.publicKey,
+            toPubkey: keypair.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+          })
+        )
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+
+      await registerProvider(program, {
+        admin: multisigAuthority.publicKey,
+        authority: providerAuthority.publicKey,
+        metadata: metadata(),
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+    });
+
+    it("should list a registered provider and find it by name", async () => {
+      const found = await findProviderByName(program, name);
+      assert.isNotNull(found);
+      assert.isTrue(found.authority.equals(providerAuthority.publicKey));
+      assert.deepEqual(found.status, ProviderStatus.Active);
+    });
+
+    it("should NOT allow a duplicate name or a non-admin registration", async () => {
+      await expectError(
+        registerProvider(program, {
+          admin: multisigAuthority.publicKey,
+          authority: outsider.publicKey,
+          metadata: metadata(),
+        })
+          .signers([multisigAuthority.payer])
+          .rpc(),
+        "ProviderAlreadyRegistered"
+      );
+      await expectError(
+        registerProvider(program, {
+          admin: outsider.publicKey,
+          authority: outsider.publicKey,
+          metadata: metadata({ name: `${name}-2` }),
+        })
+          .signers([outsider])
+          .rpc(),
+        "InvalidAuthority"
+      );
+    });
+
+    it("should let the provider update its own record but not an outsider", async () => {
+      await updateProvider(program, {
+        signer: providerAuthority.publicKey,
+        authority: providerAuthority.publicKey,
+        metadata: metadata({ metadataUri: "https://example.com/v2.json", lastEpoch: new BN(1_000) }),
+      })
+        .signers([providerAuthority])
+        .rpc();
+
+      const found = await findProviderByName(program, name);
+      assert.equal(found.metadataUri, "https://example.com/v2.json");
+      assert.equal(found.lastEpoch.toNumber(), 1_000);
+
+      await expectError(
+        updateProvider(program, {
+          signer: outsider.publicKey,
+          authority: providerAuthority.publicKey,
+          metadata: metadata(),
+        })
+          .signers([outsider])
+          .rpc(),
+        "InvalidAuthority"
+      );
+    });
+
+    it("should keep a deprecated provider visible but hide it from name lookups", async () => {
+      await deprecateProvider(program, {
+        signer: multisigAuthority.publicKey,
+        authority: providerAuthority.publicKey,
+      })
+        .signers([multisigAuthority.payer])
+        .rpc();
+
+      assert.isNull(await findProviderByName(program, name));
+      const all = await fetchProviders(program, { includeDeprecated: true });
+      const record = all.find((p) => p.authority.equals(providerAuthority.publicKey));
+      assert.deepEqual(record.status, ProviderStatus.Deprecated);
+    });
+  });
+
+
+  describe("Leader-Window Granularity", () => {
+    const curator = anchor.web3.Keypair.generate();
+    let epoch: number;
+    let pda: PublicKey;
+
+    const expectError = async (promise: Promise<unknown>, name: string) => {
+      try {
+        await promise;
+        assert.fail(`Expected '${name}' error`);
+      } catch (error) {
+        assert.isTrue(error.toString().includes(name), `Expected '${name}' error, but got: ${error}`);
+      }
+    };
+
+    before(async function () {
+      if (isDevnet || isMainnet) this.skip();
+
+      const fundTx = new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: multisigAuthority.publicKey,
+          toPubkey: curator.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL,
+        })
+      );
+      await provider.sendAndConfirm(fundTx, [multisigAuthority.payer]);
+
+      epoch = (await provider.connection.getEpochInfo()).epoch;
+      pda = getSandwichValidatorsPda(curator.publicKey, new BN(epoch), program.programId).pda;
+      await setSandwichValidators(program, {
+        epoch,
+        multisigAuthority: curator.publicKey,
+        slotsPerBit: SLOTS_PER_LEADER_WINDOW,
+      })
+        .signers([curator])
+        .rpc();
+    });
+
+    it("should reach the full leader-window size with at most one expansion", async () => {
+      const { slotsInEpoch } = await getEpochBounds(provider.connection, epoch);
+      const targetSize = getTargetAccountSize(slotsInEpoch, SLOTS_PER_LEADER_WINDOW);
+
+      if ((await provider.connection.getAccountInfo(pda)).data.length < targetSize) {
+        await expandSandwichValidatorsBitmap(program, { epoch, multisigAuthority: curator.publicKey })
+          .signers([curator])
+          .rpc();
+      }
+
+      const accountInfo = await provider.connection.getAccountInfo(pda);
+      assert.equal(accountInfo.data.length, targetSize);
+      assert.equal(getSlotsPerBit(accountInfo.data), SLOTS_PER_LEADER_WINDOW);
+    });
+
+    it("should gate the whole window of a gated slot", async () => {
+      const { firstSlot } = await getEpochBounds(provider.connection, epoch);
+      const currentSlot = await provider.connection.getSlot();
+      // A window a few hundred slots ahead, so it is still in the future when checked
+      const windowStart = firstSlot + Math.ceil((currentSlot + 400 - firstSlot) / SLOTS_PER_LEADER_WINDOW) * SLOTS_PER_LEADER_WINDOW;
+
+      await modifySandwichValidators(program, {
+        epoch,
+        slotsToGate: [new BN(windowStart + 1)],
+        multisigAuthority: curator.publicKey,
+      })
+        .signers([curator])
+        .rpc();
+
+      const result = await (
+        await querySlots(program, {
+          multisigAuthority: curator.publicKey,
+          slots: [windowStart - 1, windowStart, windowStart + 3, windowStart + 4],
+        })
+      ).view();
+      assert.deepEqual([0, 1, 2, 3].map((i) => isQueriedSlotGated(result, i)), [false, true, true, false]);
+      assert.equal(getGatedCount((await provider.connection.getAccountInfo(pda)).data), 1);
+    });
+
+    it("should reject two gated slots of one window and a window in both lists", async () => {
+      const { firstSlot } = await getEpochBounds(provider.connection, epoch);
+      const currentSlot = await provider.connection.getSlot();
+      const windowStart = firstSlot + Math.ceil((currentSlot + 800 - firstSlot) / SLOTS_PER_LEADER_WINDOW) * SLOTS_PER_LEADER_WINDOW;
+
+      await expectError(
+        modifySandwichValidators(program, {
+          epoch,
+          slotsToGate: [new BN(windowStart), new BN(windowStart + 2)],
+          multisigAuthority: curator.publicKey,
+        })
+          .signers([curator])
+          .rpc(),
+        "DuplicateSlots"
+      );
+      await expectError(
+        modifySandwichValidators(program, {
+          epoch,
+          slotsToGate: [new BN(windowStart)],
+          slotsToUngate: [new BN(windowStart + 3)],
+          multisigAuthority: curator.publicKey,
+        })
+          .signers([curator])
+          .rpc(),
+        "OverlapSlots"
+      );
+    });
+
+    it("should fail validation inside a gated window", async () => {
+      const currentSlot = await provider.connection.getSlot();
+      await modifySandwichValidatorsRanges(program, {
+        epoch,
+        rangesToGate: [{ start: new BN(currentSlot), len: 500 }],
+        multisigAuthority: curator.publicKey,
+      })
+        .signers([curator])
+        .rpc();
+
+      await expectError(
+        (await validateSandwichValidators(program, { multisigAuthority: curator.publicKey })).rpc(),
+        "SlotIsGated"
+      );
+    });
+  });
 });
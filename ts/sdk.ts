@@ -1,14 +1,195 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { createHash } from "crypto";
 import { SaguaroGatekeeper } from "../target/types/saguaro_gatekeeper";
 
 
 /**
- * The PDA seed prefix for the LargeBitmap account.
+ * The PDA seed prefix for the LargeBitmap account (u64 epoch scheme).
  * This must match the value in the Rust program.
  */
-export const SANDWICH_VALIDATORS_SEED_PREFIX = "sandwich_validators";
+export const SANDWICH_VALIDATORS_SEED_PREFIX = "sandwich_validators_v2";
+
+/**
+ * The PDA seed prefix of the legacy u16 epoch scheme. Legacy accounts can still be
+ * validated and closed, but are no longer written.
+ */
+export const LEGACY_SANDWICH_VALIDATORS_SEED_PREFIX = "sandwich_validators";
+
+/**
+ * The PDA seed for the GatekeeperConfig singleton.
+ * This must match the value in the Rust program.
+ */
+export const GATEKEEPER_CONFIG_SEED = "gatekeeper_config";
+
+/**
+ * The PDA seed prefix for AuthorityRecord accounts.
+ * This must match the value in the Rust program.
+ */
+export const AUTHORITY_RECORD_SEED = "authority_record";
+
+/**
+ * The PDA seed prefix for SignerSet accounts.
+ * This must match the value in the Rust program.
+ */
+export const SIGNER_SET_SEED = "signer_set";
+
+/**
+ * Maximum number of members in a SignerSet.
+ * This must match the value in the Rust program.
+ */
+export const MAX_SIGNER_SET_MEMBERS = 10;
+
+/**
+ * The PDA seed prefix for RoleTable accounts.
+ * This must match the value in the Rust program.
+ */
+export const ROLE_TABLE_SEED = "role_table";
+
+/**
+ * Maximum number of grants in a RoleTable.
+ * This must match the value in the Rust program.
+ */
+export const MAX_ROLE_GRANTS = 16;
+
+/**
+ * The PDA seed prefix for IntegratorProfile accounts.
+ * This must match the value in the Rust program.
+ */
+export const INTEGRATOR_PROFILE_SEED = "integrator_profile";
+
+/**
+ * Maximum number of authorities and exempt ranges in an IntegratorProfile.
+ * These must match the values in the Rust program.
+ */
+export const MAX_PROFILE_AUTHORITIES = 4;
+export const MAX_PROFILE_EXEMPT_RANGES = 8;
+
+/**
+ * The PDA seed prefix for Committee accounts.
+ * This must match the value in the Rust program.
+ */
+export const COMMITTEE_SEED = "committee";
+
+/**
+ * The PDA seed prefix for committee member namespaces.
+ * This must match the value in the Rust program.
+ */
+export const COMMITTEE_MEMBER_SEED = "committee_member";
+
+/**
+ * Maximum number of members in a Committee.
+ * This must match the value in the Rust program.
+ */
+export const MAX_COMMITTEE_MEMBERS = 10;
+
+/**
+ * The PDA seed prefix for IntegratorOverrides accounts.
+ * This must match the value in the Rust program.
+ */
+export const INTEGRATOR_OVERRIDES_SEED = "integrator_overrides";
+
+/**
+ * Maximum number of allow (and of deny) ranges in an IntegratorOverrides account.
+ * This must match the value in the Rust program.
+ */
+export const MAX_OVERRIDE_RANGES = 32;
+
+/**
+ * The PDA seed of the ProviderRegistry singleton.
+ * This must match the value in the Rust program.
+ */
+export const PROVIDER_REGISTRY_SEED = "provider_registry";
+
+/**
+ * Limits on the ProviderRegistry and its records (lengths in UTF-8 bytes).
+ * These must match the values in the Rust program.
+ */
+export const MAX_PROVIDERS = 16;
+export const MAX_PROVIDER_NAME_LEN = 32;
+export const MAX_PROVIDER_URI_LEN = 128;
+
+/**
+ * The PDA seed of Anchor's event authority, which signs `emit_cpi!` self-invocations.
+ */
+export const EVENT_AUTHORITY_SEED = "__event_authority";
+
+/**
+ * Instruction tag prefixing `emit_cpi!` event data (Anchor's `EVENT_IX_TAG_LE`).
+ */
+const EVENT_IX_TAG_LE = Buffer.from([0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d]);
+
+/**
+ * Role bits stored in `RoleGrant.roles`. Combine with `|` to grant several roles.
+ */
+export const Role = {
+  Provisioner: 1 << 0, // set / expand
+  Writer: 1 << 1, // modify (all variants) / append / chunk writes / seal / unseal
+  Ungater: 1 << 2, // ungate-only modify / AndNot chunk writes / clear
+  Closer: 1 << 3, // close
+};
+
+/**
+ * A single key's roles in a RoleTable.
+ */
+export type RoleGrant = {
+  key: PublicKey;
+  roles: number;
+};
+
+/**
+ * The BPF upgradeable loader, owner of the program data account.
+ */
+export const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+/**
+ * Enum values accepted by `initializeGatekeeperConfig` / `updateGatekeeperConfig`
+ * (and `setOverrideMode` for `OverrideMode`).
+ */
+export const OperatingMode = {
+  Active: { active: {} },
+  Frozen: { frozen: {} },
+};
+
+export const OverrideMode = {
+  Normal: { normal: {} }, // Validation uses the bitmaps
+  Paused: { paused: {} }, // Every validation passes
+  BlockAll: { blockAll: {} }, // Every validation fails with SlotIsGated
+};
+
+export const SlotsPerEpochSource = {
+  Fixed: { fixed: {} },
+  EpochSchedule: { epochSchedule: {} },
+};
+
+export const FailPolicy = {
+  Open: { open: {} },
+  Closed: { closed: {} },
+};
+
+export const UnsealedPolicy = {
+  Enforce: { enforce: {} }, // Bitmaps apply as soon as they exist
+  Ignore: { ignore: {} }, // Unsealed bitmaps are treated as unconfigured
+};
+
+export const SourceCombination = {
+  Union: { union: {} }, // Gated if any authority gates the slot
+  Intersection: { intersection: {} }, // Gated only if every authority gates the slot
+};
+
+export const ProviderStatus = {
+  Active: { active: {} },
+  Deprecated: { deprecated: {} }, // Kept in the registry, but should no longer be relied on
+};
+
+export const BitmapWriteMode = {
+  Overwrite: { overwrite: {} }, // Replace the bytes
+  Or: { or: {} }, // Gate the chunk's set bits
+  AndNot: { andNot: {} }, // Ungate the chunk's set bits
+};
 
 /**
  * Maximum number of slots allowed per transaction.
@@ -17,9 +198,41 @@ export const SANDWICH_VALIDATORS_SEED_PREFIX = "sandwich_validators";
 export const MAX_SLOTS_PER_TRANSACTION = 100;
 
 /**
- * Number of slots per epoch (432,000).
+ * Maximum number of slots per `querySlots` call (one bit each in the result mask).
  * This must match the value in the Rust program.
  */
+export const MAX_QUERY_SLOTS = 64;
+
+/**
+ * Maximum number of authorities per `validateSources` call (one bit each in the result mask).
+ * This must match the value in the Rust program.
+ */
+export const MAX_VALIDATION_SOURCES = 8;
+
+/**
+ * Maximum number of ranges (gate + ungate) per `modifySandwichValidatorsRanges` call.
+ * This must match the value in the Rust program.
+ */
+export const MAX_RANGES_PER_TRANSACTION = 32;
+
+/**
+ * Maximum number of slots (gate + ungate) per `modifySandwichValidatorsCompact` call.
+ * This must match the value in the Rust program.
+ */
+export const MAX_COMPACT_SLOTS_PER_TRANSACTION = 1_000;
+
+/**
+ * A run of `len` consecutive slots starting at `start`.
+ */
+export type SlotRange = {
+  start: anchor.BN;
+  len: number;
+};
+
+/**
+ * Number of slots per epoch on mainnet-beta (432,000).
+ * Other clusters may differ; use `getEpochBounds` to read the cluster's EpochSchedule.
+ */
 export const SLOTS_PER_EPOCH = 432_000;
 
 /**
@@ -28,269 +241,2009 @@ export const SLOTS_PER_EPOCH = 432_000;
 export const FULL_BITMAP_SIZE_BYTES = 54_000; // 432,000 bits / 8 = 54,000 bytes
 export const INITIAL_ACCOUNT_SIZE = 10240; // Initial 10KB allocation
 export const MAX_REALLOC_SIZE = 10240; // Maximum bytes per realloc operation
-export const SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE = 16; // discriminator(8) + epoch(2) + bump(1) + padding(5)
-export const TARGET_ACCOUNT_SIZE = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE + FULL_BITMAP_SIZE_BYTES; // 54,016 bytes total
+export const RUN_BYTES_PER_TRANSACTION = 900; // Encoded runs per writeBitmapRuns call, leaving room for accounts and signatures
+export const SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE = 64; // discriminator(8) + legacy epoch(2) + bump(1) + version(1) + bitmap_len(4) + epoch(8) + flags(1) + slots_per_bit(1) + reserved(2) + gated_count(4) + bitmap_hash(32)
+export const V2_SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE = 24; // Version 2 header, without flags and bitmap_hash
+export const LEGACY_SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE = 16; // discriminator(8) + epoch(2) + bump(1) + padding(5)
+export const TARGET_ACCOUNT_SIZE = SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE + FULL_BITMAP_SIZE_BYTES; // 54,064 bytes total
+export const SANDWICH_VALIDATORS_LAYOUT_VERSION = 4; // Header byte 11; older accounts hold 3, 2 or 0 until migrated
+export const V3_SANDWICH_VALIDATORS_LAYOUT_VERSION = 3; // Same offsets, without gated_count
+export const V2_SANDWICH_VALIDATORS_LAYOUT_VERSION = 2;
+export const LEGACY_SANDWICH_VALIDATORS_LAYOUT_VERSION = 0;
+export const SANDWICH_VALIDATORS_FLAG_SEALED = 1; // `flags` bit set by sealSandwichValidators
+export const SANDWICH_VALIDATORS_FLAG_LEGACY_SEEDS = 2; // `flags` bit set by migrateSandwichValidators on legacy-address accounts
+export const SLOTS_PER_LEADER_WINDOW = 4; // `slotsPerBit` of leader-window accounts
+
+/**
+ * Returns the offset at which the bitmap begins in raw SandwichValidators account data,
+ * dispatching on the layout version byte of the header.
+ */
+export const getBitmapDataOffset = (data: Buffer): number => {
+  switch (data[11]) {
+    case SANDWICH_VALIDATORS_LAYOUT_VERSION:
+    case V3_SANDWICH_VALIDATORS_LAYOUT_VERSION:
+      return SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE;
+    case V2_SANDWICH_VALIDATORS_LAYOUT_VERSION:
+      return V2_SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE;
+    case LEGACY_SANDWICH_VALIDATORS_LAYOUT_VERSION:
+      return LEGACY_SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE;
+    default:
+      throw new Error(`Unsupported SandwichValidators layout version ${data[11]}`);
+  }
+};
+
+/**
+ * Computes the SHA-256 hash that `sealSandwichValidators` records, over the bitmap
+ * bytes from the end of the header to the end of the account. Pass the artifact built
+ * off-chain, sized to the fully expanded account, or the raw data of a fetched account.
+ */
+export const computeBitmapHash = (bitmap: Buffer): Buffer =>
+  createHash("sha256").update(bitmap).digest();
+
+/**
+ * Reads the exact number of gated slots from raw account data without scanning the
+ * bitmap. Only accounts on the current layout carry the counter.
+ */
+export const getGatedCount = (data: Buffer): number => {
+  if (data[11] !== SANDWICH_VALIDATORS_LAYOUT_VERSION) {
+    throw new Error(`Layout version ${data[11]} has no gated_count; migrate the account first`);
+  }
+  return data.readUInt32LE(28);
+};
+
+/**
+ * Reads how many consecutive slots each bitmap bit covers from raw account data:
+ * `SLOTS_PER_LEADER_WINDOW` for leader-window accounts, otherwise 1.
+ */
+export const getSlotsPerBit = (data: Buffer): number =>
+  data[11] === SANDWICH_VALIDATORS_LAYOUT_VERSION ? Math.max(data[25], 1) : 1;
+
+/**
+ * Returns the bitmap portion of raw SandwichValidators account data.
+ */
+export const getBitmapData = (data: Buffer): Buffer => data.subarray(getBitmapDataOffset(data));
+
+/**
+ * Epoch boundaries as computed on-chain from the EpochSchedule sysvar.
+ */
+export type EpochBounds = {
+  firstSlot: number;
+  slotsInEpoch: number;
+};
+
+/**
+ * Reads the first slot and length of `epoch` from the cluster's EpochSchedule,
+ * matching the program's default `EpochSchedule` slots-per-epoch source.
+ */
+export const getEpochBounds = async (
+  connection: anchor.web3.Connection,
+  epoch: number
+): Promise<EpochBounds> => {
+  const schedule = await connection.getEpochSchedule();
+  return {
+    firstSlot: schedule.getFirstSlotInEpoch(epoch),
+    slotsInEpoch: schedule.getSlotsInEpoch(epoch),
+  };
+};
+
+/**
+ * Fully expanded SandwichValidators account size for an epoch of `slotsInEpoch` slots,
+ * with one bit per `slotsPerBit` slots (13,564 bytes for a leader-window mainnet epoch).
+ * The program creates accounts at `min(INITIAL_ACCOUNT_SIZE, target)`.
+ */
+export const getTargetAccountSize = (slotsInEpoch: number, slotsPerBit = 1): number =>
+  SANDWICH_VALIDATORS_ACCOUNT_BASE_SIZE + Math.ceil(Math.ceil(slotsInEpoch / slotsPerBit) / 8);
+
+
+/**
+ * Derives the Program Derived Address (PDA) for the SandwichValidators account.
+ *
+ * @param multisigAuthority The public key of the multisig authority.
+ * @param epoch The epoch number (as a BN).
+ * @param programId The program ID.
+ * @returns An object containing the PDA public key (`pda`) and the bump seed (`bump`).
+ */
+export const getSandwichValidatorsPda = (
+  multisigAuthority: PublicKey,
+  epoch: anchor.BN,
+  programId: PublicKey
+): { pda: PublicKey; bump: number } => {
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SANDWICH_VALIDATORS_SEED_PREFIX),
+      multisigAuthority.toBuffer(),
+      epoch.toArrayLike(Buffer, "le", 8), // Epoch is u64, so 8 bytes little-endian
+    ],
+    programId
+  );
+  return { pda, bump };
+};
+
+/**
+ * Derives the legacy u16-seeded SandwichValidators PDA.
+ * Only meaningful for epochs up to 65535.
+ */
+export const getLegacySandwichValidatorsPda = (
+  multisigAuthority: PublicKey,
+  epoch: number,
+  programId: PublicKey
+): { pda: PublicKey; bump: number } => {
+  if (!Number.isInteger(epoch) || epoch < 0 || epoch > 65535) {
+    throw new Error(`Invalid legacy epoch: ${epoch}. Must be a u16 (0-65535)`);
+  }
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(LEGACY_SANDWICH_VALIDATORS_SEED_PREFIX),
+      multisigAuthority.toBuffer(),
+      new anchor.BN(epoch).toArrayLike(Buffer, "le", 2),
+    ],
+    programId
+  );
+  return { pda, bump };
+};
+
+/**
+ * Derives the PDA of the GatekeeperConfig singleton.
+ */
+export const getGatekeeperConfigPda = (
+  programId: PublicKey
+): { pda: PublicKey; bump: number } => {
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GATEKEEPER_CONFIG_SEED)],
+    programId
+  );
+  return { pda, bump };
+};
+
+/**
+ * Derives the PDA of the AuthorityRecord for a `multisigAuthority` namespace.
+ * The record exists only once the namespace has opted into authority rotation,
+ * but write instructions always expect its address.
+ */
+export const getAuthorityRecordPda = (
+  multisigAuthority: PublicKey,
+  programId: PublicKey
+): { pda: PublicKey; bump: number } => {
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(AUTHORITY_RECORD_SEED), multisigAuthority.toBuffer()],
+    programId
+  );
+  return { pda, bump };
+};
+
+/**
+ * Derives the PDA of the SignerSet for a `multisigAuthority` namespace.
+ */
+export const getSignerSetPda = (
+  multisigAuthority: PublicKey,
+  programId: PublicKey
+): { pda: PublicKey; bump: number } => {
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SIGNER_SET_SEED), multisigAuthority.toBuffer()],
+    programId
+  );
+  return { pda, bump };
+};
+
+/**
+ * Derives the PDA of the RoleTable for a `multisigAuthority` namespace.
+ */
+export const getRoleTablePda = (
+  multisigAuthority: PublicKey,
+  programId: PublicKey
+): { pda: PublicKey; bump: number } => {
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(ROLE_TABLE_SEED), multisigAuthority.toBuffer()],
+    programId
+  );
+  return { pda, bump };
+};
+
+/**
+ * Derives the PDA of the IntegratorProfile of an integrating program.
+ */
+export const getIntegratorProfilePda = (
+  integratorProgram: PublicKey,
+  programId: PublicKey
+): { pda: PublicKey; bump: number } => {
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(INTEGRATOR_PROFILE_SEED), integratorProgram.toBuffer()],
+    programId
+  );
+  return { pda, bump };
+};
+
+/**
+ * Derives the PDA of the curator Committee keyed by `namespace`. Pass the namespace as
+ * `committee` to `validateSandwichValidators` for k-of-n committee validation.
+ */
+export const getCommitteePda = (
+  namespace: PublicKey,
+  programId: PublicKey
+): { pda: PublicKey; bump: number } => {
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(COMMITTEE_SEED), namespace.toBuffer()],
+    programId
+  );
+  return { pda, bump };
+};
+
+/**
+ * Derives the namespace `member` writes its bitmaps under for `committee` (the Committee
+ * PDA). Only bitmaps under this namespace count towards the committee threshold.
+ */
+export const getCommitteeMemberNamespacePda = (
+  committee: PublicKey,
+  member: PublicKey,
+  programId: PublicKey
+): { pda: PublicKey; bump: number } => {
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(COMMITTEE_MEMBER_SEED), committee.toBuffer(), member.toBuffer()],
+    programId
+  );
+  return { pda, bump };
+};
+
+/**
+ * Derives the PDA of the ProviderRegistry singleton.
+ */
+export const getProviderRegistryPda = (
+  programId: PublicKey
+): { pda: PublicKey; bump: number } => {
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PROVIDER_REGISTRY_SEED)],
+    programId
+  );
+  return { pda, bump };
+};
+
+/**
+ * Derives the PDA of an integrator's IntegratorOverrides for an epoch.
+ */
+export const getIntegratorOverridesPda = (
+  owner: PublicKey,
+  epoch: anchor.BN,
+  programId: PublicKey
+): { pda: PublicKey; bump: number } => {
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(INTEGRATOR_OVERRIDES_SEED), owner.toBuffer(), epoch.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+  return { pda, bump };
+};
+
+/**
+ * Derives the event authority PDA passed to instructions that emit events through `emit_cpi!`.
+ */
+export const getEventAuthorityPda = (
+  programId: PublicKey
+): { pda: PublicKey; bump: number } => {
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(EVENT_AUTHORITY_SEED)],
+    programId
+  );
+  return { pda, bump };
+};
+
+/**
+ * Builds the remaining accounts carrying signer set member signatures.
+ */
+export const signerSetRemainingAccounts = (
+  members: PublicKey[] | undefined,
+  isWritable = false
+) =>
+  (members ?? []).map((pubkey) => ({ pubkey, isSigner: true, isWritable }));
+
+/**
+ * Derives the program data account of an upgradeable program.
+ */
+export const getProgramDataAddress = (programId: PublicKey): PublicKey => {
+  const [programData] = PublicKey.findProgramAddressSync(
+    [programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  );
+  return programData;
+};
+
+// --- Instruction Wrapper Functions ---
+
+/**
+ * Creates a MethodsBuilder to call the `setSandwichValidators` instruction.
+ * 
+ * **CRUD Operation: CREATE**
+ * This instruction only creates the account with initial 10KB size - no slots are set.
+ * Use `expandSandwichValidatorsBitmap` to expand to full size.
+ * Use `modifySandwichValidators` to gate/ungate slots.
+ */
+export const setSandwichValidators = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    multisigAuthority: PublicKey;
+    operator?: PublicKey; // Active authority after a rotation, or a role holder
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+    slotsPerBit?: number; // 1 (default) or SLOTS_PER_LEADER_WINDOW; fixed for the account's lifetime
+  }
+) => {
+  const slotsPerBit = args.slotsPerBit ?? 1;
+  if (slotsPerBit !== 1 && slotsPerBit !== SLOTS_PER_LEADER_WINDOW) {
+    throw new Error(`Invalid slotsPerBit: ${slotsPerBit}. Must be 1 or ${SLOTS_PER_LEADER_WINDOW}.`);
+  }
+
+  const { pda } = getSandwichValidatorsPda(
+    args.multisigAuthority,
+    new anchor.BN(args.epoch),
+    program.programId
+  );
+
+  return program.methods
+    .setSandwichValidators(new anchor.BN(args.epoch), slotsPerBit)
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      systemProgram: SystemProgram.programId,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `modifySandwichValidators` instruction.
+ * 
+ * **CRUD Operation: UPDATE**
+ * This instruction supports both gating slots (set to true) and ungating slots (set to false).
+ */
+export const modifySandwichValidators = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    slotsToGate?: anchor.BN[];
+    slotsToUngate?: anchor.BN[];
+    multisigAuthority: PublicKey;
+    legacy?: boolean; // A migrated account at the legacy u16-seeded address
+    operator?: PublicKey; // Active authority after a rotation, or a role holder
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+    signerSet?: boolean; // Authorise through the namespace's SignerSet
+    signerSetMembers?: PublicKey[]; // Members signing this transaction
+  }
+) => {
+  const slotsToGate = args.slotsToGate || [];
+  const slotsToUngate = args.slotsToUngate || [];
+
+  // Client-side validation to prevent encoding errors
+  if (slotsToGate.length > MAX_SLOTS_PER_TRANSACTION) {
+    throw new Error(`TooManySlots: Cannot gate more than ${MAX_SLOTS_PER_TRANSACTION} slots per transaction. Got ${slotsToGate.length}.`);
+  }
+
+  if (slotsToUngate.length > MAX_SLOTS_PER_TRANSACTION) {
+    throw new Error(`TooManySlots: Cannot ungate more than ${MAX_SLOTS_PER_TRANSACTION} slots per transaction. Got ${slotsToUngate.length}.`);
+  }
+
+  if (slotsToGate.length === 0 && slotsToUngate.length === 0) {
+    throw new Error(`No operation specified: Must provide either slotsToGate or slotsToUngate (or both).`);
+  }
+
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, args.epoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(args.epoch), program.programId);
+
+  return program.methods
+    .modifySandwichValidators(new anchor.BN(args.epoch), slotsToGate, slotsToUngate)
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      signerSet: args.signerSet
+        ? getSignerSetPda(args.multisigAuthority, program.programId).pda
+        : null,
+      systemProgram: SystemProgram.programId,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    })
+    .remainingAccounts(signerSetRemainingAccounts(args.signerSetMembers));
+};
+
+/**
+ * Creates a MethodsBuilder to call the `modifySandwichValidatorsRanges` instruction.
+ *
+ * **CRUD Operation: UPDATE**
+ * Gates or ungates whole runs of slots. Ranges within a list must not overlap, and no
+ * gate range may overlap an ungate range. Re-gating an already gated slot is not an error.
+ */
+export const modifySandwichValidatorsRanges = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    rangesToGate?: SlotRange[];
+    rangesToUngate?: SlotRange[];
+    multisigAuthority: PublicKey;
+    legacy?: boolean; // A migrated account at the legacy u16-seeded address
+    operator?: PublicKey; // Active authority after a rotation, or a role holder
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+    signerSet?: boolean; // Authorise through the namespace's SignerSet
+    signerSetMembers?: PublicKey[]; // Members signing this transaction
+  }
+) => {
+  const rangesToGate = args.rangesToGate || [];
+  const rangesToUngate = args.rangesToUngate || [];
+
+  if (rangesToGate.length + rangesToUngate.length > MAX_RANGES_PER_TRANSACTION) {
+    throw new Error(`TooManySlots: Cannot pass more than ${MAX_RANGES_PER_TRANSACTION} ranges per transaction. Got ${rangesToGate.length + rangesToUngate.length}.`);
+  }
+
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, args.epoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(args.epoch), program.programId);
+
+  return program.methods
+    .modifySandwichValidatorsRanges(new anchor.BN(args.epoch), rangesToGate, rangesToUngate)
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      signerSet: args.signerSet
+        ? getSignerSetPda(args.multisigAuthority, program.programId).pda
+        : null,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    })
+    .remainingAccounts(signerSetRemainingAccounts(args.signerSetMembers));
+};
+
+
+/**
+ * Encodes slots for `modifySandwichValidatorsCompact` as LEB128 gaps from `epochStart`:
+ * the first value is the first slot's offset into the epoch and each later value is the
+ * distance to the previous slot. Slots are sorted first; duplicates are rejected.
+ */
+export const encodeSlotOffsets = (slots: (anchor.BN | number)[], epochStart: number): Buffer => {
+  const offsets = slots
+    .map((slot) => new anchor.BN(slot).subn(epochStart).toNumber())
+    .sort((a, b) => a - b);
+  const bytes: number[] = [];
+  let previous: number | undefined;
+  for (const offset of offsets) {
+    if (offset < 0 || offset > 0xffff_ffff) {
+      throw new Error(`SlotOutOfRange: Slot ${offset + epochStart} is not within the epoch starting at ${epochStart}.`);
+    }
+    if (offset === previous) {
+      throw new Error(`DuplicateSlots: Slot ${offset + epochStart} appears more than once.`);
+    }
+    let value = previous === undefined ? offset : offset - previous;
+    while (value >= 0x80) {
+      bytes.push((value & 0x7f) | 0x80);
+      value = Math.floor(value / 0x80);
+    }
+    bytes.push(value);
+    previous = offset;
+  }
+  return Buffer.from(bytes);
+};
+
+/**
+ * Creates a MethodsBuilder to call the `modifySandwichValidatorsCompact` instruction.
+ *
+ * **CRUD Operation: UPDATE**
+ * Same semantics as `modifySandwichValidators`, with slots encoded relative to the epoch
+ * start so up to `MAX_COMPACT_SLOTS_PER_TRANSACTION` fit in one call. `epochStart` is the
+ * epoch's first slot (see `getEpochBounds`).
+ */
+export const modifySandwichValidatorsCompact = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    epochStart: number;
+    slotsToGate?: (anchor.BN | number)[];
+    slotsToUngate?: (anchor.BN | number)[];
+    multisigAuthority: PublicKey;
+    legacy?: boolean; // A migrated account at the legacy u16-seeded address
+    operator?: PublicKey; // Active authority after a rotation, or a role holder
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+    signerSet?: boolean; // Authorise through the namespace's SignerSet
+    signerSetMembers?: PublicKey[]; // Members signing this transaction
+  }
+) => {
+  const slotsToGate = args.slotsToGate || [];
+  const slotsToUngate = args.slotsToUngate || [];
+
+  if (slotsToGate.length + slotsToUngate.length > MAX_COMPACT_SLOTS_PER_TRANSACTION) {
+    throw new Error(`TooManySlots: Cannot modify more than ${MAX_COMPACT_SLOTS_PER_TRANSACTION} slots per transaction. Got ${slotsToGate.length + slotsToUngate.length}.`);
+  }
+
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, args.epoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(args.epoch), program.programId);
+
+  return program.methods
+    .modifySandwichValidatorsCompact(
+      new anchor.BN(args.epoch),
+      encodeSlotOffsets(slotsToGate, args.epochStart),
+      encodeSlotOffsets(slotsToUngate, args.epochStart)
+    )
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      signerSet: args.signerSet
+        ? getSignerSetPda(args.multisigAuthority, program.programId).pda
+        : null,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    })
+    .remainingAccounts(signerSetRemainingAccounts(args.signerSetMembers));
+};
+
+/**
+ * Creates a MethodsBuilder to call the `validateSandwichValidators` instruction.
+ */
+export const validateSandwichValidators = async (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    epoch?: number; // Optional: specify epoch manually for testing
+    legacy?: boolean; // Pass the legacy u16-seeded PDA (transition window only)
+    // Apply the calling program's IntegratorProfile; the PDAs of `authorities` are passed
+    // as remaining accounts. Only honoured when the instruction is invoked by `integrator`
+    integratorProfile?: { integrator: PublicKey; authorities: PublicKey[] };
+    // Apply the curator Committee keyed by `namespace`; the PDAs of its `members`' namespaces
+    // are passed as remaining accounts
+    committee?: { namespace: PublicKey; members: PublicKey[] };
+  }
+) => {
+  let targetEpoch: number;
+
+  if (args.epoch !== undefined) {
+    // Use manually specified epoch (for testing)
+    targetEpoch = args.epoch;
+  } else {
+    // Get epoch info from the connection
+    const epochInfo = await program.provider.connection.getEpochInfo("processed");
+
+    // In local test validators, the epoch is typically 0 regardless of slot
+    // The Clock sysvar will also report epoch 0
+    targetEpoch = epochInfo.epoch;
+  }
+
+  // Derive the PDA for the target epoch
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, targetEpoch, program.programId)
+    : getSandwichValidatorsPda(
+        args.multisigAuthority,
+        new anchor.BN(targetEpoch),
+        program.programId
+      );
+
+  const committeePda = args.committee
+    ? getCommitteePda(args.committee.namespace, program.programId).pda
+    : null;
+  const committeeNamespaces = (args.committee?.members ?? []).map(
+    (member) => getCommitteeMemberNamespacePda(committeePda, member, program.programId).pda
+  );
+
+  return program.methods
+    .validateSandwichValidators()
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+      instructionsSysvar: args.integratorProfile ? anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY : null,
+      integratorProfile: args.integratorProfile
+        ? getIntegratorProfilePda(args.integratorProfile.integrator, program.programId).pda
+        : null,
+      committee: committeePda,
+    })
+    .remainingAccounts(
+      [...(args.integratorProfile?.authorities ?? []), ...committeeNamespaces].map((authority) => ({
+        pubkey: getSandwichValidatorsPda(authority, new anchor.BN(targetEpoch), program.programId).pda,
+        isSigner: false,
+        isWritable: false,
+      }))
+    );
+};
+
+
+/**
+ * Creates a MethodsBuilder to call the `validateShadow` instruction, a dry run of
+ * `validateSandwichValidators`. It never fails with `SlotIsGated`; where validation would,
+ * it emits a `SlotGatedShadow` event instead (see `getShadowEvents`).
+ */
+export const validateShadow = async (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    epoch?: number; // Optional: specify epoch manually for testing
+    legacy?: boolean; // Pass the legacy u16-seeded PDA (transition window only)
+  }
+) => {
+  const targetEpoch =
+    args.epoch ?? (await program.provider.connection.getEpochInfo("processed")).epoch;
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, targetEpoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(targetEpoch), program.programId);
+
+  return program.methods
+    .validateShadow()
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+      eventAuthority: getEventAuthorityPda(program.programId).pda,
+      program: program.programId,
+    });
+};
+
+/**
+ * A `SlotGatedShadow` event: `validateShadow` found that validation would have blocked.
+ */
+export type SlotGatedShadowEvent = {
+  authority: PublicKey;
+  epoch: anchor.BN;
+  slot: anchor.BN;
+};
+
+/**
+ * Extracts the `SlotGatedShadow` events of a confirmed transaction. They are read from the
+ * program's `emit_cpi!` inner instructions, so log truncation does not lose them.
+ */
+export const getShadowEvents = (
+  program: Program<SaguaroGatekeeper>,
+  tx: anchor.web3.VersionedTransactionResponse
+): SlotGatedShadowEvent[] => {
+  const accountKeys = tx.transaction.message.getAccountKeys({
+    accountKeysFromLookups: tx.meta?.loadedAddresses,
+  });
+  const events: SlotGatedShadowEvent[] = [];
+  for (const inner of tx.meta?.innerInstructions ?? []) {
+    for (const ix of inner.instructions) {
+      if (!accountKeys.get(ix.programIdIndex)?.equals(program.programId)) continue;
+      const data = Buffer.from(anchor.utils.bytes.bs58.decode(ix.data));
+      if (!data.subarray(0, 8).equals(EVENT_IX_TAG_LE)) continue;
+      const event = program.coder.events.decode(data.subarray(8).toString("base64"));
+      if (event?.name === "slotGatedShadow") {
+        events.push(event.data as SlotGatedShadowEvent);
+      }
+    }
+  }
+  return events;
+};
+
+/**
+ * Creates a MethodsBuilder to call the `validateV2` instruction, the lower-cost variant of
+ * `validateSandwichValidators`. It needs no Clock sysvar, only the PDA, the authority and
+ * the config account.
+ */
+export const validateV2 = async (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    epoch?: number; // Optional: specify epoch manually for testing
+    legacy?: boolean; // Pass the legacy u16-seeded PDA (transition window only)
+  }
+) => {
+  const targetEpoch =
+    args.epoch ?? (await program.provider.connection.getEpochInfo("processed")).epoch;
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, targetEpoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(targetEpoch), program.programId);
+
+  return program.methods
+    .validateV2()
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `validateWithOverrides` instruction, which applies
+ * `overrideOwner`'s allow and deny ranges for the epoch on top of the authority's bitmap.
+ */
+export const validateWithOverrides = async (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    overrideOwner: PublicKey;
+    epoch?: number; // Optional: specify epoch manually for testing
+  }
+) => {
+  const targetEpoch =
+    args.epoch ?? (await program.provider.connection.getEpochInfo("processed")).epoch;
+  const epoch = new anchor.BN(targetEpoch);
+
+  return program.methods
+    .validateWithOverrides()
+    .accountsStrict({
+      sandwichValidators: getSandwichValidatorsPda(args.multisigAuthority, epoch, program.programId).pda,
+      multisigAuthority: args.multisigAuthority,
+      integratorOverrides: getIntegratorOverridesPda(args.overrideOwner, epoch, program.programId).pda,
+      overrideOwner: args.overrideOwner,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `validateStrict` instruction, which fails with
+ * `InvalidSandwichValidatorsPDA` on a wrong, foreign or malformed account instead of
+ * passing. With `requireConfigured` an epoch without a bitmap fails with
+ * `EpochNotConfigured` rather than applying the fail policy.
+ */
+export const validateStrict = async (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    requireConfigured?: boolean;
+    epoch?: number; // Optional: specify epoch manually for testing
+    sandwichValidators?: PublicKey; // Override the derived PDA
+  }
+) => {
+  const targetEpoch =
+    args.epoch ?? (await program.provider.connection.getEpochInfo("processed")).epoch;
+  const pda =
+    args.sandwichValidators ??
+    getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(targetEpoch), program.programId).pda;
+
+  return program.methods
+    .validateStrict(args.requireConfigured ?? false)
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `validateEpochBoundary` instruction. The PDAs for
+ * `epoch` (default: the current epoch) and the following epoch are passed as remaining
+ * accounts, so validation still applies if the transaction lands after the boundary.
+ */
+export const validateEpochBoundary = async (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    epoch?: number; // Optional: specify epoch manually for testing
+  }
+) => {
+  const epoch =
+    args.epoch ?? (await program.provider.connection.getEpochInfo("processed")).epoch;
+  const candidates = [epoch, epoch + 1].map((candidate) => ({
+    pubkey: getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(candidate), program.programId).pda,
+    isSigner: false,
+    isWritable: false,
+  }));
+
+  return program.methods
+    .validateEpochBoundary()
+    .accountsStrict({
+      multisigAuthority: args.multisigAuthority,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    })
+    .remainingAccounts(candidates);
+};
+
+/**
+ * Decoded return data of the `validateSources` instruction.
+ */
+export type SourceValidationResult = {
+  epoch: anchor.BN;
+  slot: anchor.BN;
+  flaggedMask: number; // Bit i is set when the i-th authority gates the slot
+  flaggedCount: number;
+  gated: boolean; // Whether flaggedCount reached the quorum
+};
+
+/**
+ * Creates a MethodsBuilder to call the `validateSources` instruction, which checks the
+ * current slot against several authorities' gate lists at once. The slot counts as gated
+ * when at least `quorum` authorities (default 1, i.e. any) gate it. With `enforce` it then
+ * fails with `SlotIsGated`; without it, call `.view()` to read which authorities flagged it.
+ */
+export const validateSources = async (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    authorities: PublicKey[];
+    quorum?: number;
+    enforce?: boolean;
+    epoch?: number; // Optional: specify epoch manually for testing
+  }
+) => {
+  if (args.authorities.length === 0 || args.authorities.length > MAX_VALIDATION_SOURCES) {
+    throw new Error(`Invalid source count: ${args.authorities.length}. Must be 1-${MAX_VALIDATION_SOURCES}.`);
+  }
+
+  const targetEpoch =
+    args.epoch ?? (await program.provider.connection.getEpochInfo("processed")).epoch;
+  const pairs = args.authorities.flatMap((authority) => [
+    { pubkey: authority, isSigner: false, isWritable: false },
+    {
+      pubkey: getSandwichValidatorsPda(authority, new anchor.BN(targetEpoch), program.programId).pda,
+      isSigner: false,
+      isWritable: false,
+    },
+  ]);
+
+  return program.methods
+    .validateSources(args.quorum ?? 1, args.enforce ?? true)
+    .accountsStrict({
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    })
+    .remainingAccounts(pairs);
+};
+
+/**
+ * Decoded return data of the `querySlots` instruction.
+ */
+export type SlotQueryResult = {
+  epoch: anchor.BN;
+  gatedMask: anchor.BN; // Bit i is set when the i-th queried slot is gated
+  nextUngatedSlot: anchor.BN | null;
+  fromBitmap: boolean; // False when the override mode or fail policy decided
+};
+
+/**
+ * Whether the `index`-th slot passed to `querySlots` is gated.
+ */
+export const isQueriedSlotGated = (result: SlotQueryResult, index: number): boolean =>
+  result.gatedMask.testn(index);
+
+/**
+ * Creates a MethodsBuilder to call the `querySlots` instruction. Unlike
+ * `validateSandwichValidators` it never fails on a gated slot; call `.view()` to read the
+ * `SlotQueryResult`, or CPI into it and read the return data. An empty `slots` list
+ * queries the current slot.
+ */
+export const querySlots = async (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    slots?: (anchor.BN | number)[];
+    epoch?: number; // Optional: specify epoch manually for testing
+    legacy?: boolean; // Pass the legacy u16-seeded PDA (transition window only)
+  }
+) => {
+  const slots = (args.slots || []).map((slot) => new anchor.BN(slot));
+  if (slots.length > MAX_QUERY_SLOTS) {
+    throw new Error(`TooManySlots: Cannot query more than ${MAX_QUERY_SLOTS} slots at once. Got ${slots.length}.`);
+  }
+
+  const targetEpoch =
+    args.epoch ?? (await program.provider.connection.getEpochInfo("processed")).epoch;
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, targetEpoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(targetEpoch), program.programId);
+
+  return program.methods
+    .querySlots(slots)
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `closeSandwichValidator` instruction.
+ */
+export const closeSandwichValidator = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    multisigAuthority: PublicKey;
+    operator?: PublicKey; // Active authority after a rotation, or a role holder
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+    signerSet?: boolean; // Authorise through the namespace's SignerSet
+    signerSetMembers?: PublicKey[]; // Members signing this transaction
+    rentRecipient?: PublicKey; // Active authority; defaults to the namespace key, or `operator` without a role table
+  }
+) => {
+  const { pda } = getSandwichValidatorsPda(
+    args.multisigAuthority,
+    new anchor.BN(args.epoch),
+    program.programId
+  );
+
+  return program.methods
+    .closeSandwichValidator(new anchor.BN(args.epoch))
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      signerSet: args.signerSet
+        ? getSignerSetPda(args.multisigAuthority, program.programId).pda
+        : null,
+      rentRecipient: args.rentRecipient ?? ((!args.roleTable && args.operator) || args.multisigAuthority),
+      systemProgram: SystemProgram.programId,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    })
+    .remainingAccounts(signerSetRemainingAccounts(args.signerSetMembers));
+};
+
+/**
+ * Creates a MethodsBuilder to call the `closeLegacySandwichValidator` instruction,
+ * which closes an account created under the legacy u16 seed scheme.
+ */
+export const closeLegacySandwichValidator = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    multisigAuthority: PublicKey;
+    operator?: PublicKey; // Active authority after a rotation, or a role holder
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+    signerSet?: boolean; // Authorise through the namespace's SignerSet
+    signerSetMembers?: PublicKey[]; // Members signing this transaction
+    rentRecipient?: PublicKey; // Active authority; defaults to the namespace key, or `operator` without a role table
+  }
+) => {
+  const { pda } = getLegacySandwichValidatorsPda(
+    args.multisigAuthority,
+    args.epoch,
+    program.programId
+  );
+
+  return program.methods
+    .closeLegacySandwichValidator(args.epoch)
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      signerSet: args.signerSet
+        ? getSignerSetPda(args.multisigAuthority, program.programId).pda
+        : null,
+      rentRecipient: args.rentRecipient ?? ((!args.roleTable && args.operator) || args.multisigAuthority),
+      systemProgram: SystemProgram.programId,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    })
+    .remainingAccounts(signerSetRemainingAccounts(args.signerSetMembers));
+};
+
+/**
+ * Creates a MethodsBuilder to call the `migrateSandwichValidators` instruction,
+ * which upgrades an account to the current layout version in place.
+ * Set `legacy` to migrate the account at the legacy u16-seeded address.
+ */
+export const migrateSandwichValidators = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    multisigAuthority: PublicKey;
+    legacy?: boolean;
+    operator?: PublicKey; // Active authority after a rotation, or a Provisioner paying extra rent
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+  }
+) => {
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, args.epoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(args.epoch), program.programId);
+
+  return program.methods
+    .migrateSandwichValidators(new anchor.BN(args.epoch))
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      systemProgram: SystemProgram.programId,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `sealSandwichValidators` instruction.
+ * `expectedHash` is the SHA-256 of the built bitmap (see `computeBitmapHash`).
+ */
+export const sealSandwichValidators = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    expectedHash: Buffer | number[];
+    multisigAuthority: PublicKey;
+    legacy?: boolean; // A migrated account at the legacy u16-seeded address
+    operator?: PublicKey; // Active authority after a rotation, or a Writer
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+    signerSet?: boolean; // Authorise through the namespace's SignerSet
+    signerSetMembers?: PublicKey[]; // Members signing this transaction
+  }
+) => {
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, args.epoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(args.epoch), program.programId);
+
+  return program.methods
+    .sealSandwichValidators(new anchor.BN(args.epoch), Array.from(args.expectedHash))
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      signerSet: args.signerSet
+        ? getSignerSetPda(args.multisigAuthority, program.programId).pda
+        : null,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    })
+    .remainingAccounts(signerSetRemainingAccounts(args.signerSetMembers, true));
+};
+
+/**
+ * Creates a MethodsBuilder to call the `unsealSandwichValidators` instruction.
+ */
+export const unsealSandwichValidators = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    multisigAuthority: PublicKey;
+    legacy?: boolean; // A migrated account at the legacy u16-seeded address
+    operator?: PublicKey; // Active authority after a rotation, or a Writer
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+    signerSet?: boolean; // Authorise through the namespace's SignerSet
+    signerSetMembers?: PublicKey[]; // Members signing this transaction
+  }
+) => {
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, args.epoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(args.epoch), program.programId);
+
+  return program.methods
+    .unsealSandwichValidators(new anchor.BN(args.epoch))
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      signerSet: args.signerSet
+        ? getSignerSetPda(args.multisigAuthority, program.programId).pda
+        : null,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    })
+    .remainingAccounts(signerSetRemainingAccounts(args.signerSetMembers, true));
+};
+
+/**
+ * Creates a MethodsBuilder to call the `expandSandwichValidatorsBitmap` instruction.
+ */
+export const expandSandwichValidatorsBitmap = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    multisigAuthority: PublicKey;
+    legacy?: boolean; // A migrated account at the legacy u16-seeded address
+    operator?: PublicKey; // Active authority after a rotation, or a role holder
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+  }
+) => {
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, args.epoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(args.epoch), program.programId);
+
+  return program.methods
+    .expandSandwichValidatorsBitmap(new anchor.BN(args.epoch))
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      systemProgram: SystemProgram.programId,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    });
+};
+
+// --- Utility Instruction Wrapper Functions ---
+
+
+/**
+ * Creates a MethodsBuilder to call the `appendDataSandwichValidatorsBitmap` instruction.
+ * 
+ * **Utility Operation**: Raw bitmap data writing
+ * This is a low-level utility for writing pre-computed bitmap data.
+ * Most users should use `modifySandwichValidators` instead.
+ */
+export const appendDataSandwichValidatorsBitmap = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    multisigAuthority: PublicKey;
+    legacy?: boolean; // A migrated account at the legacy u16-seeded address
+    operator?: PublicKey; // Active authority after a rotation, or a role holder
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+    data: Buffer;
+  }
+) => {
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, args.epoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(args.epoch), program.programId);
+
+  // Keep as Buffer for Borsh serialization
+  const dataArray = args.data;
+  
+  return program.methods
+    .appendDataSandwichValidatorsBitmap(new anchor.BN(args.epoch), dataArray)
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `writeBitmapChunk` instruction.
+ *
+ * **Utility Operation**: Raw bitmap data writing at a byte offset
+ * Writing the same chunk twice has no further effect, so chunked uploads can be retried
+ * safely. `mode` defaults to `BitmapWriteMode.Overwrite`.
+ */
+export const writeBitmapChunk = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    multisigAuthority: PublicKey;
+    legacy?: boolean; // A migrated account at the legacy u16-seeded address
+    operator?: PublicKey; // Active authority after a rotation, or a role holder
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+    offset: number; // Byte offset into the bitmap
+    data: Buffer;
+    mode?: (typeof BitmapWriteMode)[keyof typeof BitmapWriteMode];
+  }
+) => {
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, args.epoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(args.epoch), program.programId);
+
+  return program.methods
+    .writeBitmapChunk(new anchor.BN(args.epoch), args.offset, args.data, args.mode ?? BitmapWriteMode.Overwrite)
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    });
+};
+
+/**
+ * Run-length encodes `bitmap` for `writeBitmapRuns`, split into pieces of at most
+ * `maxBytes` encoded bytes. Each piece holds LEB128 run lengths alternating between
+ * ungated and gated slots, starting with ungated (a piece that starts gated begins with
+ * a zero-length run), and `offset` is the slot offset at which it starts. Together the
+ * pieces cover every bit of `bitmap`, so writing them all reproduces it exactly.
+ */
+export const encodeBitmapRuns = (
+  bitmap: Buffer,
+  maxBytes: number = RUN_BYTES_PER_TRANSACTION
+): { offset: number; runs: Buffer }[] => {
+  const totalBits = bitmap.length * 8;
+  const bit = (index: number) => (bitmap[index >> 3] >> (index & 7)) & 1;
+  const pushVarint = (out: number[], value: number) => {
+    while (value >= 0x80) {
+      out.push((value & 0x7f) | 0x80);
+      value = Math.floor(value / 0x80);
+    }
+    out.push(value);
+  };
+
+  const pieces: { offset: number; runs: Buffer }[] = [];
+  let piece: number[] = [];
+  let pieceOffset = 0;
+  let position = 0;
+  while (position < totalBits) {
+    const gated = bit(position);
+    let end = position + 1;
+    while (end < totalBits) {
+      // Skip whole bytes that continue the run
+      if ((end & 7) === 0 && bitmap[end >> 3] === (gated ? 0xff : 0)) {
+        end += 8;
+      } else if (bit(end) === gated) {
+        end += 1;
+      } else {
+        break;
+      }
+    }
+
+    const run: number[] = [];
+    pushVarint(run, end - position);
+    if (piece.length > 0 && piece.length + run.length > maxBytes) {
+      pieces.push({ offset: pieceOffset, runs: Buffer.from(piece) });
+      piece = [];
+      pieceOffset = position;
+    }
+    if (piece.length === 0 && gated) {
+      piece.push(0); // Every piece starts with an ungated run
+    }
+    piece.push(...run);
+    position = end;
+  }
+  if (piece.length > 0) {
+    pieces.push({ offset: pieceOffset, runs: Buffer.from(piece) });
+  }
+  return pieces;
+};
+
+/**
+ * Creates a MethodsBuilder to call the `writeBitmapRuns` instruction.
+ *
+ * **Utility Operation**: Bulk bitmap upload
+ * Overwrites the slots covered by `runs` (one piece from `encodeBitmapRuns`), starting at
+ * slot offset `offset` from the epoch start.
+ */
+export const writeBitmapRuns = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    multisigAuthority: PublicKey;
+    legacy?: boolean; // A migrated account at the legacy u16-seeded address
+    operator?: PublicKey; // Active authority after a rotation, or a role holder
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+    offset: number; // Slot offset from the epoch start
+    runs: Buffer;
+  }
+) => {
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, args.epoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(args.epoch), program.programId);
+
+  return program.methods
+    .writeBitmapRuns(new anchor.BN(args.epoch), args.offset, args.runs)
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `clearDataSandwichValidatorsBitmap` instruction.
+ * 
+ * **Utility Operation**: Clear all bitmap data (ungate all slots)
+ * This sets all slots in the bitmap to ungated (false).
+ */
+export const clearDataSandwichValidatorsBitmap = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    epoch: number;
+    multisigAuthority: PublicKey;
+    legacy?: boolean; // A migrated account at the legacy u16-seeded address
+    operator?: PublicKey; // Active authority after a rotation, or a role holder
+    roleTable?: boolean; // Authorise `operator` through the namespace's RoleTable
+    signerSet?: boolean; // Authorise through the namespace's SignerSet
+    signerSetMembers?: PublicKey[]; // Members signing this transaction
+  }
+) => {
+  const { pda } = args.legacy
+    ? getLegacySandwichValidatorsPda(args.multisigAuthority, args.epoch, program.programId)
+    : getSandwichValidatorsPda(args.multisigAuthority, new anchor.BN(args.epoch), program.programId);
+
+  return program.methods
+    .clearDataSandwichValidatorsBitmap(new anchor.BN(args.epoch))
+    .accountsStrict({
+      sandwichValidators: pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      roleTable: args.roleTable
+        ? getRoleTablePda(args.multisigAuthority, program.programId).pda
+        : null,
+      signerSet: args.signerSet
+        ? getSignerSetPda(args.multisigAuthority, program.programId).pda
+        : null,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+    })
+    .remainingAccounts(signerSetRemainingAccounts(args.signerSetMembers));
+};
+
+/**
+ * Settings accepted by `initializeGatekeeperConfig` and `updateGatekeeperConfig`.
+ */
+export type GatekeeperConfigParams = {
+  admin: PublicKey;
+  mode: typeof OperatingMode[keyof typeof OperatingMode];
+  slotsPerEpochSource: typeof SlotsPerEpochSource[keyof typeof SlotsPerEpochSource];
+  failPolicy: typeof FailPolicy[keyof typeof FailPolicy];
+  unsealedPolicy: typeof UnsealedPolicy[keyof typeof UnsealedPolicy];
+};
+
+/**
+ * Creates a MethodsBuilder to call the `initializeGatekeeperConfig` instruction.
+ * Must be signed by the program upgrade authority.
+ */
+export const initializeGatekeeperConfig = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    upgradeAuthority: PublicKey;
+    params: GatekeeperConfigParams;
+  }
+) => {
+  const { pda } = getGatekeeperConfigPda(program.programId);
+
+  return program.methods
+    .initializeGatekeeperConfig(args.params)
+    .accountsStrict({
+      gatekeeperConfig: pda,
+      upgradeAuthority: args.upgradeAuthority,
+      program: program.programId,
+      programData: getProgramDataAddress(program.programId),
+      systemProgram: SystemProgram.programId,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `updateGatekeeperConfig` instruction.
+ * Must be signed by the current config admin.
+ */
+export const updateGatekeeperConfig = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    admin: PublicKey;
+    params: GatekeeperConfigParams;
+  }
+) => {
+  const { pda } = getGatekeeperConfigPda(program.programId);
+
+  return program.methods
+    .updateGatekeeperConfig(args.params)
+    .accountsStrict({
+      gatekeeperConfig: pda,
+      admin: args.admin,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `setOverrideMode` instruction.
+ * Must be signed by the config admin. Only validations that pass the
+ * GatekeeperConfig account observe the override.
+ */
+export const setOverrideMode = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    admin: PublicKey;
+    overrideMode: typeof OverrideMode[keyof typeof OverrideMode];
+  }
+) => {
+  const { pda } = getGatekeeperConfigPda(program.programId);
+
+  return program.methods
+    .setOverrideMode(args.overrideMode)
+    .accountsStrict({
+      gatekeeperConfig: pda,
+      admin: args.admin,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `initializeAuthorityRecord` instruction.
+ * Must be signed by the namespace key (`multisigAuthority`).
+ */
+export const initializeAuthorityRecord = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    guardian?: PublicKey;
+    recoveryDelaySeconds: number;
+  }
+) => {
+  const { pda } = getAuthorityRecordPda(args.multisigAuthority, program.programId);
+
+  return program.methods
+    .initializeAuthorityRecord(args.guardian ?? null, new anchor.BN(args.recoveryDelaySeconds))
+    .accountsStrict({
+      authorityRecord: pda,
+      multisigAuthority: args.multisigAuthority,
+      systemProgram: SystemProgram.programId,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `proposeAuthority` instruction.
+ * Must be signed by the current active authority.
+ */
+export const proposeAuthority = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    authority: PublicKey;
+    newAuthority: PublicKey;
+  }
+) => {
+  const { pda } = getAuthorityRecordPda(args.multisigAuthority, program.programId);
+
+  return program.methods
+    .proposeAuthority(args.newAuthority)
+    .accountsStrict({
+      authorityRecord: pda,
+      authority: args.authority,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `acceptAuthority` instruction.
+ * Must be signed by the pending authority.
+ */
+export const acceptAuthority = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    pendingAuthority: PublicKey;
+  }
+) => {
+  const { pda } = getAuthorityRecordPda(args.multisigAuthority, program.programId);
+
+  return program.methods
+    .acceptAuthority()
+    .accountsStrict({
+      authorityRecord: pda,
+      pendingAuthority: args.pendingAuthority,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `cancelAuthorityProposal` instruction.
+ * Must be signed by the current active authority.
+ */
+export const cancelAuthorityProposal = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    authority: PublicKey;
+  }
+) => {
+  const { pda } = getAuthorityRecordPda(args.multisigAuthority, program.programId);
+
+  return program.methods
+    .cancelAuthorityProposal()
+    .accountsStrict({
+      authorityRecord: pda,
+      authority: args.authority,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `recoverAuthority` instruction.
+ * Must be signed by the guardian; acceptance is delayed by the recovery delay.
+ */
+export const recoverAuthority = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    guardian: PublicKey;
+    newAuthority: PublicKey;
+  }
+) => {
+  const { pda } = getAuthorityRecordPda(args.multisigAuthority, program.programId);
+
+  return program.methods
+    .recoverAuthority(args.newAuthority)
+    .accountsStrict({
+      authorityRecord: pda,
+      guardian: args.guardian,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `setAuthorityGuardian` instruction.
+ * Must be signed by the current active authority.
+ */
+export const setAuthorityGuardian = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    authority: PublicKey;
+    guardian?: PublicKey;
+    recoveryDelaySeconds: number;
+  }
+) => {
+  const { pda } = getAuthorityRecordPda(args.multisigAuthority, program.programId);
+
+  return program.methods
+    .setAuthorityGuardian(args.guardian ?? null, new anchor.BN(args.recoveryDelaySeconds))
+    .accountsStrict({
+      authorityRecord: pda,
+      authority: args.authority,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `initializeSignerSet` instruction.
+ * Must be signed by the active authority of the namespace.
+ */
+export const initializeSignerSet = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    operator?: PublicKey;
+    payer: PublicKey;
+    members: PublicKey[];
+    threshold: number;
+  }
+) => {
+  if (args.members.length > MAX_SIGNER_SET_MEMBERS) {
+    throw new Error(`Too many signer set members: ${args.members.length}. Maximum is ${MAX_SIGNER_SET_MEMBERS}.`);
+  }
 
+  return program.methods
+    .initializeSignerSet(args.members, args.threshold)
+    .accountsStrict({
+      signerSet: getSignerSetPda(args.multisigAuthority, program.programId).pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      payer: args.payer,
+      systemProgram: SystemProgram.programId,
+    });
+};
 
 /**
- * Derives the Program Derived Address (PDA) for the SandwichValidators account.
- *
- * @param multisigAuthority The public key of the multisig authority.
- * @param epoch The epoch number (as a BN).
- * @param programId The program ID.
- * @returns An object containing the PDA public key (`pda`) and the bump seed (`bump`).
+ * Creates a MethodsBuilder to call the `updateSignerSet` instruction.
+ * Must be signed by the active authority of the namespace.
  */
-export const getSandwichValidatorsPda = (
-  multisigAuthority: PublicKey,
-  epoch: anchor.BN,
-  programId: PublicKey
-): { pda: PublicKey; bump: number } => {
-  const [pda, bump] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(SANDWICH_VALIDATORS_SEED_PREFIX),
-      multisigAuthority.toBuffer(),
-      epoch.toBuffer("le", 2), // Epoch is u16, so 2 bytes little-endian
-    ],
-    programId
-  );
-  return { pda, bump };
-};
+export const updateSignerSet = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    multisigAuthority: PublicKey;
+    operator?: PublicKey;
+    members: PublicKey[];
+    threshold: number;
+  }
+) => {
+  if (args.members.length > MAX_SIGNER_SET_MEMBERS) {
+    throw new Error(`Too many signer set members: ${args.members.length}. Maximum is ${MAX_SIGNER_SET_MEMBERS}.`);
+  }
 
-// --- Instruction Wrapper Functions ---
+  return program.methods
+    .updateSignerSet(args.members, args.threshold)
+    .accountsStrict({
+      signerSet: getSignerSetPda(args.multisigAuthority, program.programId).pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+    });
+};
 
 /**
- * Creates a MethodsBuilder to call the `setSandwichValidators` instruction.
- * 
- * **CRUD Operation: CREATE**
- * This instruction only creates the account with initial 10KB size - no slots are set.
- * Use `expandSandwichValidatorsBitmap` to expand to full size.
- * Use `modifySandwichValidators` to gate/ungate slots.
+ * Creates a MethodsBuilder to call the `initializeRoleTable` instruction.
+ * Must be signed by the active authority of the namespace.
  */
-export const setSandwichValidators = (
+export const initializeRoleTable = (
   program: Program<SaguaroGatekeeper>,
   args: {
-    epoch: number;
     multisigAuthority: PublicKey;
+    operator?: PublicKey;
+    payer: PublicKey;
+    grants: RoleGrant[];
   }
 ) => {
-  const { pda } = getSandwichValidatorsPda(
-    args.multisigAuthority,
-    new anchor.BN(args.epoch),
-    program.programId
-  );
+  if (args.grants.length > MAX_ROLE_GRANTS) {
+    throw new Error(`Too many role grants: ${args.grants.length}. Maximum is ${MAX_ROLE_GRANTS}.`);
+  }
 
   return program.methods
-    .setSandwichValidators(args.epoch)
+    .initializeRoleTable(args.grants)
     .accountsStrict({
-      sandwichValidators: pda,
+      roleTable: getRoleTablePda(args.multisigAuthority, program.programId).pda,
       multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+      payer: args.payer,
       systemProgram: SystemProgram.programId,
     });
 };
 
 /**
- * Creates a MethodsBuilder to call the `modifySandwichValidators` instruction.
- * 
- * **CRUD Operation: UPDATE**
- * This instruction supports both gating slots (set to true) and ungating slots (set to false).
+ * Creates a MethodsBuilder to call the `updateRoleTable` instruction.
+ * Must be signed by the active authority of the namespace. Replaces all grants.
  */
-export const modifySandwichValidators = (
+export const updateRoleTable = (
   program: Program<SaguaroGatekeeper>,
   args: {
-    epoch: number;
-    slotsToGate?: anchor.BN[];
-    slotsToUngate?: anchor.BN[];
     multisigAuthority: PublicKey;
+    operator?: PublicKey;
+    grants: RoleGrant[];
   }
 ) => {
-  const slotsToGate = args.slotsToGate || [];
-  const slotsToUngate = args.slotsToUngate || [];
-
-  // Client-side validation to prevent encoding errors
-  if (slotsToGate.length > MAX_SLOTS_PER_TRANSACTION) {
-    throw new Error(`TooManySlots: Cannot gate more than ${MAX_SLOTS_PER_TRANSACTION} slots per transaction. Got ${slotsToGate.length}.`);
+  if (args.grants.length > MAX_ROLE_GRANTS) {
+    throw new Error(`Too many role grants: ${args.grants.length}. Maximum is ${MAX_ROLE_GRANTS}.`);
   }
 
-  if (slotsToUngate.length > MAX_SLOTS_PER_TRANSACTION) {
-    throw new Error(`TooManySlots: Cannot ungate more than ${MAX_SLOTS_PER_TRANSACTION} slots per transaction. Got ${slotsToUngate.length}.`);
-  }
+  return program.methods
+    .updateRoleTable(args.grants)
+    .accountsStrict({
+      roleTable: getRoleTablePda(args.multisigAuthority, program.programId).pda,
+      multisigAuthority: args.multisigAuthority,
+      authorityRecord: getAuthorityRecordPda(args.multisigAuthority, program.programId).pda,
+      operator: args.operator ?? null,
+    });
+};
 
-  if (slotsToGate.length === 0 && slotsToUngate.length === 0) {
-    throw new Error(`No operation specified: Must provide either slotsToGate or slotsToUngate (or both).`);
+/**
+ * Settings accepted by `initializeCommittee` and `updateCommittee`.
+ */
+export type CommitteeParams = {
+  governance: PublicKey;
+  members: PublicKey[]; // Curator keys; each publishes under its member namespace
+  threshold: number; // Members that must gate a slot
+};
+
+const checkCommitteeParams = (params: CommitteeParams) => {
+  if (params.members.length === 0 || params.members.length > MAX_COMMITTEE_MEMBERS) {
+    throw new Error(`Invalid member count: ${params.members.length}. Must be 1-${MAX_COMMITTEE_MEMBERS}.`);
+  }
+  if (params.threshold < 1 || params.threshold > params.members.length) {
+    throw new Error(`Invalid threshold: ${params.threshold}. Must be 1-${params.members.length}.`);
   }
+};
 
-  const { pda } = getSandwichValidatorsPda(
-    args.multisigAuthority,
-    new anchor.BN(args.epoch),
-    program.programId
-  );
+/**
+ * Creates a MethodsBuilder to call the `initializeCommittee` instruction.
+ * Must be signed by `namespace`, which keys the committee PDA.
+ */
+export const initializeCommittee = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    namespace: PublicKey;
+    payer?: PublicKey; // Defaults to `namespace`
+    params: CommitteeParams;
+  }
+) => {
+  checkCommitteeParams(args.params);
 
   return program.methods
-    .modifySandwichValidators(args.epoch, slotsToGate, slotsToUngate)
+    .initializeCommittee(args.params)
     .accountsStrict({
-      sandwichValidators: pda,
-      multisigAuthority: args.multisigAuthority,
+      committee: getCommitteePda(args.namespace, program.programId).pda,
+      namespace: args.namespace,
+      payer: args.payer ?? args.namespace,
       systemProgram: SystemProgram.programId,
     });
 };
 
+/**
+ * Creates a MethodsBuilder to call the `updateCommittee` instruction.
+ * Must be signed by the current committee governance. Replaces every setting.
+ */
+export const updateCommittee = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    namespace: PublicKey;
+    governance: PublicKey;
+    params: CommitteeParams;
+  }
+) => {
+  checkCommitteeParams(args.params);
+
+  return program.methods
+    .updateCommittee(args.params)
+    .accountsStrict({
+      committee: getCommitteePda(args.namespace, program.programId).pda,
+      governance: args.governance,
+    });
+};
 
 /**
- * Creates a MethodsBuilder to call the `validateSandwichValidators` instruction.
+ * Creates a MethodsBuilder to call the `joinCommittee` instruction.
+ * Must be signed by `member`, which pays for and becomes the active authority of its
+ * member namespace. Afterwards it writes with `multisigAuthority` set to that namespace
+ * (see `getCommitteeMemberNamespacePda`) and `operator: member`.
  */
-export const validateSandwichValidators = async (
+export const joinCommittee = (
   program: Program<SaguaroGatekeeper>,
   args: {
-    multisigAuthority: PublicKey;
-    epoch?: number; // Optional: specify epoch manually for testing
+    namespace: PublicKey; // The committee's namespace
+    member: PublicKey;
   }
 ) => {
-  let targetEpoch: number;
+  const committee = getCommitteePda(args.namespace, program.programId).pda;
+  const memberNamespace = getCommitteeMemberNamespacePda(committee, args.member, program.programId).pda;
 
-  if (args.epoch !== undefined) {
-    // Use manually specified epoch (for testing)
-    targetEpoch = args.epoch;
-  } else {
-    // Get epoch info from the connection
-    const epochInfo = await program.provider.connection.getEpochInfo("processed");
+  return program.methods
+    .joinCommittee()
+    .accountsStrict({
+      committee,
+      memberNamespace,
+      authorityRecord: getAuthorityRecordPda(memberNamespace, program.programId).pda,
+      member: args.member,
+      systemProgram: SystemProgram.programId,
+    });
+};
 
-    // In local test validators, the epoch is typically 0 regardless of slot
-    // The Clock sysvar will also report epoch 0
-    targetEpoch = epochInfo.epoch;
+/**
+ * Settings accepted by `initializeIntegratorProfile` and `updateIntegratorProfile`.
+ */
+export type IntegratorProfileParams = {
+  admin: PublicKey;
+  authorities: PublicKey[]; // Namespaces whose gate lists are honoured
+  combination: typeof SourceCombination[keyof typeof SourceCombination];
+  failPolicy: typeof FailPolicy[keyof typeof FailPolicy];
+  exemptRanges: SlotRange[]; // Absolute slots that always pass
+};
+
+const checkIntegratorProfileParams = (params: IntegratorProfileParams) => {
+  if (params.authorities.length === 0 || params.authorities.length > MAX_PROFILE_AUTHORITIES) {
+    throw new Error(`Invalid authority count: ${params.authorities.length}. Must be 1-${MAX_PROFILE_AUTHORITIES}.`);
   }
+  if (params.exemptRanges.length > MAX_PROFILE_EXEMPT_RANGES) {
+    throw new Error(`Too many exempt ranges: ${params.exemptRanges.length}. Maximum is ${MAX_PROFILE_EXEMPT_RANGES}.`);
+  }
+};
 
-  // Derive the PDA for the target epoch
-  const { pda } = getSandwichValidatorsPda(
-    args.multisigAuthority,
-    new anchor.BN(targetEpoch),
-    program.programId
-  );
+/**
+ * Creates a MethodsBuilder to call the `initializeIntegratorProfile` instruction.
+ * Must be signed by the upgrade authority of `integratorProgram`.
+ */
+export const initializeIntegratorProfile = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    integratorProgram: PublicKey;
+    upgradeAuthority: PublicKey;
+    params: IntegratorProfileParams;
+  }
+) => {
+  checkIntegratorProfileParams(args.params);
 
   return program.methods
-    .validateSandwichValidators()
+    .initializeIntegratorProfile(args.params)
     .accountsStrict({
-      sandwichValidators: pda,
-      multisigAuthority: args.multisigAuthority,
-      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      integratorProfile: getIntegratorProfilePda(args.integratorProgram, program.programId).pda,
+      integratorProgram: args.integratorProgram,
+      upgradeAuthority: args.upgradeAuthority,
+      programData: getProgramDataAddress(args.integratorProgram),
+      systemProgram: SystemProgram.programId,
     });
 };
 
+/**
+ * Creates a MethodsBuilder to call the `updateIntegratorProfile` instruction.
+ * Must be signed by the current profile admin. Replaces every setting.
+ */
+export const updateIntegratorProfile = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    integratorProgram: PublicKey;
+    admin: PublicKey;
+    params: IntegratorProfileParams;
+  }
+) => {
+  checkIntegratorProfileParams(args.params);
+
+  return program.methods
+    .updateIntegratorProfile(args.params)
+    .accountsStrict({
+      integratorProfile: getIntegratorProfilePda(args.integratorProgram, program.programId).pda,
+      admin: args.admin,
+    });
+};
 
 /**
- * Creates a MethodsBuilder to call the `closeSandwichValidator` instruction.
+ * Creates a MethodsBuilder to call the `setIntegratorOverrides` instruction, which creates
+ * or replaces `owner`'s allow and deny ranges for an epoch. Must be signed by `owner`.
  */
-export const closeSandwichValidator = (
+export const setIntegratorOverrides = (
   program: Program<SaguaroGatekeeper>,
   args: {
+    owner: PublicKey;
+    payer?: PublicKey; // Defaults to `owner`
     epoch: number;
-    multisigAuthority: PublicKey;
+    allow?: SlotRange[];
+    deny?: SlotRange[];
+    gatekeeperConfig?: PublicKey;
   }
 ) => {
-  const { pda } = getSandwichValidatorsPda(
-    args.multisigAuthority,
-    new anchor.BN(args.epoch),
-    program.programId
-  );
+  const allow = args.allow ?? [];
+  const deny = args.deny ?? [];
+  if (allow.length > MAX_OVERRIDE_RANGES || deny.length > MAX_OVERRIDE_RANGES) {
+    throw new Error(`Too many override ranges. Maximum is ${MAX_OVERRIDE_RANGES} per list.`);
+  }
+  const epoch = new anchor.BN(args.epoch);
 
   return program.methods
-    .closeSandwichValidator(args.epoch)
+    .setIntegratorOverrides(epoch, allow, deny)
     .accountsStrict({
-      sandwichValidators: pda,
-      multisigAuthority: args.multisigAuthority,
+      integratorOverrides: getIntegratorOverridesPda(args.owner, epoch, program.programId).pda,
+      owner: args.owner,
+      payer: args.payer ?? args.owner,
       systemProgram: SystemProgram.programId,
+      gatekeeperConfig: args.gatekeeperConfig ?? null,
     });
 };
 
 /**
- * Creates a MethodsBuilder to call the `expandSandwichValidatorsBitmap` instruction.
+ * Creates a MethodsBuilder to call the `closeIntegratorOverrides` instruction.
+ * Must be signed by `owner`, who receives the rent.
  */
-export const expandSandwichValidatorsBitmap = (
+export const closeIntegratorOverrides = (
   program: Program<SaguaroGatekeeper>,
   args: {
+    owner: PublicKey;
     epoch: number;
-    multisigAuthority: PublicKey;
   }
 ) => {
-  const { pda } = getSandwichValidatorsPda(
-    args.multisigAuthority,
-    new anchor.BN(args.epoch),
-    program.programId
-  );
+  const epoch = new anchor.BN(args.epoch);
 
   return program.methods
-    .expandSandwichValidatorsBitmap(args.epoch)
+    .closeIntegratorOverrides(epoch)
     .accountsStrict({
-      sandwichValidators: pda,
-      multisigAuthority: args.multisigAuthority,
-      systemProgram: SystemProgram.programId,
+      integratorOverrides: getIntegratorOverridesPda(args.owner, epoch, program.programId).pda,
+      owner: args.owner,
     });
 };
 
-// --- Utility Instruction Wrapper Functions ---
+/**
+ * Descriptive fields accepted by `registerProvider` and `updateProvider`.
+ */
+export type ProviderMetadata = {
+  name: string; // Unique within the registry, 1-32 UTF-8 bytes
+  metadataUri: string; // At most 128 UTF-8 bytes
+  firstEpoch: anchor.BN;
+  lastEpoch: anchor.BN | null; // null while the provider keeps publishing
+};
+
+/**
+ * A record of the ProviderRegistry, as returned by `fetchProviders`.
+ */
+export type ProviderRecord = ProviderMetadata & {
+  authority: PublicKey; // Namespace the provider publishes its bitmaps under
+  status: typeof ProviderStatus[keyof typeof ProviderStatus];
+};
 
+const checkProviderMetadata = (metadata: ProviderMetadata) => {
+  const nameLen = Buffer.byteLength(metadata.name, "utf8");
+  if (nameLen === 0 || nameLen > MAX_PROVIDER_NAME_LEN) {
+    throw new Error(`Invalid provider name length: ${nameLen}. Must be 1-${MAX_PROVIDER_NAME_LEN} bytes.`);
+  }
+  const uriLen = Buffer.byteLength(metadata.metadataUri, "utf8");
+  if (uriLen > MAX_PROVIDER_URI_LEN) {
+    throw new Error(`Invalid metadata URI length: ${uriLen}. Must be at most ${MAX_PROVIDER_URI_LEN} bytes.`);
+  }
+  if (metadata.lastEpoch && metadata.lastEpoch.lt(metadata.firstEpoch)) {
+    throw new Error(`Invalid epoch range: ${metadata.firstEpoch}-${metadata.lastEpoch}.`);
+  }
+};
 
 /**
- * Creates a MethodsBuilder to call the `appendDataSandwichValidatorsBitmap` instruction.
- * 
- * **Utility Operation**: Raw bitmap data writing
- * This is a low-level utility for writing pre-computed bitmap data.
- * Most users should use `modifySandwichValidators` instead.
+ * Creates a MethodsBuilder to call the `registerProvider` instruction.
+ * Must be signed by the config admin, who also pays for the registry on first use.
  */
-export const appendDataSandwichValidatorsBitmap = (
+export const registerProvider = (
   program: Program<SaguaroGatekeeper>,
   args: {
-    epoch: number;
-    multisigAuthority: PublicKey;
-    data: Buffer;
+    admin: PublicKey;
+    authority: PublicKey;
+    metadata: ProviderMetadata;
   }
 ) => {
-  const { pda } = getSandwichValidatorsPda(
-    args.multisigAuthority,
-    new anchor.BN(args.epoch),
-    program.programId
-  );
+  checkProviderMetadata(args.metadata);
 
-  // Keep as Buffer for Borsh serialization
-  const dataArray = args.data;
-  
   return program.methods
-    .appendDataSandwichValidatorsBitmap(args.epoch, dataArray)
+    .registerProvider(args.authority, args.metadata)
     .accountsStrict({
-      sandwichValidators: pda,
-      multisigAuthority: args.multisigAuthority,
+      providerRegistry: getProviderRegistryPda(program.programId).pda,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+      admin: args.admin,
+      systemProgram: SystemProgram.programId,
     });
 };
 
 /**
- * Creates a MethodsBuilder to call the `clearDataSandwichValidatorsBitmap` instruction.
- * 
- * **Utility Operation**: Clear all bitmap data (ungate all slots)
- * This sets all slots in the bitmap to ungated (false).
+ * Creates a MethodsBuilder to call the `updateProvider` instruction.
+ * Must be signed by the config admin or by the provider's authority.
  */
-export const clearDataSandwichValidatorsBitmap = (
+export const updateProvider = (
   program: Program<SaguaroGatekeeper>,
   args: {
-    epoch: number;
-    multisigAuthority: PublicKey;
+    signer: PublicKey;
+    authority: PublicKey;
+    metadata: ProviderMetadata;
   }
 ) => {
-  const { pda } = getSandwichValidatorsPda(
-    args.multisigAuthority,
-    new anchor.BN(args.epoch),
-    program.programId
-  );
+  checkProviderMetadata(args.metadata);
 
   return program.methods
-    .clearDataSandwichValidatorsBitmap(args.epoch)
+    .updateProvider(args.authority, args.metadata)
     .accountsStrict({
-      sandwichValidators: pda,
-      multisigAuthority: args.multisigAuthority,
+      providerRegistry: getProviderRegistryPda(program.programId).pda,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+      signer: args.signer,
+    });
+};
+
+/**
+ * Creates a MethodsBuilder to call the `deprecateProvider` instruction.
+ * Must be signed by the config admin or by the provider's authority. Permanent.
+ */
+export const deprecateProvider = (
+  program: Program<SaguaroGatekeeper>,
+  args: {
+    signer: PublicKey;
+    authority: PublicKey;
+  }
+) => {
+  return program.methods
+    .deprecateProvider(args.authority)
+    .accountsStrict({
+      providerRegistry: getProviderRegistryPda(program.programId).pda,
+      gatekeeperConfig: getGatekeeperConfigPda(program.programId).pda,
+      signer: args.signer,
     });
 };
 
+/**
+ * Fetches the records of the ProviderRegistry, or an empty list if no provider was
+ * ever registered. Deprecated providers are left out unless `includeDeprecated` is set.
+ */
+export const fetchProviders = async (
+  program: Program<SaguaroGatekeeper>,
+  options?: { includeDeprecated?: boolean }
+): Promise<ProviderRecord[]> => {
+  const registry = await program.account.providerRegistry.fetchNullable(
+    getProviderRegistryPda(program.programId).pda
+  );
+  const providers = (registry?.providers ?? []) as ProviderRecord[];
+  return options?.includeDeprecated
+    ? providers
+    : providers.filter((provider) => "active" in provider.status);
+};
+
+/**
+ * Looks up an active provider by its exact name, e.g. to obtain the authority to pass
+ * as `multisigAuthority`. Returns null if no active provider has that name.
+ */
+export const findProviderByName = async (
+  program: Program<SaguaroGatekeeper>,
+  name: string
+): Promise<ProviderRecord | null> => {
+  const providers = await fetchProviders(program);
+  return providers.find((provider) => provider.name === name) ?? null;
+};
+
 /**
  * Prepares instructions to create and populate a sandwich validators account.
- * Uses the streamlined approach: set_sandwich_validators + expand_bitmap + append_data.
+ * Uses the streamlined approach: set_sandwich_validators + expand_bitmap + append_data,
+ * or write_bitmap_runs with `runLength`, which needs far fewer write instructions for
+ * a sparse bitmap.
  */
 export const prepareLargeBitmapTransaction = async (
   program: Program<SaguaroGatekeeper>,
@@ -298,6 +2251,9 @@ export const prepareLargeBitmapTransaction = async (
     epoch: number;
     multisigAuthority: PublicKey;
     bitmapData: Buffer;
+    slotsInEpoch?: number; // Defaults to SLOTS_PER_EPOCH; see getEpochBounds
+    slotsPerBit?: number; // Granularity `bitmapData` was built with; see createBitmapForSlots
+    runLength?: boolean; // Upload with writeBitmapRuns instead of raw appends
   }
 ) => {
   const { pda } = getSandwichValidatorsPda(
@@ -310,10 +2266,12 @@ export const prepareLargeBitmapTransaction = async (
   const createIx = await setSandwichValidators(program, {
     epoch: args.epoch,
     multisigAuthority: args.multisigAuthority,
+    slotsPerBit: args.slotsPerBit,
   }).instruction();
 
-  // Step 2: Calculate number of expansion instructions needed
-  const totalExpansionNeeded = TARGET_ACCOUNT_SIZE - INITIAL_ACCOUNT_SIZE;
+  // Step 2: Calculate number of expansion instructions needed (one for leader-window accounts)
+  const targetSize = getTargetAccountSize(args.slotsInEpoch ?? SLOTS_PER_EPOCH, args.slotsPerBit);
+  const totalExpansionNeeded = Math.max(0, targetSize - INITIAL_ACCOUNT_SIZE);
   const numExpansions = Math.ceil(totalExpansionNeeded / MAX_REALLOC_SIZE);
   
   const expandInstructions = [];
@@ -327,7 +2285,17 @@ export const prepareLargeBitmapTransaction = async (
 
   // Step 3: Write data in chunks (if data provided)
   const writeInstructions = [];
-  if (args.bitmapData.length > 0) {
+  if (args.runLength) {
+    for (const { offset, runs } of encodeBitmapRuns(args.bitmapData)) {
+      const writeIx = await writeBitmapRuns(program, {
+        epoch: args.epoch,
+        multisigAuthority: args.multisigAuthority,
+        offset,
+        runs,
+      }).instruction();
+      writeInstructions.push(writeIx);
+    }
+  } else if (args.bitmapData.length > 0) {
     const chunkSize = 900; // Conservative chunk size for data writing
     
     for (let offset = 0; offset < args.bitmapData.length; offset += chunkSize) {
@@ -347,7 +2315,7 @@ export const prepareLargeBitmapTransaction = async (
     writeInstructions,
     sandwichValidatorsPda: pda,
     totalExpansions: numExpansions,
-    targetSize: TARGET_ACCOUNT_SIZE,
+    targetSize,
   };
 };
 
@@ -355,12 +2323,20 @@ export const prepareLargeBitmapTransaction = async (
  * Helper function to create a bitmap buffer for a given set of slots within an epoch.
  * @param slots Array of slot numbers to mark as gated
  * @param epoch The epoch number
+ * @param bounds Epoch boundaries from `getEpochBounds`; defaults to `epoch * SLOTS_PER_EPOCH`
+ * @param slotsPerBit Slots covered by each bit; `SLOTS_PER_LEADER_WINDOW` gates the whole
+ *   leader window of every listed slot
  * @returns Buffer containing the bitmap data
  */
-export const createBitmapForSlots = (slots: number[], epoch: number): Buffer => {
+export const createBitmapForSlots = (
+  slots: number[],
+  epoch: number,
+  bounds?: EpochBounds,
+  slotsPerBit = 1
+): Buffer => {
   // Input validation with overflow protection
-  if (!Number.isInteger(epoch) || epoch < 0 || epoch > 65535) {
-    throw new Error(`Invalid epoch: ${epoch}. Must be a u16 (0-65535)`);
+  if (!Number.isSafeInteger(epoch) || epoch < 0) {
+    throw new Error(`Invalid epoch: ${epoch}. Must be a non-negative integer`);
   }
   
   if (!Array.isArray(slots)) {
@@ -371,12 +2347,16 @@ export const createBitmapForSlots = (slots: number[], epoch: number): Buffer =>
     throw new Error(`Too many slots: ${slots.length}. Consider processing in smaller batches.`);
   }
   
-  // Calculate bitmap size needed (full epoch = 54,000 bytes)
-  const bitmapSize = FULL_BITMAP_SIZE_BYTES;
+  // Calculate bitmap size needed (full mainnet epoch = 54,000 bytes, or 13,500 with leader windows)
+  const { firstSlot, slotsInEpoch } = bounds ?? {
+    firstSlot: epoch * SLOTS_PER_EPOCH,
+    slotsInEpoch: SLOTS_PER_EPOCH,
+  };
+  const bitmapSize = Math.ceil(Math.ceil(slotsInEpoch / slotsPerBit) / 8);
   const bitmap = Buffer.alloc(bitmapSize, 0);
 
-  const epochStart = epoch * SLOTS_PER_EPOCH;
-  const epochEnd = epochStart + SLOTS_PER_EPOCH - 1;
+  const epochStart = firstSlot;
+  const epochEnd = epochStart + slotsInEpoch - 1;
   
   // Check for duplicates and validate ranges in a single pass
   const seenSlots = new Set<number>();
@@ -401,9 +2381,10 @@ export const createBitmapForSlots = (slots: number[], epoch: number): Buffer =>
     // Calculate slot offset within the epoch with overflow protection
     const slotOffset = slot - epochStart;
     
-    // Set the bit for this slot with bounds checking
-    const byteIndex = Math.floor(slotOffset / 8);
-    const bitIndex = slotOffset % 8;
+    // Set the bit covering this slot with bounds checking
+    const bit = Math.floor(slotOffset / slotsPerBit);
+    const byteIndex = Math.floor(bit / 8);
+    const bitIndex = bit % 8;
     
     if (byteIndex >= bitmapSize) {
       throw new Error(`Bitmap overflow: slot ${slot} requires byte index ${byteIndex}, but bitmap is only ${bitmapSize} bytes`);